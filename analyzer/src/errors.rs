@@ -1,4 +1,5 @@
 use gneurshk_parser::{BinaryOperator, types::DataType};
+use std::ops::Range;
 use thiserror::Error;
 
 #[derive(Error, Debug, Clone, PartialEq, Eq, Hash)]
@@ -6,9 +7,62 @@ pub enum SematicError {
     #[error("Function '{0}' not found")]
     FunctionNotFound(String),
 
+    #[error("Function '{0}' is already defined")]
+    FunctionRedefined(String),
+
+    #[error("Function '{0}' has more than one parameter named '{1}'")]
+    DuplicateParameter(String, String),
+
     #[error("Function '{0}' takes {1} arguments but {2} were given")]
     FunctionCallArgumentCountMismatch(String, usize, usize),
 
+    #[error("Function '{0}' is missing an argument for parameter '{1}'")]
+    MissingArgument(String, String, Range<usize>),
+
+    #[error("Function '{0}' does not have a parameter named '{1}'")]
+    UnknownNamedArgument(String, String, Range<usize>),
+
+    #[error("Function '{0}' was given more than one argument for parameter '{1}'")]
+    DuplicateArgument(String, String, Range<usize>),
+
+    #[error("Function call for '{0}' has positional arguments after named arguments")]
+    PositionalArgumentAfterNamed(String, Range<usize>),
+
+    #[error("Type parameter '{1}' of function '{0}' is inferred as both '{2}' and '{3}'")]
+    TypeArgumentMismatch(String, String, DataType, DataType),
+
+    #[error("Type parameter '{1}' of function '{0}' is declared more than once")]
+    DuplicateTypeParameter(String, String, Range<usize>),
+
+    #[error(
+        "Type parameter '{1}' of function '{0}' must be the type of a parameter so it can be inferred"
+    )]
+    UninferableTypeParameter(String, String, Range<usize>),
+
+    #[error("External function '{0}' can't have type parameters")]
+    GenericExternFunction(String, Range<usize>),
+
+    #[error("Type '{0}' is already defined")]
+    TypeAliasRedefined(String, Range<usize>),
+
+    #[error("Type alias '{0}' refers to itself")]
+    TypeAliasCycle(String, Range<usize>),
+
+    #[error("Type '{0}' does not exist{suggestion}", suggestion = format_suggestion(.1))]
+    UnknownType(String, Option<String>, Range<usize>),
+
+    #[error("Type alias '{0}' is a union, which is not supported yet")]
+    UnsupportedUnionType(String, Range<usize>),
+
+    #[error("Member access is not supported yet")]
+    UnsupportedMemberAccess(Option<Range<usize>>),
+
+    #[error("Default value for parameter '{1}' of function '{0}' should be '{2}' but is '{3}'")]
+    DefaultValueTypeMismatch(String, String, DataType, DataType),
+
+    #[error("Default value for parameter '{1}' of function '{0}' should only use literals")]
+    NonConstantDefaultValue(String, String, Range<usize>),
+
     #[error(
         "Function call for '{0}' type mismatch. Expected '{2}' for argument {1}, but found '{3}'"
     )]
@@ -17,6 +71,18 @@ pub enum SematicError {
     #[error("Function '{0}' return type mismatch.")]
     FunctionReturnTypeMismatch(String),
 
+    #[error("Function '{0}' does not return a value on every path")]
+    FunctionMissingReturn(String),
+
+    #[error("Function '{0}' should return a value of type '{1}' but returns '{2}'")]
+    ReturnValueTypeMismatch(String, DataType, DataType),
+
+    #[error("Function '{0}' must return a value of type '{1}'")]
+    ReturnValueMissing(String, DataType),
+
+    #[error("Function '{0}' does not have a return type, so it cannot return a value")]
+    UnexpectedReturnValue(String),
+
     #[error("Variable '{0}' not found")]
     VariableNotFound(String),
 
@@ -29,8 +95,25 @@ pub enum SematicError {
     #[error("No type or value provided for variable declaration")]
     NoTypeOrValueProvided,
 
+    #[error("Variable '{0}' is already declared in this scope")]
+    VariableRedeclared(String),
+
+    #[error("Variable '{0}' might not have been initialized")]
+    UninitializedVariable(String, Range<usize>),
+
     #[error("Variable '{0}' is not able to be modified")]
-    VariableUnmodifiable(String),
+    VariableUnmodifiable(String, Range<usize>, Range<usize>),
+
+    #[error("Parameter '{0}' is not able to be modified, mark it as 'mut' to allow it")]
+    ParameterUnmodifiable(String, Range<usize>, Range<usize>),
+
+    #[error(
+        "Variable '{0}' is captured by an anonymous function, so it can't be modified inside it"
+    )]
+    CapturedVariableUnmodifiable(String, Range<usize>, Range<usize>),
+
+    #[error("Only variables and their members can be assigned to")]
+    InvalidAssignmentTarget(Range<usize>),
 
     #[error("The if statement requires an else that evaluates to the expected type")]
     IfMissingElse,
@@ -41,6 +124,38 @@ pub enum SematicError {
     #[error("If condition must evaluate to a boolean value")]
     BooleanOnlyIfCondition,
 
+    #[error("If let can only unwrap an optional value, but got '{0}'")]
+    IfLetRequiresOptional(DataType, Range<usize>),
+
+    #[error("None can only be used as an optional value, but '{0}' is expected")]
+    NoneRequiresOptional(DataType, Range<usize>),
+
+    #[error("The type of none can't be inferred, give it an optional type")]
+    UntypedNone(Range<usize>),
+
+    #[error("The type of '{0}(...)' can't be inferred, give it a Result type")]
+    UntypedResult(String, Range<usize>),
+
+    #[error("'{0}(...)' creates a Result, but '{1}' is expected")]
+    ResultRequired(String, DataType, Range<usize>),
+
+    #[error("The Result returned by '{0}' must be used or passed on with '?'")]
+    UnusedResult(String, Range<usize>),
+
+    #[error("The '?' operator can only be used on a Result or optional value, but got '{0}'")]
+    TryRequiresResult(DataType, Range<usize>),
+
+    #[error(
+        "Function '{0}' can't pass on '{1}' with the '?' operator, it must return a matching Result or optional"
+    )]
+    TryReturnTypeMismatch(String, DataType, Range<usize>),
+
+    #[error("Function '{0}' can't be used as a value because it is generic or variadic")]
+    FunctionValueNotAllowed(String, Range<usize>),
+
+    #[error("'{0}' can't be called because it is a '{1}' and not a function")]
+    NotCallable(String, DataType, Range<usize>),
+
     #[error("Break statement cannot belong outside of a loop")]
     BreakOutsideLoop,
 
@@ -52,10 +167,179 @@ pub enum SematicError {
 
     #[error("Cannot apply the negative operator to a non-numeric type")]
     CannotUseNegative,
+
+    #[error("Unknown lint '{0}'")]
+    UnknownLint(String),
+
+    #[error("Annotation '@{0}' only accepts lint names")]
+    InvalidLintAnnotation(String),
+
+    #[error("{0}")]
+    DeniedWarning(SematicWarning),
+
+    #[error("Unknown annotation '@{0}'")]
+    UnknownAnnotation(String, Range<usize>),
+
+    #[error("Annotation '@{0}' takes {1} arguments but {2} were given")]
+    AnnotationArgumentCountMismatch(String, String, usize, Range<usize>),
+
+    #[error("Annotation '@{0}' expects a string argument")]
+    AnnotationExpectedString(String, Range<usize>),
+
+    #[error("Annotations '@{0}' and '@{1}' cannot be used together")]
+    ConflictingAnnotations(String, String, Range<usize>),
+
+    #[error("Test function '{0}' cannot have parameters or a return type")]
+    InvalidTestFunction(String, Range<usize>),
+
+    #[error(
+        "Function '{0}' is missing a body, only '@extern' functions can be declared without one"
+    )]
+    FunctionMissingBody(String, Range<usize>),
+
+    #[error("External function '{0}' cannot have a body")]
+    ExternFunctionWithBody(String, Range<usize>),
+
+    #[error(
+        "Function '{0}' cannot be variadic, only '@extern' functions can accept extra arguments"
+    )]
+    VariadicFunctionNotExtern(String, Range<usize>),
+}
+
+impl SematicError {
+    /// Returns the location in the source code that caused the error, if it is known
+    pub fn span(&self) -> Option<Range<usize>> {
+        match self {
+            SematicError::UninitializedVariable(_, span)
+            | SematicError::VariableUnmodifiable(_, span, _)
+            | SematicError::ParameterUnmodifiable(_, span, _)
+            | SematicError::CapturedVariableUnmodifiable(_, span, _)
+            | SematicError::InvalidAssignmentTarget(span)
+            | SematicError::IfLetRequiresOptional(_, span)
+            | SematicError::NoneRequiresOptional(_, span)
+            | SematicError::UntypedNone(span)
+            | SematicError::UntypedResult(_, span)
+            | SematicError::ResultRequired(_, _, span)
+            | SematicError::UnusedResult(_, span)
+            | SematicError::TryRequiresResult(_, span)
+            | SematicError::TryReturnTypeMismatch(_, _, span)
+            | SematicError::FunctionValueNotAllowed(_, span)
+            | SematicError::NotCallable(_, _, span)
+            | SematicError::DuplicateTypeParameter(_, _, span)
+            | SematicError::UninferableTypeParameter(_, _, span)
+            | SematicError::GenericExternFunction(_, span)
+            | SematicError::TypeAliasRedefined(_, span)
+            | SematicError::TypeAliasCycle(_, span)
+            | SematicError::UnknownType(_, _, span)
+            | SematicError::UnsupportedUnionType(_, span)
+            | SematicError::MissingArgument(_, _, span)
+            | SematicError::NonConstantDefaultValue(_, _, span)
+            | SematicError::UnknownNamedArgument(_, _, span)
+            | SematicError::DuplicateArgument(_, _, span)
+            | SematicError::PositionalArgumentAfterNamed(_, span)
+            | SematicError::UnknownAnnotation(_, span)
+            | SematicError::AnnotationArgumentCountMismatch(_, _, _, span)
+            | SematicError::AnnotationExpectedString(_, span)
+            | SematicError::ConflictingAnnotations(_, _, span)
+            | SematicError::InvalidTestFunction(_, span)
+            | SematicError::FunctionMissingBody(_, span)
+            | SematicError::ExternFunctionWithBody(_, span)
+            | SematicError::VariadicFunctionNotExtern(_, span) => Some(span.clone()),
+            SematicError::UnsupportedMemberAccess(span) => span.clone(),
+            SematicError::DeniedWarning(warning) => warning.span(),
+            _ => None,
+        }
+    }
+
+    /// Returns the location where the binding involved in the error was declared, if any
+    pub fn declaration_span(&self) -> Option<Range<usize>> {
+        match self {
+            SematicError::VariableUnmodifiable(_, _, declaration)
+            | SematicError::ParameterUnmodifiable(_, _, declaration)
+            | SematicError::CapturedVariableUnmodifiable(_, _, declaration) => {
+                Some(declaration.clone())
+            }
+            _ => None,
+        }
+    }
 }
 
 #[derive(Error, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SematicWarning {
     #[error("Variable '{0}' is never used")]
     UnusedVariable(String),
+
+    #[error("Parameter '{1}' of function '{0}' is never used")]
+    UnusedParameter(String, String),
+
+    #[error("Function '{0}' is never called")]
+    UnusedFunction(String),
+
+    #[error("Import '{0}' is never used")]
+    UnusedImport(String),
+
+    #[error("Variable '{0}' shadows a variable from an outer scope")]
+    ShadowedVariable(String),
+
+    #[error("Unreachable code in function '{0}'")]
+    UnreachableCode(String, Option<Range<usize>>),
+
+    #[error("Condition is always '{0}'")]
+    ConstantCondition(bool, Option<Range<usize>>),
+
+    #[error("Loop in function '{0}' never breaks or returns")]
+    InfiniteLoop(String),
+
+    #[error("Comparing '{0}' with itself always has the same result")]
+    SelfComparison(String, Range<usize>),
+
+    #[error("Division by zero")]
+    DivisionByZero(Range<usize>),
+
+    #[error("Integer literal '{0}' does not fit in type '{1}'")]
+    IntegerLiteralOverflow(String, DataType, Range<usize>),
+
+    #[error("Statement has no effect")]
+    NoEffect(Option<Range<usize>>),
+}
+
+impl SematicWarning {
+    /// Returns the location in the source code that caused the warning, if it is known
+    pub fn span(&self) -> Option<Range<usize>> {
+        match self {
+            SematicWarning::UnreachableCode(_, span)
+            | SematicWarning::ConstantCondition(_, span)
+            | SematicWarning::NoEffect(span) => span.clone(),
+            SematicWarning::SelfComparison(_, span)
+            | SematicWarning::DivisionByZero(span)
+            | SematicWarning::IntegerLiteralOverflow(_, _, span) => Some(span.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the name used to configure the level of the warning
+    pub fn lint_name(&self) -> &'static str {
+        match self {
+            SematicWarning::UnusedVariable(_) => "unused_variable",
+            SematicWarning::UnusedParameter(_, _) => "unused_parameter",
+            SematicWarning::UnusedFunction(_) => "unused_function",
+            SematicWarning::UnusedImport(_) => "unused_import",
+            SematicWarning::ShadowedVariable(_) => "shadowed_variable",
+            SematicWarning::UnreachableCode(_, _) => "unreachable_code",
+            SematicWarning::ConstantCondition(_, _) => "constant_condition",
+            SematicWarning::InfiniteLoop(_) => "infinite_loop",
+            SematicWarning::SelfComparison(_, _) => "self_comparison",
+            SematicWarning::DivisionByZero(_) => "division_by_zero",
+            SematicWarning::IntegerLiteralOverflow(_, _, _) => "integer_literal_overflow",
+            SematicWarning::NoEffect(_) => "no_effect",
+        }
+    }
+}
+
+/// Formats the name suggested for a misspelled name
+fn format_suggestion(suggestion: &Option<String>) -> String {
+    match suggestion {
+        Some(suggestion) => format!(", did you mean '{suggestion}'?"),
+        None => String::new(),
+    }
 }
@@ -0,0 +1,272 @@
+use crate::{errors::SematicError, function::FunctionAnalyzer, options::LintLevel};
+use gneurshk_parser::{Annotation, Expression};
+
+/// How many arguments a built-in annotation accepts
+enum ArgumentCount {
+    None,
+    Optional,
+    AtLeastOne,
+}
+
+/// Gets the arguments accepted by a built-in annotation, or None if the annotation is unknown
+fn get_argument_count(name: &str) -> Option<ArgumentCount> {
+    match name {
+        "inline" | "noinline" | "test" => Some(ArgumentCount::None),
+        "extern" | "export" => Some(ArgumentCount::Optional),
+        _ if LintLevel::from_name(name).is_some() => Some(ArgumentCount::AtLeastOne),
+        _ => None,
+    }
+}
+
+impl<'a> FunctionAnalyzer<'a> {
+    pub(crate) fn analyze_annotations(&mut self) {
+        let function = self.function_declaration.clone();
+        let span = function.span.clone();
+
+        for Annotation { name, args } in &function.annotations {
+            // Check if the annotation is built-in
+            let Some(count) = get_argument_count(name) else {
+                self.errors
+                    .push(SematicError::UnknownAnnotation(name.clone(), span.clone()));
+                continue;
+            };
+
+            // Check the number of arguments
+            let expected = match count {
+                ArgumentCount::None if !args.is_empty() => Some("0"),
+                ArgumentCount::Optional if args.len() > 1 => Some("0 or 1"),
+                ArgumentCount::AtLeastOne if args.is_empty() => Some("at least 1"),
+                _ => None,
+            };
+
+            if let Some(expected) = expected {
+                self.errors
+                    .push(SematicError::AnnotationArgumentCountMismatch(
+                        name.clone(),
+                        expected.to_string(),
+                        args.len(),
+                        span.clone(),
+                    ));
+                continue;
+            }
+
+            // Symbol names must be strings
+            if matches!(count, ArgumentCount::Optional)
+                && let Some(arg) = args.first()
+                && !matches!(arg, Expression::String(_))
+            {
+                self.errors.push(SematicError::AnnotationExpectedString(
+                    name.clone(),
+                    span.clone(),
+                ));
+            }
+        }
+
+        // Check for annotations that contradict each other
+        for (first, second) in [("inline", "noinline"), ("extern", "export")] {
+            if function.has_annotation(first) && function.has_annotation(second) {
+                self.errors.push(SematicError::ConflictingAnnotations(
+                    first.to_string(),
+                    second.to_string(),
+                    span.clone(),
+                ));
+            }
+        }
+
+        // Only external functions are declared without a body
+        let is_extern = function.has_annotation("extern");
+
+        match (&function.block, is_extern) {
+            (Some(_), true) => self.errors.push(SematicError::ExternFunctionWithBody(
+                function.name.clone(),
+                span.clone(),
+            )),
+            (None, false) => self.errors.push(SematicError::FunctionMissingBody(
+                function.name.clone(),
+                span.clone(),
+            )),
+            _ => {}
+        }
+
+        if function.variadic && !is_extern {
+            self.errors.push(SematicError::VariadicFunctionNotExtern(
+                function.name.clone(),
+                span.clone(),
+            ));
+        }
+
+        // Tests are called by the test runner, so they cannot take or return anything
+        if function.has_annotation("test")
+            && (!function.params.is_empty() || function.return_type.is_some())
+        {
+            self.errors.push(SematicError::InvalidTestFunction(
+                function.name.clone(),
+                span.clone(),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        errors::SematicError,
+        program::{AnalyzedProgram, ProgramAnalyzer},
+    };
+    use gneurshk_lexer::lex;
+    use gneurshk_parser::parse;
+
+    /// Helper function for testing the analyze function
+    fn lex_then_analyze(input: &'static str) -> AnalyzedProgram {
+        let tokens = lex(input).expect("Failed to lex");
+
+        match parse(&mut tokens.clone()) {
+            Ok(result) => ProgramAnalyzer::analyze(result),
+            Err(e) => panic!("Parsing error: {e}"),
+        }
+    }
+
+    #[test]
+    fn builtin_annotations() {
+        let source = include_str!("../../tests/annotations/builtin_annotations.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(analyzed.get_all_errors(), vec![]);
+        assert_eq!(analyzed.get_all_warnings(), vec![]);
+    }
+
+    #[test]
+    fn unknown_annotation() {
+        let source = include_str!("../../tests/annotations/unknown_annotation.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::UnknownAnnotation("fast".to_string(), 11..15)]
+        );
+    }
+
+    #[test]
+    fn argument_count_mismatch() {
+        let source = include_str!("../../tests/annotations/argument_count_mismatch.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![
+                SematicError::AnnotationArgumentCountMismatch(
+                    "inline".to_string(),
+                    "0".to_string(),
+                    1,
+                    16..20
+                ),
+                SematicError::AnnotationArgumentCountMismatch(
+                    "export".to_string(),
+                    "0 or 1".to_string(),
+                    2,
+                    72..82
+                ),
+                SematicError::AnnotationArgumentCountMismatch(
+                    "allow".to_string(),
+                    "at least 1".to_string(),
+                    0,
+                    125..129
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn export_expects_string() {
+        let source = include_str!("../../tests/annotations/export_expects_string.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::AnnotationExpectedString(
+                "export".to_string(),
+                18..23
+            )]
+        );
+    }
+
+    #[test]
+    fn conflicting_annotations() {
+        let source = include_str!("../../tests/annotations/conflicting_annotations.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::ConflictingAnnotations(
+                "inline".to_string(),
+                "noinline".to_string(),
+                23..27
+            )]
+        );
+    }
+
+    #[test]
+    fn invalid_test_function() {
+        let source = include_str!("../../tests/annotations/invalid_test_function.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::InvalidTestFunction(
+                "kiwi".to_string(),
+                11..15
+            )]
+        );
+    }
+
+    #[test]
+    fn extern_functions() {
+        let source = include_str!("../../tests/annotations/extern_functions.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(analyzed.get_all_errors(), vec![]);
+        assert_eq!(analyzed.get_all_warnings(), vec![]);
+    }
+
+    #[test]
+    fn missing_body() {
+        let source = include_str!("../../tests/annotations/missing_body.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::FunctionMissingBody(
+                "apple".to_string(),
+                5..10
+            )]
+        );
+    }
+
+    #[test]
+    fn extern_with_body() {
+        let source = include_str!("../../tests/annotations/extern_with_body.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::ExternFunctionWithBody(
+                "banana".to_string(),
+                13..19
+            )]
+        );
+    }
+
+    #[test]
+    fn variadic_not_extern() {
+        let source = include_str!("../../tests/annotations/variadic_not_extern.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::VariadicFunctionNotExtern(
+                "cherry".to_string(),
+                5..11
+            )]
+        );
+    }
+}
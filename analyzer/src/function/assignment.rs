@@ -1,5 +1,6 @@
-use crate::{errors::SematicError, function::FunctionAnalyzer};
+use crate::{errors::SematicError, function::FunctionAnalyzer, scope::Variable};
 use gneurshk_parser::{Expression, MemberExpressionBase, types::DataType};
+use std::ops::Range;
 
 impl<'a> FunctionAnalyzer<'a> {
     pub(crate) fn analyze_assignment(
@@ -7,7 +8,19 @@ impl<'a> FunctionAnalyzer<'a> {
         member: MemberExpressionBase,
         value: Expression,
     ) -> Option<DataType> {
-        let value_type = self.analyze_expression(value);
+        // Values assigned to a variable can take on its optional type
+        let expected_type = match &member {
+            MemberExpressionBase::Identifier(identifier) => self
+                .scope
+                .get_variable(&identifier.name)
+                .map(|variable| variable.data_type),
+            _ => None,
+        };
+
+        let value_type = match expected_type {
+            Some(expected_type) => self.analyze_expression_as(value, &expected_type),
+            None => self.analyze_expression(value),
+        };
 
         match member {
             MemberExpressionBase::Identifier(identifier) => {
@@ -23,11 +36,7 @@ impl<'a> FunctionAnalyzer<'a> {
                     }
                 };
 
-                if !variable.mutable {
-                    self.program_analyzer
-                        .errors
-                        .push(SematicError::VariableUnmodifiable(name.clone()));
-                }
+                self.check_mutable(&variable, identifier.span);
 
                 if let Some(value_type) = value_type
                     && value_type != variable.data_type
@@ -47,9 +56,127 @@ impl<'a> FunctionAnalyzer<'a> {
 
                 Some(variable.data_type)
             }
-            MemberExpressionBase::FunctionCall(_) | MemberExpressionBase::MemberAccess(_) => {
-                todo!()
+            MemberExpressionBase::FunctionCall(function_call) => {
+                self.program_analyzer
+                    .errors
+                    .push(SematicError::InvalidAssignmentTarget(function_call.span));
+
+                None
+            }
+            MemberExpressionBase::MemberAccess(member_access) => {
+                // Find the binding that owns the member
+                let mut base = *member_access.base;
+                let mut is_static = member_access.is_static;
+
+                while let MemberExpressionBase::MemberAccess(inner) = base {
+                    is_static = inner.is_static;
+                    base = *inner.base;
+                }
+
+                match base {
+                    // Members of static paths like `Module::value` don't belong to a binding
+                    MemberExpressionBase::Identifier(_) if is_static => {}
+                    MemberExpressionBase::Identifier(identifier) => {
+                        let Some(variable) = self.scope.get_variable(&identifier.name) else {
+                            self.program_analyzer
+                                .errors
+                                .push(SematicError::VariableNotFound(identifier.name));
+                            return None;
+                        };
+
+                        // Members can only be changed through a mutable binding
+                        self.check_mutable(&variable, identifier.span);
+
+                        if let Some(var_mut) = self.scope.get_mut_variable(&identifier.name) {
+                            var_mut.used = true;
+                        }
+                    }
+                    // Members of temporary values can always be changed
+                    MemberExpressionBase::FunctionCall(function_call) => {
+                        self.analyze_function_call(function_call);
+                    }
+                    MemberExpressionBase::MemberAccess(_) => unreachable!(),
+                }
+
+                // TODO: return the type of the member once structs are supported
+                None
             }
         }
     }
+
+    fn check_mutable(&mut self, variable: &Variable, span: Range<usize>) {
+        // Anonymous functions only hold a copy of the variables they capture
+        if self.scope.is_captured(&variable.name) {
+            self.program_analyzer
+                .errors
+                .push(SematicError::CapturedVariableUnmodifiable(
+                    variable.name.clone(),
+                    span,
+                    variable.span.clone(),
+                ));
+
+            return;
+        }
+
+        if variable.mutable {
+            return;
+        }
+
+        let error = if variable.parameter {
+            SematicError::ParameterUnmodifiable(variable.name.clone(), span, variable.span.clone())
+        } else {
+            SematicError::VariableUnmodifiable(variable.name.clone(), span, variable.span.clone())
+        };
+
+        self.program_analyzer.errors.push(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        errors::SematicError,
+        program::{AnalyzedProgram, ProgramAnalyzer},
+    };
+    use gneurshk_lexer::lex;
+    use gneurshk_parser::parse;
+
+    /// Helper function for testing the analyze function
+    fn lex_then_analyze(input: &'static str) -> AnalyzedProgram {
+        let tokens = lex(input).expect("Failed to lex");
+
+        match parse(&mut tokens.clone()) {
+            Ok(result) => ProgramAnalyzer::analyze(result),
+            Err(e) => panic!("Parsing error: {e}"),
+        }
+    }
+
+    #[test]
+    fn immutable_bindings() {
+        let source = include_str!("../../tests/assignments/immutable_bindings.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![
+                SematicError::ParameterUnmodifiable("apples".to_string(), 57..63, 9..15),
+                SematicError::VariableUnmodifiable("kiwi".to_string(), 149..153, 136..140),
+            ]
+        );
+    }
+
+    #[test]
+    fn member_of_constant() {
+        let source = include_str!("../../tests/assignments/member_of_constant.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::VariableUnmodifiable(
+                "basket".to_string(),
+                39..45,
+                24..30
+            )]
+        );
+    }
 }
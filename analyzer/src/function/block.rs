@@ -1,13 +1,45 @@
-use crate::{errors::SematicWarning, function::FunctionAnalyzer};
-use gneurshk_parser::{Block, types::DataType};
+use crate::{
+    errors::{SematicError, SematicWarning},
+    function::FunctionAnalyzer,
+};
+use gneurshk_parser::{Block, Stmt, types::DataType};
 
 impl<'a> FunctionAnalyzer<'a> {
     pub(crate) fn analyze_block(&mut self, block: Block) -> Option<DataType> {
         self.enter_new_scope();
 
+        // Blocks that are unreachable as a whole have already been reported
+        let mut reported_unreachable = self.diverged;
+
         let mut last_value = None;
-        for stmt in block.body {
+        let statement_count = block.body.len();
+
+        for (i, stmt) in block.body.into_iter().enumerate() {
+            // Warn about the first statement that comes after a terminator
+            if self.diverged && !reported_unreachable {
+                self.warnings.push(SematicWarning::UnreachableCode(
+                    self.function_declaration.name.clone(),
+                    stmt.span(),
+                ));
+
+                reported_unreachable = true;
+            }
+
+            // Results of calls that aren't the value of the block would be ignored
+            let call = match &stmt {
+                Stmt::FunctionCall(call) if i + 1 < statement_count => {
+                    Some((call.name.clone(), call.span.clone()))
+                }
+                _ => None,
+            };
+
             last_value = self.analyze_statement(stmt);
+
+            if let (Some((name, span)), Some(DataType::Result { .. })) = (call, &last_value) {
+                self.program_analyzer
+                    .errors
+                    .push(SematicError::UnusedResult(name, span));
+            }
         }
 
         // Check for unused variables before exiting the scope
@@ -0,0 +1,255 @@
+use crate::{errors::SematicError, function::FunctionAnalyzer, scope::Variable};
+use gneurshk_parser::{
+    Expression, FunctionDeclaration, FunctionParam, Identifier, Lambda, NamedArgument,
+    types::{DataType, FunctionType},
+};
+use std::ops::Range;
+
+impl<'a> FunctionAnalyzer<'a> {
+    /// Analyzes an anonymous function, whose body is checked like the body of a function
+    pub(crate) fn analyze_lambda(&mut self, lambda: Lambda) -> Option<DataType> {
+        let Lambda {
+            params,
+            return_type,
+            block,
+            span,
+        } = lambda;
+
+        // Replace the type aliases in the signature with the types they refer to
+        let params = params
+            .into_iter()
+            .map(|param| FunctionParam {
+                data_type: self.resolve_type(&param.data_type),
+                ..param
+            })
+            .collect::<Vec<_>>();
+        let return_type = return_type.map(|return_type| self.resolve_type(&return_type));
+
+        for param in &params {
+            self.check_type_exists(&param.data_type, param.span.clone());
+        }
+
+        if let Some(return_type) = &return_type {
+            self.check_type_exists(return_type, span);
+        }
+
+        let function_type = FunctionType {
+            params: params.iter().map(|param| param.data_type.clone()).collect(),
+            return_type: return_type.clone().map(Box::new),
+        };
+
+        // The body returns from the anonymous function, but calls still belong to the enclosing one
+        let declaration = FunctionDeclaration {
+            params: params.clone(),
+            return_type,
+            block: None,
+            ..self.function_declaration.clone()
+        };
+
+        let outer_declaration = std::mem::replace(&mut self.function_declaration, declaration);
+        let outer_loops = std::mem::take(&mut self.loop_stack);
+        let outer_diverged = std::mem::replace(&mut self.diverged, false);
+
+        self.enter_new_scope();
+        self.scope.closure = true;
+
+        for param in params {
+            if self.scope.has_local_variable(&param.name) {
+                self.errors.push(SematicError::DuplicateParameter(
+                    self.function_declaration.name.clone(),
+                    param.name.clone(),
+                ));
+            }
+
+            // Parameters often only exist to match a function type, so they aren't reported as unused
+            self.scope.set_variable(
+                param.name.clone(),
+                Variable {
+                    name: param.name,
+                    data_type: param.data_type,
+                    mutable: param.mutable,
+                    used: true,
+                    initialized: true,
+                    parameter: true,
+                    span: param.span,
+                },
+            );
+        }
+
+        let mut block = *block;
+
+        // Values at the end of the body that need the return type are checked like a return statement
+        if function_type.return_type.is_some() {
+            block.return_untyped_value();
+        }
+
+        let implicit_return = self.analyze_block(block);
+        self.check_implicit_return(implicit_return);
+
+        self.exit_scope();
+
+        self.function_declaration = outer_declaration;
+        self.loop_stack = outer_loops;
+        self.diverged = outer_diverged;
+
+        Some(DataType::Function(function_type))
+    }
+
+    /// Analyzes a call through a variable that holds a function value
+    pub(crate) fn analyze_closure_call(
+        &mut self,
+        name: String,
+        args: Vec<Expression>,
+        named_args: Vec<NamedArgument>,
+        span: Range<usize>,
+    ) -> Option<DataType> {
+        let data_type = self.analyze_identifier(Identifier {
+            name: name.clone(),
+            span: span.clone(),
+        })?;
+
+        let DataType::Function(function_type) = data_type else {
+            self.errors
+                .push(SematicError::NotCallable(name, data_type, span));
+
+            for arg in args {
+                self.analyze_expression(arg);
+            }
+
+            for arg in named_args {
+                self.analyze_expression(arg.value);
+            }
+
+            return None;
+        };
+
+        // Function values don't keep the names of their parameters
+        for NamedArgument {
+            name: arg_name,
+            value,
+            span,
+            ..
+        } in named_args
+        {
+            self.analyze_expression(value);
+
+            self.program_analyzer
+                .errors
+                .push(SematicError::UnknownNamedArgument(
+                    name.clone(),
+                    arg_name,
+                    span,
+                ));
+        }
+
+        let params = &function_type.params;
+
+        if args.len() != params.len() {
+            self.program_analyzer
+                .errors
+                .push(SematicError::FunctionCallArgumentCountMismatch(
+                    name.clone(),
+                    params.len(),
+                    args.len(),
+                ));
+        }
+
+        for (i, arg) in args.into_iter().enumerate() {
+            let Some(expected) = params.get(i) else {
+                self.analyze_expression(arg);
+                continue;
+            };
+
+            if let Some(actual) = self.analyze_expression_as(arg, expected)
+                && &actual != expected
+            {
+                self.program_analyzer
+                    .errors
+                    .push(SematicError::FunctionCallArgumentMismatch(
+                        name.clone(),
+                        i + 1,
+                        expected.clone(),
+                        actual,
+                    ));
+            }
+        }
+
+        function_type.return_type.map(|return_type| *return_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        errors::SematicError,
+        program::{AnalyzedProgram, ProgramAnalyzer},
+    };
+    use gneurshk_lexer::lex;
+    use gneurshk_parser::{
+        parse,
+        types::{DataType, FunctionType},
+    };
+
+    /// Helper function for testing the analyze function
+    fn lex_then_analyze(input: &'static str) -> AnalyzedProgram {
+        let tokens = lex(input).expect("Failed to lex");
+
+        match parse(&mut tokens.clone()) {
+            Ok(result) => ProgramAnalyzer::analyze(result),
+            Err(e) => panic!("Parsing error: {e}"),
+        }
+    }
+
+    #[test]
+    fn valid_closures() {
+        let source = include_str!("../../tests/closures/valid_closures.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(analyzed.get_all_errors(), vec![]);
+        assert_eq!(analyzed.get_all_warnings(), vec![]);
+    }
+
+    #[test]
+    fn invalid_closures() {
+        let source = include_str!("../../tests/closures/invalid_closures.iv");
+        let analyzed = lex_then_analyze(source);
+
+        let function_type = |return_type| {
+            DataType::Function(FunctionType {
+                params: vec![DataType::Int32],
+                return_type: Some(Box::new(return_type)),
+            })
+        };
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![
+                SematicError::CapturedVariableUnmodifiable(
+                    "cherries".to_string(),
+                    153..161,
+                    65..73
+                ),
+                SematicError::FunctionCallArgumentMismatch(
+                    "eat".to_string(),
+                    1,
+                    DataType::Int32,
+                    DataType::Boolean
+                ),
+                SematicError::FunctionCallArgumentCountMismatch("eat".to_string(), 1, 2),
+                SematicError::UnknownNamedArgument(
+                    "eat".to_string(),
+                    "amount".to_string(),
+                    285..291
+                ),
+                SematicError::FunctionCallArgumentCountMismatch("eat".to_string(), 1, 0),
+                SematicError::AssignmentTypeMismatch(
+                    "wrong".to_string(),
+                    function_type(DataType::Boolean),
+                    function_type(DataType::Int32)
+                ),
+                SematicError::NotCallable("count".to_string(), DataType::Int32, 259..267),
+                SematicError::FunctionValueNotAllowed("pick".to_string(), 353..357),
+            ]
+        );
+    }
+}
@@ -1,5 +1,15 @@
-use crate::function::FunctionAnalyzer;
-use gneurshk_parser::{BinaryExpression, Expression, FunctionCall, Identifier, types::DataType};
+use crate::{errors::SematicError, function::FunctionAnalyzer};
+use gneurshk_parser::{BinaryExpression, Expression, types::DataType};
+
+/// Checks if a value of the actual type can be used where the expected type is required
+///
+/// Values are wrapped automatically when they are used as an optional of their own type
+pub(crate) fn is_assignable(expected: &DataType, actual: &DataType) -> bool {
+    match expected {
+        DataType::Optional(inner) => actual == expected || actual == inner.as_ref(),
+        _ => actual == expected,
+    }
+}
 
 impl<'a> FunctionAnalyzer<'a> {
     pub(crate) fn analyze_expression(&mut self, expr: Expression) -> Option<DataType> {
@@ -13,16 +23,119 @@ impl<'a> FunctionAnalyzer<'a> {
             Expression::Integer(..) => self.analyze_integer(),
             Expression::Float(..) => self.analyze_float(),
             Expression::Boolean(..) => self.analyze_boolean(),
-            Expression::Identifier(Identifier { name, .. }) => self.analyze_identifier(name),
-            Expression::FunctionCall(FunctionCall { name, args, .. }) => {
-                self.analyze_function_call(name, args)
-            }
+            Expression::None(none) => self.analyze_none(none),
+            Expression::Identifier(identifier) => self.analyze_identifier(identifier),
+            Expression::FunctionCall(call) => self.analyze_function_call(call),
             Expression::UnaryExpression(unary_expr) => self.analyze_unary_expression(unary_expr),
-            _ => {
-                println!("expression: {expr:?}");
+            Expression::Cast(cast) => self.analyze_cast(cast),
+            Expression::Try(try_expr) => self.analyze_try(try_expr),
+            Expression::Lambda(lambda) => self.analyze_lambda(lambda),
+            Expression::Block(block) => self.analyze_block(block),
+            Expression::IfStatement(if_stmt) => self.analyze_if(if_stmt),
+            Expression::MemberAccess(member_access) => self.analyze_member_access(member_access),
+        }
+    }
+
+    /// Analyzes an expression that is used where a value of the expected type is required
+    ///
+    /// Values that fit the expected type take it on, so `none` and wrapped values become optionals
+    pub(crate) fn analyze_expression_as(
+        &mut self,
+        expr: Expression,
+        expected: &DataType,
+    ) -> Option<DataType> {
+        match expr {
+            Expression::None(none) => {
+                if !matches!(expected, DataType::Optional(_)) {
+                    self.errors.push(SematicError::NoneRequiresOptional(
+                        expected.clone(),
+                        none.span,
+                    ));
+                }
 
-                todo!();
+                // The mismatch is already reported, so treat none as the expected type
+                return Some(expected.clone());
+            }
+            Expression::FunctionCall(call) if call.is_result_constructor() => {
+                return self.analyze_result_constructor(call, expected);
             }
+            _ => {}
+        }
+
+        let actual = self.analyze_expression(expr)?;
+
+        if is_assignable(expected, &actual) {
+            Some(expected.clone())
+        } else {
+            Some(actual)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        errors::SematicError,
+        program::{AnalyzedProgram, ProgramAnalyzer},
+    };
+    use gneurshk_lexer::lex;
+    use gneurshk_parser::{BinaryOperator, parse, types::DataType};
+
+    /// Helper function for testing the analyze function
+    fn lex_then_analyze(input: &'static str) -> AnalyzedProgram {
+        let tokens = lex(input).expect("Failed to lex");
+
+        match parse(&mut tokens.clone()) {
+            Ok(result) => ProgramAnalyzer::analyze(result),
+            Err(e) => panic!("Parsing error: {e}"),
         }
     }
+
+    #[test]
+    fn wrapped_values() {
+        let source = include_str!("../../tests/optionals/wrapped_values.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(analyzed.get_all_errors(), vec![]);
+    }
+
+    #[test]
+    fn unchecked_values() {
+        let source = include_str!("../../tests/optionals/unchecked_values.iv");
+        let analyzed = lex_then_analyze(source);
+
+        let optional_int = DataType::Optional(Box::new(DataType::Int32));
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![
+                SematicError::AssignmentTypeMismatch(
+                    "total".to_string(),
+                    DataType::Int32,
+                    optional_int.clone()
+                ),
+                SematicError::UnsupportedOperator(
+                    optional_int,
+                    BinaryOperator::Add,
+                    DataType::Int32
+                ),
+                SematicError::NoneRequiresOptional(DataType::Int32, 93..97),
+                SematicError::UntypedNone(114..118),
+            ]
+        );
+    }
+
+    #[test]
+    fn member_access() {
+        let source = include_str!("../../tests/members/member_access.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![
+                SematicError::UnsupportedMemberAccess(Some(39..45)),
+                SematicError::UnsupportedMemberAccess(Some(72..78)),
+            ]
+        );
+    }
 }
@@ -1,12 +1,35 @@
-use crate::{errors::SematicError, function::FunctionAnalyzer};
-use gneurshk_parser::{Expression, types::DataType};
+use crate::{
+    errors::SematicError,
+    function::{
+        FunctionAnalyzer,
+        generics::{TypeArguments, get_type_param, substitute_type},
+    },
+    scope::Function,
+};
+use gneurshk_parser::{Expression, FunctionCall, NamedArgument, types::DataType};
+use std::collections::HashSet;
 
 impl<'a> FunctionAnalyzer<'a> {
-    pub(crate) fn analyze_function_call(
-        &mut self,
-        name: String,
-        args: Vec<Expression>,
-    ) -> Option<DataType> {
+    pub(crate) fn analyze_function_call(&mut self, call: FunctionCall) -> Option<DataType> {
+        // Results can only be created where the Result type is known
+        if call.is_result_constructor() {
+            self.errors
+                .push(SematicError::UntypedResult(call.name, call.span));
+
+            for arg in call.args {
+                self.analyze_expression(arg);
+            }
+
+            return None;
+        }
+
+        let FunctionCall {
+            name,
+            args,
+            named_args,
+            span,
+        } = call;
+
         // Handle built-in functions
         if matches!(name.as_str(), "println" | "print") {
             // Analyze arguments and ignore types for these functions
@@ -14,50 +37,178 @@ impl<'a> FunctionAnalyzer<'a> {
                 self.analyze_expression(arg);
             }
 
+            // Built-in functions don't have named parameters
+            for NamedArgument {
+                name: arg_name,
+                value,
+                span,
+                ..
+            } in named_args
+            {
+                self.analyze_expression(value);
+
+                self.program_analyzer
+                    .errors
+                    .push(SematicError::UnknownNamedArgument(
+                        name.clone(),
+                        arg_name,
+                        span,
+                    ));
+            }
+
+            return None;
+        }
+
+        // Counts the heap objects that are alive, so programs can check that nothing leaks
+        if name == "live_allocations" {
+            if !args.is_empty() || !named_args.is_empty() {
+                self.program_analyzer
+                    .errors
+                    .push(SematicError::FunctionCallArgumentCountMismatch(
+                        name,
+                        0,
+                        args.len() + named_args.len(),
+                    ));
+            }
+
+            for arg in args {
+                self.analyze_expression(arg);
+            }
+
+            for arg in named_args {
+                self.analyze_expression(arg.value);
+            }
+
+            return Some(DataType::Int32);
+        }
+
+        // Stops the program with a message, assert only does so when its condition is false
+        if matches!(name.as_str(), "panic" | "assert") {
+            self.analyze_panic_call(name, args, named_args);
+
             return None;
         }
 
+        // Variables holding function values shadow the declared functions
+        if self.scope.get_variable(&name).is_some() {
+            return self.analyze_closure_call(name, args, named_args, span);
+        }
+
+        // Remember the call so functions that are never called can be reported
+        self.program_analyzer
+            .calls
+            .entry(self.function_declaration.name.clone())
+            .or_default()
+            .insert(name.clone());
+
         if let Some(function) = self.program_analyzer.functions.get(&name).cloned() {
-            // Check for correct number of arguments
-            if args.len() != function.params.len() {
+            let params = &function.params;
+
+            // Positional arguments cannot come after named ones
+            if let Some(named_arg) = named_args.iter().find(|arg| arg.position < args.len()) {
+                self.program_analyzer
+                    .errors
+                    .push(SematicError::PositionalArgumentAfterNamed(
+                        name.clone(),
+                        named_arg.span.clone(),
+                    ));
+            }
+
+            // Check for too many arguments, variadic functions accept extra ones
+            if args.len() > params.len() && !function.variadic {
                 self.program_analyzer
                     .errors
                     .push(SematicError::FunctionCallArgumentCountMismatch(
                         name.clone(),
-                        function.params.len(),
+                        params.len(),
                         args.len(),
                     ));
             }
-            // Check for correct types of arguments
-            else {
-                let mut arg_types = Vec::with_capacity(args.len());
-                for arg in args {
-                    arg_types.push(self.analyze_expression(arg)?);
+
+            // Check for correct types of the positional arguments
+            let mut provided = HashSet::new();
+            let mut type_args = TypeArguments::new();
+
+            for (i, arg) in args.into_iter().enumerate() {
+                let actual = self.analyze_argument(&function, i, arg);
+
+                if let Some(param) = params.get(i) {
+                    provided.insert(param.name.clone());
+
+                    self.check_argument_type(&function, &name, i, actual, &mut type_args);
+                }
+            }
+
+            // Match the named arguments to their parameters
+            for NamedArgument {
+                name: arg_name,
+                value,
+                span: arg_span,
+                ..
+            } in named_args
+            {
+                let position = params.iter().position(|param| param.name == arg_name);
+                let actual = match position {
+                    Some(i) => self.analyze_argument(&function, i, value),
+                    None => self.analyze_expression(value),
+                };
+
+                let Some(i) = position else {
+                    self.program_analyzer
+                        .errors
+                        .push(SematicError::UnknownNamedArgument(
+                            name.clone(),
+                            arg_name,
+                            arg_span,
+                        ));
+                    continue;
+                };
+
+                if !provided.insert(arg_name.clone()) {
+                    self.program_analyzer
+                        .errors
+                        .push(SematicError::DuplicateArgument(
+                            name.clone(),
+                            arg_name,
+                            arg_span,
+                        ));
+                    continue;
                 }
 
-                let expected_types = function
-                    .params
-                    .iter()
-                    .map(|param| param.data_type.clone())
-                    .collect::<Vec<_>>();
-
-                for (i, (expected, actual)) in
-                    expected_types.iter().zip(arg_types.iter()).enumerate()
-                {
-                    if expected != actual {
-                        self.program_analyzer.errors.push(
-                            SematicError::FunctionCallArgumentMismatch(
-                                name.clone(),
-                                i + 1,
-                                expected.clone(),
-                                actual.clone(),
-                            ),
-                        );
-                    }
+                self.check_argument_type(&function, &name, i, actual, &mut type_args);
+            }
+
+            // Every parameter without a default value must be given
+            for param in params {
+                if !provided.contains(&param.name) && param.default_value.is_none() {
+                    self.program_analyzer
+                        .errors
+                        .push(SematicError::MissingArgument(
+                            name.clone(),
+                            param.name.clone(),
+                            span.clone(),
+                        ));
                 }
             }
 
-            function.return_type
+            // Generic functions return the type inferred for their type parameters
+            function
+                .return_type
+                .as_ref()
+                .and_then(|return_type| substitute_type(&function, return_type, &type_args))
+        } else if let Some(used) = self.program_analyzer.imports.get_mut(&name) {
+            // Imported functions can't be checked yet, so only mark the import as used
+            *used = true;
+
+            for arg in args {
+                self.analyze_expression(arg);
+            }
+
+            for arg in named_args {
+                self.analyze_expression(arg.value);
+            }
+
+            None
         } else {
             self.program_analyzer
                 .errors
@@ -68,4 +219,246 @@ impl<'a> FunctionAnalyzer<'a> {
             None
         }
     }
+
+    /// Analyzes an argument against the type of the parameter it is given for
+    fn analyze_argument(
+        &mut self,
+        function: &Function,
+        index: usize,
+        arg: Expression,
+    ) -> Option<DataType> {
+        match function.params.get(index) {
+            // Type parameters are inferred from the argument instead
+            Some(param) if get_type_param(function, &param.data_type).is_none() => {
+                self.analyze_expression_as(arg, &param.data_type)
+            }
+            _ => self.analyze_expression(arg),
+        }
+    }
+
+    fn check_argument_type(
+        &mut self,
+        function: &Function,
+        name: &str,
+        index: usize,
+        actual: Option<DataType>,
+        type_args: &mut TypeArguments,
+    ) {
+        let Some(actual) = actual else {
+            return;
+        };
+
+        let expected = &function.params[index].data_type;
+
+        // Infer the type argument from the first argument that uses it
+        if let Some(type_param) = get_type_param(function, expected) {
+            match type_args.get(type_param) {
+                Some(inferred) if inferred != &actual => {
+                    self.program_analyzer
+                        .errors
+                        .push(SematicError::TypeArgumentMismatch(
+                            name.to_string(),
+                            type_param.to_string(),
+                            inferred.clone(),
+                            actual,
+                        ));
+                }
+                Some(_) => {}
+                None => {
+                    type_args.insert(type_param.to_string(), actual);
+                }
+            }
+
+            return;
+        }
+
+        if &actual != expected {
+            self.program_analyzer
+                .errors
+                .push(SematicError::FunctionCallArgumentMismatch(
+                    name.to_string(),
+                    index + 1,
+                    expected.clone(),
+                    actual,
+                ));
+        }
+    }
+
+    /// Analyzes a `panic(message)` or `assert(condition, message)` call
+    fn analyze_panic_call(
+        &mut self,
+        name: String,
+        args: Vec<Expression>,
+        named_args: Vec<NamedArgument>,
+    ) {
+        let params = if name == "panic" {
+            vec![DataType::String]
+        } else {
+            vec![DataType::Boolean, DataType::String]
+        };
+
+        if args.len() != params.len() {
+            self.program_analyzer
+                .errors
+                .push(SematicError::FunctionCallArgumentCountMismatch(
+                    name.clone(),
+                    params.len(),
+                    args.len(),
+                ));
+        }
+
+        for (index, arg) in args.into_iter().enumerate() {
+            let actual = self.analyze_expression(arg);
+
+            if let (Some(actual), Some(expected)) = (actual, params.get(index))
+                && &actual != expected
+            {
+                self.program_analyzer
+                    .errors
+                    .push(SematicError::FunctionCallArgumentMismatch(
+                        name.clone(),
+                        index + 1,
+                        expected.clone(),
+                        actual,
+                    ));
+            }
+        }
+
+        // Built-in functions don't have named parameters
+        for NamedArgument {
+            name: arg_name,
+            value,
+            span,
+            ..
+        } in named_args
+        {
+            self.analyze_expression(value);
+
+            self.program_analyzer
+                .errors
+                .push(SematicError::UnknownNamedArgument(
+                    name.clone(),
+                    arg_name,
+                    span,
+                ));
+        }
+
+        // Nothing after a panic is reached
+        if name == "panic" {
+            self.diverged = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        errors::SematicError,
+        program::{AnalyzedProgram, ProgramAnalyzer},
+    };
+    use gneurshk_lexer::lex;
+    use gneurshk_parser::{parse, types::DataType};
+
+    /// Helper function for testing the analyze function
+    fn lex_then_analyze(input: &'static str) -> AnalyzedProgram {
+        let tokens = lex(input).expect("Failed to lex");
+
+        match parse(&mut tokens.clone()) {
+            Ok(result) => ProgramAnalyzer::analyze(result),
+            Err(e) => panic!("Parsing error: {e}"),
+        }
+    }
+
+    #[test]
+    fn named_and_default_args() {
+        let source = include_str!("../../tests/calls/named_and_default_args.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(analyzed.get_all_errors(), vec![]);
+    }
+
+    #[test]
+    fn invalid_named_args() {
+        let source = include_str!("../../tests/calls/invalid_named_args.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![
+                SematicError::UnknownNamedArgument(
+                    "mix".to_string(),
+                    "kiwis".to_string(),
+                    115..120
+                ),
+                SematicError::DuplicateArgument("mix".to_string(), "apples".to_string(), 146..152),
+                SematicError::PositionalArgumentAfterNamed("mix".to_string(), 175..180),
+                SematicError::MissingArgument("mix".to_string(), "apples".to_string(), 202..216),
+            ]
+        );
+    }
+
+    #[test]
+    fn default_value_mismatch() {
+        let source = include_str!("../../tests/calls/default_value_mismatch.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::DefaultValueTypeMismatch(
+                "mix".to_string(),
+                "pears".to_string(),
+                DataType::Int32,
+                DataType::Boolean
+            )]
+        );
+    }
+
+    #[test]
+    fn non_constant_default_value() {
+        let source = include_str!("../../tests/calls/non_constant_default.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::NonConstantDefaultValue(
+                "mix".to_string(),
+                "pears".to_string(),
+                24..29
+            )]
+        );
+    }
+
+    #[test]
+    fn live_allocations() {
+        let source = include_str!("../../tests/calls/live_allocations.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::FunctionCallArgumentCountMismatch(
+                "live_allocations".to_string(),
+                0,
+                1
+            )]
+        );
+    }
+
+    #[test]
+    fn panic_and_assert() {
+        let source = include_str!("../../tests/calls/panic.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![
+                SematicError::FunctionCallArgumentMismatch(
+                    "assert".to_string(),
+                    1,
+                    DataType::Boolean,
+                    DataType::Int32
+                ),
+                SematicError::FunctionCallArgumentCountMismatch("panic".to_string(), 1, 0),
+            ]
+        );
+    }
 }
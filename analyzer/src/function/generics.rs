@@ -0,0 +1,134 @@
+use crate::{errors::SematicError, function::FunctionAnalyzer, scope::Function};
+use gneurshk_parser::types::DataType;
+use std::collections::{HashMap, HashSet};
+
+/// The concrete types inferred for the type parameters of a generic function call
+pub(crate) type TypeArguments = HashMap<String, DataType>;
+
+/// Gets the name of the type parameter a data type refers to, if any
+pub(crate) fn get_type_param<'f>(function: &'f Function, data_type: &DataType) -> Option<&'f str> {
+    match data_type {
+        DataType::Custom(name) => function
+            .type_params
+            .iter()
+            .find(|type_param| *type_param == name)
+            .map(String::as_str),
+        _ => None,
+    }
+}
+
+/// Replaces the type parameters in a data type with their inferred types
+///
+/// Returns None if a type parameter could not be inferred
+pub(crate) fn substitute_type(
+    function: &Function,
+    data_type: &DataType,
+    type_args: &TypeArguments,
+) -> Option<DataType> {
+    match get_type_param(function, data_type) {
+        Some(type_param) => type_args.get(type_param).cloned(),
+        None => Some(data_type.clone()),
+    }
+}
+
+impl<'a> FunctionAnalyzer<'a> {
+    pub(crate) fn analyze_type_params(&mut self) {
+        let function = self.function_declaration.clone();
+        let span = function.span.clone();
+
+        if function.type_params.is_empty() {
+            return;
+        }
+
+        // External functions have a fixed C signature
+        if function.has_annotation("extern") {
+            self.errors.push(SematicError::GenericExternFunction(
+                function.name.clone(),
+                span.clone(),
+            ));
+        }
+
+        let mut seen = HashSet::new();
+
+        for type_param in &function.type_params {
+            if !seen.insert(type_param) {
+                self.errors.push(SematicError::DuplicateTypeParameter(
+                    function.name.clone(),
+                    type_param.clone(),
+                    span.clone(),
+                ));
+                continue;
+            }
+
+            // Type arguments are inferred from the arguments, so every type parameter needs a parameter
+            let is_inferable = function
+                .params
+                .iter()
+                .any(|param| param.data_type == DataType::Custom(type_param.clone()));
+
+            if !is_inferable {
+                self.errors.push(SematicError::UninferableTypeParameter(
+                    function.name.clone(),
+                    type_param.clone(),
+                    span.clone(),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        errors::SematicError,
+        program::{AnalyzedProgram, ProgramAnalyzer},
+    };
+    use gneurshk_lexer::lex;
+    use gneurshk_parser::{BinaryOperator, parse, types::DataType};
+
+    /// Helper function for testing the analyze function
+    fn lex_then_analyze(input: &'static str) -> AnalyzedProgram {
+        let tokens = lex(input).expect("Failed to lex");
+
+        match parse(&mut tokens.clone()) {
+            Ok(result) => ProgramAnalyzer::analyze(result),
+            Err(e) => panic!("Parsing error: {e}"),
+        }
+    }
+
+    #[test]
+    fn generic_functions() {
+        let source = include_str!("../../tests/generics/generic_functions.iv");
+        let analyzed = lex_then_analyze(source);
+
+        // The return type is inferred from the arguments at each call
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::UnsupportedOperator(
+                DataType::Float32,
+                BinaryOperator::Add,
+                DataType::Int32
+            )]
+        );
+    }
+
+    #[test]
+    fn invalid_generics() {
+        let source = include_str!("../../tests/generics/invalid_generics.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![
+                SematicError::TypeArgumentMismatch(
+                    "largest".to_string(),
+                    "T".to_string(),
+                    DataType::Int32,
+                    DataType::Float32
+                ),
+                SematicError::DuplicateTypeParameter("pick".to_string(), "T".to_string(), 5..9),
+                SematicError::UninferableTypeParameter("make".to_string(), "T".to_string(), 50..54),
+            ]
+        );
+    }
+}
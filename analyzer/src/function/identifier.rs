@@ -1,14 +1,48 @@
 use crate::{errors::SematicError, function::FunctionAnalyzer};
-use gneurshk_parser::types::DataType;
+use gneurshk_parser::{Identifier, types::DataType};
 
 impl<'a> FunctionAnalyzer<'a> {
-    pub(crate) fn analyze_identifier(&mut self, name: String) -> Option<DataType> {
+    pub(crate) fn analyze_identifier(&mut self, identifier: Identifier) -> Option<DataType> {
+        let Identifier { name, span } = identifier;
+
         if let Some(variable) = self.scope.get_mut_variable(&name) {
             variable.used = true;
 
-            Some(variable.data_type.clone())
+            let data_type = variable.data_type.clone();
+
+            // Reading a variable is only allowed once every path leading here has initialized it
+            if !variable.initialized && !self.diverged {
+                self.errors
+                    .push(SematicError::UninitializedVariable(name, span));
+            }
+
+            Some(data_type)
+        } else if let Some(function) = self.program_analyzer.functions.get(&name).cloned() {
+            // Functions that are used as values count as being called by this one
+            self.program_analyzer
+                .calls
+                .entry(self.function_declaration.name.clone())
+                .or_default()
+                .insert(name.clone());
+
+            // The signature of a function value must be known without any arguments
+            if !function.type_params.is_empty() || function.variadic {
+                self.errors
+                    .push(SematicError::FunctionValueNotAllowed(name, span));
+
+                return None;
+            }
+
+            Some(DataType::Function(function.function_type()))
+        } else if let Some(used) = self.program_analyzer.imports.get_mut(&name) {
+            // Imported values can't be checked yet, so only mark the import as used
+            *used = true;
+
+            None
         } else {
-            self.program_analyzer.errors.push(SematicError::VariableNotFound(name));
+            self.program_analyzer
+                .errors
+                .push(SematicError::VariableNotFound(name));
 
             None
         }
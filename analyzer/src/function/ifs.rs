@@ -1,31 +1,119 @@
-use crate::{errors::SematicError, function::FunctionAnalyzer};
+use crate::{
+    errors::{SematicError, SematicWarning},
+    function::FunctionAnalyzer,
+    scope::{Variable, merge_initialization},
+};
 use gneurshk_parser::{ElseBranch, IfStatement, types::DataType};
 
 impl<'a> FunctionAnalyzer<'a> {
     pub(crate) fn analyze_if(&mut self, if_stmt: IfStatement) -> Option<DataType> {
-        let data_type = self.analyze_block(*if_stmt.if_block);
+        let binding = match if_stmt.binding {
+            // Make sure the unwrapped value is an optional
+            Some(binding) => {
+                let span = if_stmt.condition.span();
 
-        // Make sure the condition evaluates to a boolean
-        if self.analyze_expression(*if_stmt.condition) != Some(DataType::Boolean) {
-            self.errors.push(SematicError::BooleanOnlyIfCondition);
+                match self.analyze_expression(*if_stmt.condition) {
+                    Some(DataType::Optional(inner)) => Some((binding, *inner)),
+                    Some(data_type) => {
+                        self.errors.push(SematicError::IfLetRequiresOptional(
+                            data_type.clone(),
+                            span.unwrap_or(binding.span.clone()),
+                        ));
+
+                        // Still declare the binding so its uses aren't reported as well
+                        Some((binding, data_type))
+                    }
+                    None => None,
+                }
+            }
+            // Make sure the condition evaluates to a boolean
+            None => {
+                if self.analyze_expression(*if_stmt.condition) != Some(DataType::Boolean) {
+                    self.errors.push(SematicError::BooleanOnlyIfCondition);
+                }
+
+                None
+            }
+        };
+
+        // Remember the state before branching so each branch starts from it
+        let initialization = self.scope.get_initialization();
+        let diverged = self.diverged;
+
+        let data_type = match binding {
+            // The unwrapped value only exists inside the if block
+            Some((binding, binding_type)) => {
+                self.enter_new_scope();
+
+                self.scope.set_variable(
+                    binding.name.clone(),
+                    Variable {
+                        name: binding.name,
+                        data_type: binding_type,
+                        mutable: false,
+                        used: false,
+                        initialized: true,
+                        parameter: false,
+                        span: binding.span,
+                    },
+                );
+
+                let data_type = self.analyze_block(*if_stmt.if_block);
+
+                for variable in self.scope.get_unused_variables() {
+                    self.warnings
+                        .push(SematicWarning::UnusedVariable(variable.name));
+                }
+
+                self.exit_scope();
+
+                data_type
+            }
+            None => self.analyze_block(*if_stmt.if_block),
+        };
+
+        let if_initialization = self.scope.get_initialization();
+        let if_diverged = self.diverged;
+
+        self.scope.set_initialization(&initialization);
+        self.diverged = diverged;
+
+        // Analyze the else branch if there is one
+        let branch_type = if_stmt
+            .else_statement
+            .map(|else_branch| match *else_branch {
+                ElseBranch::Block(block) => self.analyze_block(block),
+                ElseBranch::IfStatement(if_stmt2) => self.analyze_if(if_stmt2),
+            });
+
+        // Only variables initialized by every branch that falls through remain initialized
+        match (if_diverged, self.diverged) {
+            (false, false) => {
+                let else_initialization = self.scope.get_initialization();
+
+                self.scope.set_initialization(&merge_initialization(
+                    if_initialization,
+                    else_initialization,
+                ));
+            }
+            (false, true) => {
+                self.scope.set_initialization(&if_initialization);
+                self.diverged = false;
+            }
+            (true, _) => {}
         }
 
         // Enforce else branch type consistency when the if block has a data type
         if let Some(expected_type) = &data_type {
-            // Check if there is an else branch
-            if let Some(else_branch) = if_stmt.else_statement {
-                let branch_type = match *else_branch {
-                    ElseBranch::Block(block) => self.analyze_block(block),
-                    ElseBranch::IfStatement(if_stmt2) => self.analyze_if(if_stmt2),
-                };
-
+            match branch_type {
                 // Make sure the else block matches the expected type
-                if branch_type != Some(expected_type.clone()) {
-                    self.errors.push(SematicError::IfElseTypeMismatch);
+                Some(branch_type) => {
+                    if branch_type != Some(expected_type.clone()) {
+                        self.errors.push(SematicError::IfElseTypeMismatch);
+                    }
                 }
-            } else {
                 // Else branches are required when the if block has a data type
-                self.errors.push(SematicError::IfMissingElse)
+                None => self.errors.push(SematicError::IfMissingElse),
             }
         }
 
@@ -33,3 +121,38 @@ impl<'a> FunctionAnalyzer<'a> {
         data_type
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        errors::SematicError,
+        program::{AnalyzedProgram, ProgramAnalyzer},
+    };
+    use gneurshk_lexer::lex;
+    use gneurshk_parser::{parse, types::DataType};
+
+    /// Helper function for testing the analyze function
+    fn lex_then_analyze(input: &'static str) -> AnalyzedProgram {
+        let tokens = lex(input).expect("Failed to lex");
+
+        match parse(&mut tokens.clone()) {
+            Ok(result) => ProgramAnalyzer::analyze(result),
+            Err(e) => panic!("Parsing error: {e}"),
+        }
+    }
+
+    #[test]
+    fn if_let() {
+        let source = include_str!("../../tests/optionals/if_let.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::IfLetRequiresOptional(
+                DataType::Int32,
+                175..180
+            )]
+        );
+        assert_eq!(analyzed.get_all_warnings(), vec![]);
+    }
+}
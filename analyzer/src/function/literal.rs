@@ -1,5 +1,5 @@
-use crate::function::FunctionAnalyzer;
-use gneurshk_parser::types::DataType;
+use crate::{errors::SematicError, function::FunctionAnalyzer};
+use gneurshk_parser::{NoneLit, types::DataType};
 
 impl<'a> FunctionAnalyzer<'a> {
     pub(crate) fn analyze_string(&mut self) -> Option<DataType> {
@@ -17,4 +17,11 @@ impl<'a> FunctionAnalyzer<'a> {
     pub(crate) fn analyze_boolean(&mut self) -> Option<DataType> {
         Some(DataType::Boolean)
     }
+
+    /// Analyzes a none that isn't used where an optional type is expected
+    pub(crate) fn analyze_none(&mut self, none: NoneLit) -> Option<DataType> {
+        self.errors.push(SematicError::UntypedNone(none.span));
+
+        None
+    }
 }
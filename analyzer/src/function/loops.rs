@@ -1,21 +1,65 @@
-use crate::function::{FunctionAnalyzer, LoopContext};
+use crate::{
+    errors::SematicError,
+    function::{FunctionAnalyzer, LoopContext},
+    scope::merge_initialization,
+};
 use gneurshk_parser::{LoopStmt, types::DataType};
 
-// TODO: If the loop never ends,
-//   return an warning that any code after the loop is unreachable
-//   or if the function the loop is inside has a return type,
-//     return an error that the function will never return
-
-// TODO: Add warnings for code unreachable after a terminator (break/continue/return)
+// TODO: If the loop never ends and the function the loop is inside has a return type,
+//   return an error that the function will never return
 
 impl<'a> FunctionAnalyzer<'a> {
     pub(crate) fn analyze_loop(&mut self, loop_stmt: LoopStmt) -> Option<DataType> {
-        self.loop_stack.push(LoopContext {});
+        self.loop_stack.push(LoopContext {
+            break_initializations: Vec::new(),
+        });
 
         self.analyze_block(*loop_stmt.block);
 
-        self.loop_stack.pop();
+        let loop_context = self.loop_stack.pop().unwrap();
+
+        // Code after the loop can only be reached through a break statement,
+        // so only variables initialized before every break are initialized afterwards
+        match loop_context
+            .break_initializations
+            .into_iter()
+            .reduce(merge_initialization)
+        {
+            Some(initialization) => {
+                self.scope.set_initialization(&initialization);
+                self.diverged = false;
+            }
+            None => self.diverged = true,
+        }
 
         None // NOTE: Loops will have a return type in the future
     }
+
+    pub(crate) fn analyze_break(&mut self) -> Option<DataType> {
+        let initialization = self.scope.get_initialization();
+
+        match self.loop_stack.last_mut() {
+            Some(loop_context) => {
+                // Unreachable breaks never exit the loop
+                if !self.diverged {
+                    loop_context.break_initializations.push(initialization);
+                }
+            }
+            None => self.errors.push(SematicError::BreakOutsideLoop),
+        }
+
+        self.diverged = true;
+
+        None
+    }
+
+    pub(crate) fn analyze_continue(&mut self) -> Option<DataType> {
+        if self.loop_stack.is_empty() {
+            self.errors.push(SematicError::ContinueOutsideLoop);
+        }
+
+        self.diverged = true;
+
+        None
+    }
 }
@@ -1,21 +1,31 @@
 use crate::{
     errors::{SematicError, SematicWarning},
+    function::expression::is_assignable,
+    levels::{apply_lint_levels, get_lint_levels},
+    lint::lint_function,
+    options::LintLevel,
     program::ProgramAnalyzer,
-    scope::{Scope, Variable},
+    scope::{Initialization, Scope, Variable},
 };
-use gneurshk_parser::FunctionDeclaration;
+use gneurshk_parser::{Annotation, FunctionDeclaration, types::DataType};
+use std::collections::HashMap;
 
+mod annotations;
 mod assignment;
 mod binary_expression;
 mod block;
+mod closures;
 mod expression;
 mod function_call;
+mod generics;
 mod identifier;
 mod ifs;
 mod literal;
 mod loops;
+mod results;
 mod returns;
 mod statement;
+mod types;
 mod unary_expression;
 mod variables;
 
@@ -23,10 +33,15 @@ mod variables;
 pub struct AnalyzedFunction {
     pub errors: Vec<SematicError>,
     pub warnings: Vec<SematicWarning>,
+    /// The lint levels that apply to the function
+    pub(crate) lint_levels: HashMap<String, LintLevel>,
 }
 
 #[derive(Debug)]
-pub(crate) struct LoopContext {}
+pub(crate) struct LoopContext {
+    /// The initialization state at each break statement that exits the loop
+    pub(crate) break_initializations: Vec<Initialization>,
+}
 
 #[derive(Debug)]
 pub(crate) struct FunctionAnalyzer<'a> {
@@ -34,6 +49,8 @@ pub(crate) struct FunctionAnalyzer<'a> {
     pub(crate) program_analyzer: &'a mut ProgramAnalyzer,
     pub(crate) function_declaration: FunctionDeclaration,
     pub(crate) loop_stack: Vec<LoopContext>,
+    /// Whether control flow can no longer reach the current statement
+    pub(crate) diverged: bool,
 
     pub(crate) errors: Vec<SematicError>,
     pub(crate) warnings: Vec<SematicWarning>,
@@ -44,6 +61,9 @@ impl<'a> FunctionAnalyzer<'a> {
         program_analyzer: &'a mut ProgramAnalyzer,
         function: FunctionDeclaration,
     ) -> AnalyzedFunction {
+        // Replace the type aliases in the signature with the types they refer to
+        let function = program_analyzer.resolve_function_types(function);
+
         // Create an analyzer instance
         let mut analyzer = FunctionAnalyzer {
             scope: Box::new(Scope::new(None)),
@@ -53,10 +73,57 @@ impl<'a> FunctionAnalyzer<'a> {
             errors: Vec::new(),
             warnings: Vec::new(),
             loop_stack: Vec::new(),
+            diverged: false,
         };
 
+        // Check the annotations of the function
+        analyzer.analyze_annotations();
+
+        // Check the type parameters of generic functions
+        analyzer.analyze_type_params();
+
+        // Check that every type in the signature exists
+        analyzer.analyze_signature_types();
+
+        // Look for suspicious code patterns
+        analyzer.warnings.extend(lint_function(&function));
+
+        // Default values are built at the call site, so they can only use literals
+        for param in &function.params {
+            let Some(default_value) = param.default_value.clone() else {
+                continue;
+            };
+
+            if !default_value.is_constant() {
+                analyzer.errors.push(SematicError::NonConstantDefaultValue(
+                    function.name.clone(),
+                    param.name.clone(),
+                    param.span.clone(),
+                ));
+                continue;
+            }
+
+            if let Some(actual) = analyzer.analyze_expression_as(default_value, &param.data_type)
+                && actual != param.data_type
+            {
+                analyzer.errors.push(SematicError::DefaultValueTypeMismatch(
+                    function.name.clone(),
+                    param.name.clone(),
+                    param.data_type.clone(),
+                    actual,
+                ));
+            }
+        }
+
         // Declare the params in the scope
         for param in function.params {
+            if analyzer.scope.has_local_variable(&param.name) {
+                analyzer.errors.push(SematicError::DuplicateParameter(
+                    function.name.clone(),
+                    param.name.clone(),
+                ));
+            }
+
             analyzer.scope.set_variable(
                 param.name.clone(),
                 Variable {
@@ -65,35 +132,90 @@ impl<'a> FunctionAnalyzer<'a> {
                     mutable: param.mutable,
                     used: false,
                     initialized: true,
+                    parameter: true,
+                    span: param.span,
                 },
             );
         }
 
-        // Analyze function body
-        let implicit_return = analyzer.analyze_block(*function.block);
+        // External functions are only declared, so they don't have a body to analyze
+        let Some(mut block) = function.block else {
+            return analyzer.finish(&function.annotations);
+        };
 
-        // Check if the implicit return doesn't match the expected return type
-        if implicit_return != function.return_type {
-            analyzer
-                .errors
-                .push(SematicError::FunctionReturnTypeMismatch(function.name));
+        // Values at the end of the body that need the return type are checked like a return statement
+        if function.return_type.is_some() {
+            block.return_untyped_value();
         }
 
-        // Check for unused variables before exiting the scope
+        // Analyze function body
+        let implicit_return = analyzer.analyze_block(*block);
+        analyzer.check_implicit_return(implicit_return);
+
+        // Only the parameters are left in the function's scope
         for variable in analyzer.scope.get_unused_variables() {
-            analyzer
-                .warnings
-                .push(SematicWarning::UnusedVariable(variable.name));
+            analyzer.warnings.push(SematicWarning::UnusedParameter(
+                function.name.clone(),
+                variable.name,
+            ));
         }
 
+        analyzer.finish(&function.annotations)
+    }
+
+    /// Checks the value at the end of the body against the return type of the function
+    pub(crate) fn check_implicit_return(&mut self, implicit_return: Option<DataType>) {
+        // The implicit return only matters if the end of the function can be reached
+        if self.diverged {
+            return;
+        }
+
+        let FunctionDeclaration {
+            name, return_type, ..
+        } = &self.function_declaration;
+
+        if implicit_return.is_none() && return_type.is_some() {
+            // Non-void functions cannot fall off the end without a value
+            self.errors
+                .push(SematicError::FunctionMissingReturn(name.clone()));
+        } else if !is_implicit_return_valid(&implicit_return, return_type) {
+            // Check if the implicit return doesn't match the expected return type
+            self.errors
+                .push(SematicError::FunctionReturnTypeMismatch(name.clone()));
+        }
+    }
+
+    /// Applies the lint levels set by the function's annotations and returns the results
+    fn finish(mut self, annotations: &[Annotation]) -> AnalyzedFunction {
+        let options = &self.program_analyzer.options;
+        let lint_levels = get_lint_levels(options, annotations, &mut self.errors);
+        let warnings = apply_lint_levels(
+            &lint_levels,
+            options.deny_warnings,
+            self.warnings,
+            &mut self.errors,
+        );
+
         // Return a static analyzed function
         AnalyzedFunction {
-            errors: analyzer.errors,
-            warnings: analyzer.warnings,
+            errors: self.errors,
+            warnings,
+            lint_levels,
         }
     }
 }
 
+/// Checks if the value at the end of a function body matches its return type
+fn is_implicit_return_valid(
+    implicit_return: &Option<DataType>,
+    return_type: &Option<DataType>,
+) -> bool {
+    match (implicit_return, return_type) {
+        (Some(actual), Some(expected)) => is_assignable(expected, actual),
+        _ => implicit_return == return_type,
+    }
+}
+
 impl<'a> FunctionAnalyzer<'a> {
     pub fn enter_new_scope(&mut self) {
         let parent = self.scope.to_owned();
@@ -0,0 +1,156 @@
+use crate::{errors::SematicError, function::FunctionAnalyzer};
+use gneurshk_parser::{FunctionCall, TryExpression, types::DataType};
+
+impl<'a> FunctionAnalyzer<'a> {
+    /// Analyzes an `ok(...)` or `err(...)` call that creates a value of the expected result type
+    pub(crate) fn analyze_result_constructor(
+        &mut self,
+        call: FunctionCall,
+        expected: &DataType,
+    ) -> Option<DataType> {
+        let FunctionCall {
+            name, args, span, ..
+        } = call;
+
+        let DataType::Result { value, error } = expected else {
+            self.errors
+                .push(SematicError::ResultRequired(name, expected.clone(), span));
+
+            for arg in args {
+                self.analyze_expression(arg);
+            }
+
+            // The mismatch is already reported, so treat the call as the expected type
+            return Some(expected.clone());
+        };
+
+        // Results hold exactly one value or error
+        if args.len() != 1 {
+            self.program_analyzer
+                .errors
+                .push(SematicError::FunctionCallArgumentCountMismatch(
+                    name.clone(),
+                    1,
+                    args.len(),
+                ));
+        }
+
+        let inner = if name == "ok" { value } else { error };
+
+        if let Some(arg) = args.into_iter().next()
+            && let Some(actual) = self.analyze_expression_as(arg, inner)
+            && &actual != inner.as_ref()
+        {
+            self.program_analyzer
+                .errors
+                .push(SematicError::FunctionCallArgumentMismatch(
+                    name,
+                    1,
+                    *inner.clone(),
+                    actual,
+                ));
+        }
+
+        Some(expected.clone())
+    }
+
+    /// Analyzes the '?' operator, which returns errors and missing values from the function early
+    pub(crate) fn analyze_try(&mut self, try_expr: TryExpression) -> Option<DataType> {
+        let name = self.function_declaration.name.clone();
+        let return_type = self.function_declaration.return_type.clone();
+        let data_type = self.analyze_expression(*try_expr.value)?;
+
+        // Only the same kind of value can be passed on to the caller
+        let matches_return_type = match (&data_type, &return_type) {
+            (
+                DataType::Result { error, .. },
+                Some(DataType::Result {
+                    error: expected, ..
+                }),
+            ) => error == expected,
+            (DataType::Optional(_), Some(DataType::Optional(_))) => true,
+            _ => false,
+        };
+
+        match data_type {
+            DataType::Result { ref value, .. } | DataType::Optional(ref value) => {
+                if !matches_return_type {
+                    self.errors.push(SematicError::TryReturnTypeMismatch(
+                        name,
+                        data_type.clone(),
+                        try_expr.span,
+                    ));
+                }
+
+                Some(*value.clone())
+            }
+            _ => {
+                self.errors
+                    .push(SematicError::TryRequiresResult(data_type, try_expr.span));
+
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        errors::SematicError,
+        program::{AnalyzedProgram, ProgramAnalyzer},
+    };
+    use gneurshk_lexer::lex;
+    use gneurshk_parser::{parse, types::DataType};
+
+    /// Helper function for testing the analyze function
+    fn lex_then_analyze(input: &'static str) -> AnalyzedProgram {
+        let tokens = lex(input).expect("Failed to lex");
+
+        match parse(&mut tokens.clone()) {
+            Ok(result) => ProgramAnalyzer::analyze(result),
+            Err(e) => panic!("Parsing error: {e}"),
+        }
+    }
+
+    #[test]
+    fn propagated_results() {
+        let source = include_str!("../../tests/results/propagated_results.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(analyzed.get_all_errors(), vec![]);
+    }
+
+    #[test]
+    fn invalid_results() {
+        let source = include_str!("../../tests/results/invalid_results.iv");
+        let analyzed = lex_then_analyze(source);
+
+        let result_type = DataType::Result {
+            value: Box::new(DataType::Int32),
+            error: Box::new(DataType::String),
+        };
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![
+                SematicError::FunctionCallArgumentMismatch(
+                    "ok".to_string(),
+                    1,
+                    DataType::Int32,
+                    DataType::Boolean
+                ),
+                SematicError::UnusedResult("pick_cherry".to_string(), 229..242),
+                SematicError::TryReturnTypeMismatch(
+                    "pick_plum".to_string(),
+                    result_type.clone(),
+                    118..119
+                ),
+                SematicError::TryReturnTypeMismatch("pick_pear".to_string(), result_type, 195..196),
+                SematicError::UntypedResult("ok".to_string(), 259..264),
+                SematicError::ResultRequired("err".to_string(), DataType::Int32, 287..302),
+                SematicError::TryRequiresResult(DataType::Int32, 319..320),
+            ]
+        );
+    }
+}
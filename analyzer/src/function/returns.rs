@@ -3,19 +3,150 @@ use gneurshk_parser::{Return, types::DataType};
 
 impl<'a> FunctionAnalyzer<'a> {
     pub(crate) fn analyze_return(&mut self, return_stmt: Return) -> Option<DataType> {
-        // Analyze the return's expression type
-        let return_type = return_stmt
-            .value
-            .and_then(|expr| self.analyze_expression(expr));
-
-        // Check if the return type doesn't match the return type of the function
-        if return_type != self.function_declaration.return_type {
-            self.errors.push(SematicError::FunctionReturnTypeMismatch(
-                self.function_declaration.name.clone(),
-            ));
+        let name = self.function_declaration.name.clone();
+        let expected_type = self.function_declaration.return_type.clone();
+
+        match (return_stmt.value, expected_type) {
+            (Some(value), Some(expected_type)) => {
+                // Make sure the returned value matches the return type of the function
+                if let Some(return_type) = self.analyze_expression_as(value, &expected_type)
+                    && return_type != expected_type
+                {
+                    self.errors.push(SematicError::ReturnValueTypeMismatch(
+                        name,
+                        expected_type,
+                        return_type,
+                    ));
+                }
+            }
+            (Some(value), None) => {
+                self.analyze_expression(value);
+
+                self.errors.push(SematicError::UnexpectedReturnValue(name));
+            }
+            (None, Some(expected_type)) => {
+                self.errors
+                    .push(SematicError::ReturnValueMissing(name, expected_type));
+            }
+            (None, None) => {}
         }
 
+        // Nothing after a return statement can be reached
+        self.diverged = true;
+
         // Returns don't have a value
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        errors::{SematicError, SematicWarning},
+        program::{AnalyzedProgram, ProgramAnalyzer},
+    };
+    use gneurshk_lexer::lex;
+    use gneurshk_parser::{parse, types::DataType};
+
+    /// Helper function for testing the analyze function
+    fn lex_then_analyze(input: &'static str) -> AnalyzedProgram {
+        let tokens = lex(input).expect("Failed to lex");
+
+        match parse(&mut tokens.clone()) {
+            Ok(result) => ProgramAnalyzer::analyze(result),
+            Err(e) => panic!("Parsing error: {e}"),
+        }
+    }
+
+    #[test]
+    fn returns_on_every_path() {
+        let source = include_str!("../../tests/returns/returns_on_every_path.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(analyzed.get_all_errors(), vec![]);
+    }
+
+    #[test]
+    fn missing_return() {
+        let source = include_str!("../../tests/returns/missing_return.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::FunctionMissingReturn("apple".to_string())]
+        );
+    }
+
+    #[test]
+    fn return_value_type_mismatch() {
+        let source = include_str!("../../tests/returns/return_value_type_mismatch.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::ReturnValueTypeMismatch(
+                "banana".to_string(),
+                DataType::Int32,
+                DataType::Boolean
+            )]
+        );
+    }
+
+    #[test]
+    fn return_value_missing() {
+        let source = include_str!("../../tests/returns/return_value_missing.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::ReturnValueMissing(
+                "cherry".to_string(),
+                DataType::Int32
+            )]
+        );
+    }
+
+    #[test]
+    fn unexpected_return_value() {
+        let source = include_str!("../../tests/returns/unexpected_return_value.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::UnexpectedReturnValue("main".to_string())]
+        );
+    }
+
+    #[test]
+    fn unreachable_code() {
+        let source = include_str!("../../tests/returns/unreachable_code.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(analyzed.get_all_errors(), vec![]);
+        assert_eq!(
+            analyzed.get_all_warnings(),
+            vec![
+                SematicWarning::UnreachableCode("main".to_string(), Some(47..63)),
+                SematicWarning::UnreachableCode("main".to_string(), Some(86..102)),
+            ]
+        );
+    }
+
+    #[test]
+    fn unreachable_statements() {
+        let source = include_str!("../../tests/returns/unreachable_statements.iv");
+        let analyzed = lex_then_analyze(source);
+
+        // Declarations, ifs and loop exits point at their own location
+        assert_eq!(analyzed.get_all_errors(), vec![]);
+        assert_eq!(
+            analyzed.get_all_warnings(),
+            vec![
+                SematicWarning::UnreachableCode("count".to_string(), Some(65..70)),
+                SematicWarning::UnusedVariable("pears".to_string()),
+                SematicWarning::UnreachableCode("wait".to_string(), Some(135..140)),
+                SematicWarning::UnreachableCode("main".to_string(), Some(215..227)),
+            ]
+        );
+    }
+}
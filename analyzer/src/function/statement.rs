@@ -1,7 +1,5 @@
-use crate::function::FunctionAnalyzer;
-use gneurshk_parser::{
-    Assignment, BinaryExpression, FunctionCall, Identifier, Stmt, types::DataType,
-};
+use crate::{errors::SematicError, function::FunctionAnalyzer};
+use gneurshk_parser::{Assignment, BinaryExpression, MemberAccess, Stmt, types::DataType};
 
 impl<'a> FunctionAnalyzer<'a> {
     pub(crate) fn analyze_statement(&mut self, statement: Stmt) -> Option<DataType> {
@@ -15,10 +13,13 @@ impl<'a> FunctionAnalyzer<'a> {
             Stmt::Integer(..) => self.analyze_integer(),
             Stmt::Float(..) => self.analyze_float(),
             Stmt::Boolean(..) => self.analyze_boolean(),
-            Stmt::Identifier(Identifier { name, .. }) => self.analyze_identifier(name),
-            Stmt::FunctionCall(FunctionCall { name, args, .. }) => {
-                self.analyze_function_call(name, args)
-            }
+            Stmt::None(none) => self.analyze_none(none),
+            Stmt::Identifier(identifier) => self.analyze_identifier(identifier),
+            Stmt::UnaryExpression(unary_expr) => self.analyze_unary_expression(unary_expr),
+            Stmt::Cast(cast) => self.analyze_cast(cast),
+            Stmt::FunctionCall(call) => self.analyze_function_call(call),
+            Stmt::Try(try_expr) => self.analyze_try(try_expr),
+            Stmt::Lambda(lambda) => self.analyze_lambda(lambda),
             Stmt::VariableDeclaration(variable) => self.analyze_variable_declaration(variable),
             Stmt::Assignment(Assignment { member, value }) => {
                 self.analyze_assignment(member, value)
@@ -27,27 +28,20 @@ impl<'a> FunctionAnalyzer<'a> {
             Stmt::IfStatement(return_stmt) => self.analyze_if(return_stmt),
             Stmt::Return(return_stmt) => self.analyze_return(return_stmt),
             Stmt::Loop(loop_stmt) => self.analyze_loop(loop_stmt),
-            Stmt::Break => {
-                if self.loop_stack.is_empty() {
-                    self.errors
-                        .push(crate::errors::SematicError::BreakOutsideLoop);
-                }
-
-                None
-            }
-            Stmt::Continue => {
-                if self.loop_stack.is_empty() {
-                    self.errors
-                        .push(crate::errors::SematicError::ContinueOutsideLoop);
-                }
+            Stmt::Break(_) => self.analyze_break(),
+            Stmt::Continue(_) => self.analyze_continue(),
+            Stmt::MemberAccess(member_access) => self.analyze_member_access(member_access),
+        }
+    }
 
-                None
-            }
-            _ => {
-                println!("statement: {statement:?}");
+    /// Reports member access as unsupported, since there are no types with members yet
+    pub(crate) fn analyze_member_access(
+        &mut self,
+        member_access: MemberAccess,
+    ) -> Option<DataType> {
+        self.errors
+            .push(SematicError::UnsupportedMemberAccess(member_access.span()));
 
-                todo!();
-            }
-        }
+        None
     }
 }
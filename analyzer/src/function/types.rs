@@ -0,0 +1,138 @@
+use crate::{
+    errors::SematicError,
+    function::FunctionAnalyzer,
+    suggestions::{PRIMITIVE_TYPES, find_similar_name},
+};
+use gneurshk_parser::types::{DataType, FunctionType};
+use std::ops::Range;
+
+impl<'a> FunctionAnalyzer<'a> {
+    /// Replaces a type alias with the type it refers to, unless it names a type parameter
+    pub(crate) fn resolve_type(&self, data_type: &DataType) -> DataType {
+        match data_type {
+            DataType::Custom(name) if self.function_declaration.type_params.contains(name) => {
+                data_type.clone()
+            }
+            DataType::Optional(inner) => DataType::Optional(Box::new(self.resolve_type(inner))),
+            DataType::Result { value, error } => DataType::Result {
+                value: Box::new(self.resolve_type(value)),
+                error: Box::new(self.resolve_type(error)),
+            },
+            DataType::Function(FunctionType {
+                params,
+                return_type,
+            }) => DataType::Function(FunctionType {
+                params: params
+                    .iter()
+                    .map(|param| self.resolve_type(param))
+                    .collect(),
+                return_type: return_type
+                    .as_ref()
+                    .map(|return_type| Box::new(self.resolve_type(return_type))),
+            }),
+            _ => self.program_analyzer.resolve_type(data_type),
+        }
+    }
+
+    /// Reports the types in the signature of the function that don't exist
+    pub(crate) fn analyze_signature_types(&mut self) {
+        let function = self.function_declaration.clone();
+
+        for param in &function.params {
+            self.check_type_exists(&param.data_type, param.span.clone());
+        }
+
+        if let Some(return_type) = &function.return_type {
+            self.check_type_exists(return_type, function.span.clone());
+        }
+    }
+
+    /// Reports a custom type that is neither a type parameter nor a type alias
+    pub(crate) fn check_type_exists(&mut self, data_type: &DataType, span: Range<usize>) {
+        let name = match data_type {
+            DataType::Custom(name) => name,
+            DataType::Optional(inner) => return self.check_type_exists(inner, span),
+            DataType::Result { value, error } => {
+                self.check_type_exists(value, span.clone());
+                return self.check_type_exists(error, span);
+            }
+            DataType::Function(FunctionType {
+                params,
+                return_type,
+            }) => {
+                for param in params {
+                    self.check_type_exists(param, span.clone());
+                }
+
+                if let Some(return_type) = return_type {
+                    self.check_type_exists(return_type, span);
+                }
+
+                return;
+            }
+            _ => return,
+        };
+
+        let type_params = &self.function_declaration.type_params;
+        let type_aliases = &self.program_analyzer.type_aliases;
+
+        if type_params.contains(name) || type_aliases.contains_key(name) {
+            return;
+        }
+
+        // Suggest the closest type in case the name is a typo
+        let candidates = PRIMITIVE_TYPES
+            .into_iter()
+            .chain(type_params.iter().map(String::as_str))
+            .chain(type_aliases.keys().map(String::as_str));
+
+        let suggestion = find_similar_name(name, candidates);
+
+        self.errors
+            .push(SematicError::UnknownType(name.clone(), suggestion, span));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        errors::SematicError,
+        program::{AnalyzedProgram, ProgramAnalyzer},
+    };
+    use gneurshk_lexer::lex;
+    use gneurshk_parser::parse;
+
+    /// Helper function for testing the analyze function
+    fn lex_then_analyze(input: &'static str) -> AnalyzedProgram {
+        let tokens = lex(input).expect("Failed to lex");
+
+        match parse(&mut tokens.clone()) {
+            Ok(result) => ProgramAnalyzer::analyze(result),
+            Err(e) => panic!("Parsing error: {e}"),
+        }
+    }
+
+    #[test]
+    fn unknown_types() {
+        let source = include_str!("../../tests/types/unknown_types.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![
+                SematicError::UnknownType(
+                    "Flot32".to_string(),
+                    Some("Float32".to_string()),
+                    42..47
+                ),
+                SematicError::UnknownType("Basket".to_string(), None, 36..41),
+                SematicError::UnknownType("Int23".to_string(), Some("Int32".to_string()), 90..95),
+                SematicError::UnknownType(
+                    "Meter".to_string(),
+                    Some("Meters".to_string()),
+                    115..121
+                ),
+            ]
+        );
+    }
+}
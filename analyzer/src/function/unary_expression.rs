@@ -1,5 +1,5 @@
 use crate::{errors::SematicError, function::FunctionAnalyzer};
-use gneurshk_parser::{UnaryExpression, UnaryOperator, types::DataType};
+use gneurshk_parser::{CastExpression, UnaryExpression, UnaryOperator, types::DataType};
 
 impl<'a> FunctionAnalyzer<'a> {
     pub(crate) fn analyze_unary_expression(&mut self, expr: UnaryExpression) -> Option<DataType> {
@@ -27,4 +27,11 @@ impl<'a> FunctionAnalyzer<'a> {
             },
         }
     }
+
+    /// Analyzes a cast, whose value takes on the type it is cast to
+    pub(crate) fn analyze_cast(&mut self, cast: CastExpression) -> Option<DataType> {
+        self.analyze_expression(*cast.value);
+
+        Some(self.resolve_type(&cast.data_type))
+    }
 }
@@ -1,5 +1,9 @@
-use crate::{errors::SematicError, function::FunctionAnalyzer, scope::Variable};
-use gneurshk_parser::{VariableDeclaration, types::DataType};
+use crate::{
+    errors::{SematicError, SematicWarning},
+    function::FunctionAnalyzer,
+    scope::Variable,
+};
+use gneurshk_parser::{Expression, VariableDeclaration, types::DataType};
 
 impl<'a> FunctionAnalyzer<'a> {
     pub(crate) fn analyze_variable_declaration(
@@ -7,24 +11,31 @@ impl<'a> FunctionAnalyzer<'a> {
         variable: VariableDeclaration,
     ) -> Option<DataType> {
         // Get values from the variable declaration
-        let (mutable, name, data_type, value) = match variable {
+        let (mutable, name, data_type, value, span) = match variable {
             VariableDeclaration::Mutable {
                 name,
                 value,
                 data_type,
-                ..
-            } => (true, name, data_type, value),
+                span,
+            } => (true, name, data_type, value, span),
             VariableDeclaration::Constant {
                 name,
                 value,
                 data_type,
-                ..
-            } => (false, name, data_type, Some(value)),
+                span,
+            } => (false, name, data_type, Some(value), span),
         };
 
         // Analyze data type
         let var_type = if let Some(dt) = data_type {
-            dt
+            self.check_type_exists(&dt, span.clone());
+            let var_type = self.resolve_type(&dt);
+
+            if let Some(val) = value.clone() {
+                self.check_initial_value(&name, &var_type, val);
+            }
+
+            var_type
         } else if let Some(val) = value.clone() {
             self.analyze_expression(val)?
         } else {
@@ -35,6 +46,15 @@ impl<'a> FunctionAnalyzer<'a> {
             return None;
         };
 
+        // Report declarations that collide with existing variables
+        if self.scope.has_local_variable(&name) {
+            self.errors
+                .push(SematicError::VariableRedeclared(name.clone()));
+        } else if self.scope.get_variable(&name).is_some() {
+            self.warnings
+                .push(SematicWarning::ShadowedVariable(name.clone()));
+        }
+
         // Store variable in scope
         let variable = Variable {
             name: name.clone(),
@@ -42,10 +62,177 @@ impl<'a> FunctionAnalyzer<'a> {
             mutable,
             used: false,
             initialized: value.is_some(),
+            parameter: false,
+            span,
         };
 
         self.scope.set_variable(name, variable);
 
         None
     }
+
+    /// Makes sure the initial value of a variable fits its optional, Result or function type
+    ///
+    /// Other values are not checked yet, so literals can still be used with every numeric type
+    fn check_initial_value(&mut self, name: &str, var_type: &DataType, value: Expression) {
+        let Some(value_type) = self.analyze_expression_as(value, var_type) else {
+            return;
+        };
+
+        let is_checked = |data_type: &DataType| {
+            matches!(
+                data_type,
+                DataType::Optional(_) | DataType::Result { .. } | DataType::Function(_)
+            )
+        };
+
+        if (is_checked(var_type) || is_checked(&value_type)) && &value_type != var_type {
+            self.program_analyzer
+                .errors
+                .push(SematicError::AssignmentTypeMismatch(
+                    name.to_string(),
+                    var_type.clone(),
+                    value_type,
+                ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        errors::{SematicError, SematicWarning},
+        options::{AnalyzerOptions, LintLevel},
+        program::{AnalyzedProgram, ProgramAnalyzer},
+    };
+    use gneurshk_lexer::lex;
+    use gneurshk_parser::parse;
+    use std::collections::HashMap;
+
+    /// Helper function for testing the analyze function
+    fn lex_then_analyze(input: &'static str) -> AnalyzedProgram {
+        let tokens = lex(input).expect("Failed to lex");
+
+        match parse(&mut tokens.clone()) {
+            Ok(result) => ProgramAnalyzer::analyze(result),
+            Err(e) => panic!("Parsing error: {e}"),
+        }
+    }
+
+    #[test]
+    fn read_before_initialization() {
+        let source = include_str!("../../tests/variables/read_before_initialization.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::UninitializedVariable(
+                "apple".to_string(),
+                47..52
+            )]
+        );
+    }
+
+    #[test]
+    fn initialized_in_both_branches() {
+        let source = include_str!("../../tests/variables/initialized_in_both_branches.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(analyzed.get_all_errors(), vec![]);
+    }
+
+    #[test]
+    fn initialized_in_one_branch() {
+        let source = include_str!("../../tests/variables/initialized_in_one_branch.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::UninitializedVariable(
+                "cherry".to_string(),
+                133..139
+            )]
+        );
+    }
+
+    #[test]
+    fn other_branch_returns() {
+        let source = include_str!("../../tests/variables/other_branch_returns.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(analyzed.get_all_errors(), vec![]);
+    }
+
+    #[test]
+    fn initialized_before_break() {
+        let source = include_str!("../../tests/variables/initialized_before_break.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(analyzed.get_all_errors(), vec![]);
+    }
+
+    #[test]
+    fn initialized_inside_while_loop() {
+        let source = include_str!("../../tests/variables/initialized_inside_while_loop.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::UninitializedVariable(
+                "grape".to_string(),
+                131..136
+            )]
+        );
+    }
+
+    #[test]
+    fn redeclared_in_same_scope() {
+        let source = include_str!("../../tests/variables/redeclared_in_same_scope.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::VariableRedeclared("kiwi".to_string())]
+        );
+    }
+
+    #[test]
+    fn shadowed_in_inner_scope() {
+        let source = include_str!("../../tests/variables/shadowed_in_inner_scope.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(analyzed.get_all_errors(), vec![]);
+        assert_eq!(
+            analyzed.get_all_warnings(),
+            vec![SematicWarning::ShadowedVariable("lemon".to_string())]
+        );
+    }
+
+    #[test]
+    fn shadowing_lint_allowed() {
+        let source = include_str!("../../tests/variables/shadowed_in_inner_scope.iv");
+        let tokens = lex(source).expect("Failed to lex");
+        let program = parse(&mut tokens.clone()).expect("Failed to parse");
+
+        let analyzed = ProgramAnalyzer::analyze_with_options(
+            program,
+            AnalyzerOptions {
+                lint_levels: HashMap::from([("shadowed_variable".to_string(), LintLevel::Allow)]),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(analyzed.get_all_warnings(), vec![]);
+    }
+
+    #[test]
+    fn unused_in_nested_scope() {
+        let source = include_str!("../../tests/variables/unused_in_nested_scope.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_warnings(),
+            vec![SematicWarning::UnusedVariable("strawberry".to_string())]
+        );
+    }
 }
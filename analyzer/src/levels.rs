@@ -0,0 +1,167 @@
+use crate::{
+    errors::{SematicError, SematicWarning},
+    options::{AnalyzerOptions, LINT_NAMES, LintLevel},
+};
+use gneurshk_parser::{Annotation, Expression};
+use std::collections::HashMap;
+
+/// Combines the project wide lint levels with the ones set by a function's annotations
+pub(crate) fn get_lint_levels(
+    options: &AnalyzerOptions,
+    annotations: &[Annotation],
+    errors: &mut Vec<SematicError>,
+) -> HashMap<String, LintLevel> {
+    let mut levels = options.lint_levels.clone();
+
+    for annotation in annotations {
+        // Other annotations are not related to lints
+        let Some(level) = LintLevel::from_name(&annotation.name) else {
+            continue;
+        };
+
+        for arg in &annotation.args {
+            match arg {
+                Expression::Identifier(identifier) if LINT_NAMES.contains(&&*identifier.name) => {
+                    levels.insert(identifier.name.clone(), level);
+                }
+                Expression::Identifier(identifier) => {
+                    errors.push(SematicError::UnknownLint(identifier.name.clone()));
+                }
+                _ => {
+                    errors.push(SematicError::InvalidLintAnnotation(annotation.name.clone()));
+                }
+            }
+        }
+    }
+
+    levels
+}
+
+/// Removes allowed warnings and turns denied warnings into errors
+pub(crate) fn apply_lint_levels(
+    levels: &HashMap<String, LintLevel>,
+    deny_warnings: bool,
+    warnings: Vec<SematicWarning>,
+    errors: &mut Vec<SematicError>,
+) -> Vec<SematicWarning> {
+    let mut kept = Vec::new();
+
+    for warning in warnings {
+        let level = match levels.get(warning.lint_name()) {
+            Some(level) => *level,
+            None => LintLevel::Warn,
+        };
+
+        match level {
+            LintLevel::Allow => {}
+            LintLevel::Warn if !deny_warnings => kept.push(warning),
+            LintLevel::Warn | LintLevel::Deny => {
+                errors.push(SematicError::DeniedWarning(warning));
+            }
+        }
+    }
+
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        errors::{SematicError, SematicWarning},
+        options::{AnalyzerOptions, LintLevel},
+        program::{AnalyzedProgram, ProgramAnalyzer},
+    };
+    use gneurshk_lexer::lex;
+    use gneurshk_parser::parse;
+    use std::collections::HashMap;
+
+    /// Helper function for testing the analyze function
+    fn lex_then_analyze(input: &'static str, options: AnalyzerOptions) -> AnalyzedProgram {
+        let tokens = lex(input).expect("Failed to lex");
+
+        match parse(&mut tokens.clone()) {
+            Ok(result) => ProgramAnalyzer::analyze_with_options(result, options),
+            Err(e) => panic!("Parsing error: {e}"),
+        }
+    }
+
+    #[test]
+    fn allow_annotation() {
+        let source = include_str!("../tests/levels/allow_annotation.iv");
+        let analyzed = lex_then_analyze(source, AnalyzerOptions::default());
+
+        assert_eq!(analyzed.get_all_errors(), vec![]);
+        assert_eq!(analyzed.get_all_warnings(), vec![]);
+    }
+
+    #[test]
+    fn deny_annotation() {
+        let source = include_str!("../tests/levels/deny_annotation.iv");
+        let analyzed = lex_then_analyze(source, AnalyzerOptions::default());
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::DeniedWarning(SematicWarning::UnusedVariable(
+                "banana".to_string()
+            ))]
+        );
+        assert_eq!(analyzed.get_all_warnings(), vec![]);
+    }
+
+    #[test]
+    fn unknown_lint() {
+        let source = include_str!("../tests/levels/unknown_lint.iv");
+        let analyzed = lex_then_analyze(source, AnalyzerOptions::default());
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::UnknownLint("unused_banana".to_string())]
+        );
+    }
+
+    #[test]
+    fn project_levels() {
+        let source = include_str!("../tests/levels/project_levels.iv");
+        let analyzed = lex_then_analyze(
+            source,
+            AnalyzerOptions {
+                lint_levels: HashMap::from([("unused_variable".to_string(), LintLevel::Deny)]),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::DeniedWarning(SematicWarning::UnusedVariable(
+                "elderberry".to_string()
+            ))]
+        );
+        assert_eq!(
+            analyzed.get_all_warnings(),
+            vec![SematicWarning::UnusedFunction("date".to_string())]
+        );
+    }
+
+    #[test]
+    fn deny_warnings() {
+        let source = include_str!("../tests/levels/project_levels.iv");
+        let analyzed = lex_then_analyze(
+            source,
+            AnalyzerOptions {
+                deny_warnings: true,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![
+                SematicError::DeniedWarning(SematicWarning::UnusedFunction("date".to_string())),
+                SematicError::DeniedWarning(SematicWarning::UnusedVariable(
+                    "elderberry".to_string()
+                )),
+            ]
+        );
+        assert_eq!(analyzed.get_all_warnings(), vec![]);
+    }
+}
@@ -1,4 +1,8 @@
 pub mod errors;
 pub mod function;
+mod levels;
+mod lint;
+pub mod options;
 pub mod program;
 mod scope;
+mod suggestions;
@@ -0,0 +1,596 @@
+use crate::errors::SematicWarning;
+use gneurshk_parser::{
+    Assignment, BinaryExpression, BinaryOperator, Block, CastExpression, ElseBranch, Expression,
+    FunctionCall, FunctionDeclaration, IfStatement, Lambda, LoopStmt, Return, Stmt,
+    UnaryExpression, UnaryOperator, VariableDeclaration, types::DataType,
+};
+
+/// Looks for code that is valid but most likely a mistake
+pub(crate) struct Linter {
+    function_name: String,
+    return_type: Option<DataType>,
+
+    warnings: Vec<SematicWarning>,
+}
+
+/// Lints the body of a function and returns the warnings that were found
+pub(crate) fn lint_function(function: &FunctionDeclaration) -> Vec<SematicWarning> {
+    let mut linter = Linter {
+        function_name: function.name.clone(),
+        return_type: function.return_type.clone(),
+        warnings: Vec::new(),
+    };
+
+    // The value of the body is only used as the implicit return of functions that return a value
+    if let Some(block) = &function.block {
+        linter.lint_block(block, function.return_type.is_some());
+    }
+
+    linter.warnings
+}
+
+impl Linter {
+    /// Lints the statements of a block, whose value is either used or discarded
+    fn lint_block(&mut self, block: &Block, value_used: bool) {
+        self.lint_statements(&block.body, value_used);
+    }
+
+    fn lint_statements(&mut self, statements: &[Stmt], value_used: bool) {
+        for (i, stmt) in statements.iter().enumerate() {
+            // The last statement is the value of the block, so it is allowed to be pure when that is used
+            let is_value = value_used && i == statements.len() - 1;
+
+            if !is_value && is_pure_statement(stmt) {
+                self.warnings.push(SematicWarning::NoEffect(stmt.span()));
+            }
+
+            self.lint_statement(stmt, is_value);
+        }
+    }
+
+    fn lint_statement(&mut self, stmt: &Stmt, is_value: bool) {
+        match stmt {
+            Stmt::VariableDeclaration(VariableDeclaration::Mutable {
+                data_type,
+                value: Some(value),
+                ..
+            })
+            | Stmt::VariableDeclaration(VariableDeclaration::Constant {
+                data_type, value, ..
+            }) => {
+                // Integer literals default to Int32 when no type is given
+                self.check_integer_overflow(value, data_type.as_ref().unwrap_or(&DataType::Int32));
+                self.lint_expression(value);
+            }
+            Stmt::VariableDeclaration(VariableDeclaration::Mutable { value: None, .. }) => {}
+            Stmt::Assignment(assignment) => self.lint_expression(&assignment.value),
+            Stmt::Block(block) => self.lint_block(block, is_value),
+            Stmt::IfStatement(if_stmt) => self.lint_if(if_stmt, is_value),
+            Stmt::Loop(loop_stmt) => self.lint_loop(loop_stmt),
+            Stmt::Return(Return {
+                value: Some(value), ..
+            }) => {
+                if let Some(return_type) = self.return_type.clone() {
+                    self.check_integer_overflow(value, &return_type);
+                }
+
+                self.lint_expression(value);
+            }
+            Stmt::BinaryExpression(expr) => self.lint_binary_expression(expr),
+            Stmt::UnaryExpression(UnaryExpression { value, .. }) => self.lint_expression(value),
+            Stmt::Cast(cast) => self.lint_expression(&cast.value),
+            Stmt::Try(try_expr) => self.lint_expression(&try_expr.value),
+            Stmt::Lambda(lambda) => self.lint_lambda(lambda),
+            Stmt::FunctionCall(call) => {
+                for arg in &call.args {
+                    self.lint_expression(arg);
+                }
+
+                for arg in &call.named_args {
+                    self.lint_expression(&arg.value);
+                }
+            }
+            Stmt::Return(Return { value: None, .. })
+            | Stmt::Identifier(_)
+            | Stmt::MemberAccess(_)
+            | Stmt::Integer(_)
+            | Stmt::Float(_)
+            | Stmt::Boolean(_)
+            | Stmt::String(_)
+            | Stmt::None(_)
+            | Stmt::Continue(_)
+            | Stmt::Break(_) => {}
+        }
+    }
+
+    fn lint_expression(&mut self, expr: &Expression) {
+        match expr {
+            Expression::BinaryExpression(expr) => self.lint_binary_expression(expr),
+            Expression::UnaryExpression(UnaryExpression { value, .. }) => {
+                self.lint_expression(value)
+            }
+            Expression::Cast(cast) => self.lint_expression(&cast.value),
+            Expression::Try(try_expr) => self.lint_expression(&try_expr.value),
+            Expression::Lambda(lambda) => self.lint_lambda(lambda),
+            Expression::FunctionCall(call) => {
+                for arg in &call.args {
+                    self.lint_expression(arg);
+                }
+
+                for arg in &call.named_args {
+                    self.lint_expression(&arg.value);
+                }
+            }
+            Expression::Block(block) => self.lint_block(block, true),
+            Expression::IfStatement(if_stmt) => self.lint_if(if_stmt, true),
+            Expression::Identifier(_)
+            | Expression::MemberAccess(_)
+            | Expression::Integer(_)
+            | Expression::Float(_)
+            | Expression::Boolean(_)
+            | Expression::String(_)
+            | Expression::None(_) => {}
+        }
+    }
+
+    fn lint_binary_expression(&mut self, expr: &BinaryExpression) {
+        let BinaryExpression {
+            left,
+            right,
+            operator,
+        } = expr;
+
+        // Comparing a variable with itself always has the same result
+        if is_comparison(operator)
+            && let (Expression::Identifier(left), Expression::Identifier(right)) =
+                (left.as_ref(), right.as_ref())
+            && left.name == right.name
+        {
+            self.warnings.push(SematicWarning::SelfComparison(
+                left.name.clone(),
+                left.span.start..right.span.end,
+            ));
+        }
+
+        // Dividing by a literal zero always fails
+        if matches!(operator, BinaryOperator::Divide | BinaryOperator::Modulus) {
+            match right.as_ref() {
+                Expression::Integer(literal) if literal.value == 0 => self
+                    .warnings
+                    .push(SematicWarning::DivisionByZero(literal.span.clone())),
+                Expression::Float(literal) if literal.value == 0.0 => self
+                    .warnings
+                    .push(SematicWarning::DivisionByZero(literal.span.clone())),
+                _ => {}
+            }
+        }
+
+        self.lint_expression(left);
+        self.lint_expression(right);
+    }
+
+    fn lint_if(&mut self, if_stmt: &IfStatement, value_used: bool) {
+        self.check_constant_condition(&if_stmt.condition);
+        self.lint_expression(&if_stmt.condition);
+
+        self.lint_block(&if_stmt.if_block, value_used);
+
+        match if_stmt.else_statement.as_deref() {
+            Some(ElseBranch::Block(block)) => self.lint_block(block, value_used),
+            Some(ElseBranch::IfStatement(if_stmt)) => self.lint_if(if_stmt, value_used),
+            None => {}
+        }
+    }
+
+    fn lint_lambda(&mut self, lambda: &Lambda) {
+        // Returns inside the body belong to the anonymous function
+        let outer_return_type =
+            std::mem::replace(&mut self.return_type, lambda.return_type.clone());
+
+        self.lint_block(&lambda.block, lambda.return_type.is_some());
+
+        self.return_type = outer_return_type;
+    }
+
+    fn lint_loop(&mut self, loop_stmt: &LoopStmt) {
+        let body = &loop_stmt.block.body;
+
+        // Loops that never exit are most likely missing a break
+        if !body.iter().any(exits_loop) {
+            self.warnings
+                .push(SematicWarning::InfiniteLoop(self.function_name.clone()));
+        }
+
+        // While loops are desugared into a loop that starts with a guard,
+        // so lint the original condition instead of the negated one
+        match body.split_first() {
+            Some((Stmt::IfStatement(guard), rest)) if is_while_guard(guard) => {
+                let Expression::UnaryExpression(UnaryExpression { value, .. }) =
+                    guard.condition.as_ref()
+                else {
+                    unreachable!()
+                };
+
+                self.check_constant_condition(value);
+                self.lint_expression(value);
+                self.lint_statements(rest, false);
+            }
+            _ => self.lint_statements(body, false),
+        }
+    }
+
+    fn check_constant_condition(&mut self, condition: &Expression) {
+        if let Some(value) = evaluate_constant_condition(condition) {
+            self.warnings
+                .push(SematicWarning::ConstantCondition(value, condition.span()));
+        }
+    }
+
+    fn check_integer_overflow(&mut self, value: &Expression, data_type: &DataType) {
+        let (literal, negative) = match value {
+            Expression::Integer(literal) => (literal, false),
+            Expression::UnaryExpression(UnaryExpression {
+                value,
+                operator: UnaryOperator::Negative,
+            }) => match value.as_ref() {
+                Expression::Integer(literal) => (literal, true),
+                _ => return,
+            },
+            _ => return,
+        };
+
+        // Get the largest magnitude the type can hold in each direction
+        let (negative_limit, positive_limit) = match data_type {
+            DataType::Int8 => (i8::MIN.unsigned_abs() as u64, i8::MAX as u64),
+            DataType::Int16 => (i16::MIN.unsigned_abs() as u64, i16::MAX as u64),
+            DataType::Int32 => (i32::MIN.unsigned_abs() as u64, i32::MAX as u64),
+            DataType::Int64 => (i64::MIN.unsigned_abs(), i64::MAX as u64),
+            DataType::UInt8 => (0, u8::MAX as u64),
+            DataType::UInt16 => (0, u16::MAX as u64),
+            DataType::UInt32 => (0, u32::MAX as u64),
+            DataType::UInt64 => (0, u64::MAX),
+            _ => return,
+        };
+
+        let fits = if negative {
+            literal.value <= negative_limit
+        } else {
+            literal.value <= positive_limit
+        };
+
+        if !fits {
+            let text = if negative {
+                format!("-{}", literal.value)
+            } else {
+                literal.value.to_string()
+            };
+
+            self.warnings.push(SematicWarning::IntegerLiteralOverflow(
+                text,
+                data_type.clone(),
+                literal.span.clone(),
+            ));
+        }
+    }
+}
+
+fn is_comparison(operator: &BinaryOperator) -> bool {
+    matches!(
+        operator,
+        BinaryOperator::Equal
+            | BinaryOperator::NotEqual
+            | BinaryOperator::GreaterThan
+            | BinaryOperator::GreaterThanEqual
+            | BinaryOperator::LessThan
+            | BinaryOperator::LessThanEqual
+    )
+}
+
+/// Checks if an if statement is the `if not condition { break }` guard of a while loop
+fn is_while_guard(if_stmt: &IfStatement) -> bool {
+    matches!(
+        if_stmt.condition.as_ref(),
+        Expression::UnaryExpression(UnaryExpression {
+            operator: UnaryOperator::Not,
+            ..
+        })
+    ) && if_stmt.binding.is_none()
+        && matches!(if_stmt.if_block.body.as_slice(), [Stmt::Break(_)])
+        && if_stmt.else_statement.is_none()
+}
+
+/// Checks if a statement can leave the loop it belongs to
+fn exits_loop(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::Break(_) | Stmt::Return(_) => true,
+        _ if returns_early(stmt) => true,
+        Stmt::Block(block) => block.body.iter().any(exits_loop),
+        Stmt::IfStatement(if_stmt) => if_exits_loop(if_stmt),
+        // Breaks inside of a nested loop only exit that loop, but returns exit every loop
+        Stmt::Loop(loop_stmt) => loop_stmt.block.body.iter().any(returns),
+        _ => false,
+    }
+}
+
+fn if_exits_loop(if_stmt: &IfStatement) -> bool {
+    if_stmt.if_block.body.iter().any(exits_loop)
+        || match if_stmt.else_statement.as_deref() {
+            Some(ElseBranch::Block(block)) => block.body.iter().any(exits_loop),
+            Some(ElseBranch::IfStatement(if_stmt)) => if_exits_loop(if_stmt),
+            None => false,
+        }
+}
+
+/// Checks if a statement contains a return statement
+fn returns(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::Return(_) => true,
+        _ if returns_early(stmt) => true,
+        Stmt::Block(block) => block.body.iter().any(returns),
+        Stmt::Loop(loop_stmt) => loop_stmt.block.body.iter().any(returns),
+        Stmt::IfStatement(if_stmt) => if_returns(if_stmt),
+        _ => false,
+    }
+}
+
+fn if_returns(if_stmt: &IfStatement) -> bool {
+    if_stmt.if_block.body.iter().any(returns)
+        || match if_stmt.else_statement.as_deref() {
+            Some(ElseBranch::Block(block)) => block.body.iter().any(returns),
+            Some(ElseBranch::IfStatement(if_stmt)) => if_returns(if_stmt),
+            None => false,
+        }
+}
+
+/// Evaluates a condition that only depends on literals
+fn evaluate_constant_condition(expr: &Expression) -> Option<bool> {
+    match expr {
+        Expression::Boolean(literal) => Some(literal.value),
+        Expression::UnaryExpression(UnaryExpression {
+            value,
+            operator: UnaryOperator::Not,
+        }) => evaluate_constant_condition(value).map(|value| !value),
+        Expression::BinaryExpression(BinaryExpression {
+            left,
+            right,
+            operator,
+        }) => match (left.as_ref(), right.as_ref()) {
+            (Expression::Integer(left), Expression::Integer(right)) => match operator {
+                BinaryOperator::Equal => Some(left.value == right.value),
+                BinaryOperator::NotEqual => Some(left.value != right.value),
+                BinaryOperator::GreaterThan => Some(left.value > right.value),
+                BinaryOperator::GreaterThanEqual => Some(left.value >= right.value),
+                BinaryOperator::LessThan => Some(left.value < right.value),
+                BinaryOperator::LessThanEqual => Some(left.value <= right.value),
+                _ => None,
+            },
+            _ => {
+                let left = evaluate_constant_condition(left)?;
+                let right = evaluate_constant_condition(right)?;
+
+                match operator {
+                    BinaryOperator::And => Some(left && right),
+                    BinaryOperator::Or => Some(left || right),
+                    BinaryOperator::Equal => Some(left == right),
+                    BinaryOperator::NotEqual => Some(left != right),
+                    _ => None,
+                }
+            }
+        },
+        _ => None,
+    }
+}
+
+/// Checks if a statement only computes a value without any side effects
+fn is_pure_statement(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::Integer(_)
+        | Stmt::Float(_)
+        | Stmt::Boolean(_)
+        | Stmt::String(_)
+        | Stmt::None(_)
+        | Stmt::Lambda(_)
+        | Stmt::Identifier(_) => true,
+        Stmt::BinaryExpression(BinaryExpression { left, right, .. }) => {
+            is_pure_expression(left) && is_pure_expression(right)
+        }
+        Stmt::UnaryExpression(UnaryExpression { value, .. }) => is_pure_expression(value),
+        Stmt::Cast(cast) => is_pure_expression(&cast.value),
+        _ => false,
+    }
+}
+
+fn is_pure_expression(expr: &Expression) -> bool {
+    match expr {
+        Expression::Integer(_)
+        | Expression::Float(_)
+        | Expression::Boolean(_)
+        | Expression::String(_)
+        | Expression::None(_)
+        | Expression::Lambda(_)
+        | Expression::Identifier(_) => true,
+        Expression::BinaryExpression(BinaryExpression { left, right, .. }) => {
+            is_pure_expression(left) && is_pure_expression(right)
+        }
+        Expression::UnaryExpression(UnaryExpression { value, .. }) => is_pure_expression(value),
+        Expression::Cast(cast) => is_pure_expression(&cast.value),
+        _ => false,
+    }
+}
+
+/// Checks if the expressions of a statement can return from the function,
+/// either through the '?' operator or through a block that returns
+///
+/// The statements inside of blocks, ifs and loops are checked separately
+fn returns_early(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::Try(_) => true,
+        Stmt::VariableDeclaration(VariableDeclaration::Mutable {
+            value: Some(value), ..
+        })
+        | Stmt::VariableDeclaration(VariableDeclaration::Constant { value, .. })
+        | Stmt::Assignment(Assignment { value, .. })
+        | Stmt::Return(Return {
+            value: Some(value), ..
+        }) => expression_returns_early(value),
+        Stmt::IfStatement(if_stmt) => expression_returns_early(&if_stmt.condition),
+        Stmt::BinaryExpression(BinaryExpression { left, right, .. }) => {
+            expression_returns_early(left) || expression_returns_early(right)
+        }
+        Stmt::UnaryExpression(UnaryExpression { value, .. })
+        | Stmt::Cast(CastExpression { value, .. }) => expression_returns_early(value),
+        Stmt::FunctionCall(call) => call_returns_early(call),
+        _ => false,
+    }
+}
+
+/// Checks if an expression can return from the function, anonymous functions only return from themselves
+fn expression_returns_early(expr: &Expression) -> bool {
+    match expr {
+        Expression::Try(_) => true,
+        Expression::BinaryExpression(BinaryExpression { left, right, .. }) => {
+            expression_returns_early(left) || expression_returns_early(right)
+        }
+        Expression::UnaryExpression(UnaryExpression { value, .. })
+        | Expression::Cast(CastExpression { value, .. }) => expression_returns_early(value),
+        Expression::FunctionCall(call) => call_returns_early(call),
+        Expression::Block(block) => block.body.iter().any(returns),
+        Expression::IfStatement(if_stmt) => {
+            expression_returns_early(&if_stmt.condition) || if_returns(if_stmt)
+        }
+        _ => false,
+    }
+}
+
+fn call_returns_early(call: &FunctionCall) -> bool {
+    call.args.iter().any(expression_returns_early)
+        || call
+            .named_args
+            .iter()
+            .any(|arg| expression_returns_early(&arg.value))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        errors::SematicWarning,
+        program::{AnalyzedProgram, ProgramAnalyzer},
+    };
+    use gneurshk_lexer::lex;
+    use gneurshk_parser::{parse, types::DataType};
+
+    /// Helper function for testing the analyze function
+    fn lex_then_analyze(input: &'static str) -> AnalyzedProgram {
+        let tokens = lex(input).expect("Failed to lex");
+
+        match parse(&mut tokens.clone()) {
+            Ok(result) => ProgramAnalyzer::analyze(result),
+            Err(e) => panic!("Parsing error: {e}"),
+        }
+    }
+
+    #[test]
+    fn constant_conditions() {
+        let source = include_str!("../tests/lints/constant_conditions.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_warnings(),
+            vec![
+                SematicWarning::ConstantCondition(true, Some(21..25)),
+                SematicWarning::ConstantCondition(false, Some(71..76)),
+            ]
+        );
+    }
+
+    #[test]
+    fn infinite_loop() {
+        let source = include_str!("../tests/lints/infinite_loop.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_warnings(),
+            vec![SematicWarning::InfiniteLoop("main".to_string())]
+        );
+    }
+
+    #[test]
+    fn loop_with_try() {
+        let source = include_str!("../tests/lints/loop_with_try.iv");
+        let analyzed = lex_then_analyze(source);
+
+        // Loops that pass on an error with '?' can still be left
+        assert_eq!(analyzed.get_all_errors(), vec![]);
+        assert_eq!(
+            analyzed.get_all_warnings(),
+            vec![SematicWarning::UnusedVariable("pears".to_string())]
+        );
+    }
+
+    #[test]
+    fn self_comparison() {
+        let source = include_str!("../tests/lints/self_comparison.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_warnings(),
+            vec![SematicWarning::SelfComparison("banana".to_string(), 41..57)]
+        );
+    }
+
+    #[test]
+    fn division_by_zero() {
+        let source = include_str!("../tests/lints/division_by_zero.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_warnings(),
+            vec![
+                SematicWarning::DivisionByZero(58..59),
+                SematicWarning::DivisionByZero(90..91),
+            ]
+        );
+    }
+
+    #[test]
+    fn integer_literal_overflow() {
+        let source = include_str!("../tests/lints/integer_literal_overflow.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_warnings(),
+            vec![
+                SematicWarning::IntegerLiteralOverflow("300".to_string(), DataType::UInt8, 33..36),
+                SematicWarning::IntegerLiteralOverflow(
+                    "-129".to_string(),
+                    DataType::Int8,
+                    103..106
+                ),
+                SematicWarning::IntegerLiteralOverflow(
+                    "3000000000".to_string(),
+                    DataType::Int32,
+                    123..133
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn no_effect() {
+        let source = include_str!("../tests/lints/no_effect.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(analyzed.get_all_errors(), vec![]);
+        assert_eq!(
+            analyzed.get_all_warnings(),
+            vec![
+                SematicWarning::NoEffect(Some(37..46)),
+                SematicWarning::NoEffect(Some(52..57)),
+                SematicWarning::NoEffect(Some(62..67)),
+                // The value of the if statement is discarded, so its branches have no effect
+                SematicWarning::NoEffect(Some(105..110)),
+                SematicWarning::NoEffect(Some(132..133)),
+            ]
+        );
+    }
+}
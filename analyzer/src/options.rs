@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+/// The names of every lint that can be configured
+pub const LINT_NAMES: [&str; 12] = [
+    "unused_variable",
+    "unused_parameter",
+    "unused_function",
+    "unused_import",
+    "shadowed_variable",
+    "unreachable_code",
+    "constant_condition",
+    "infinite_loop",
+    "self_comparison",
+    "division_by_zero",
+    "integer_literal_overflow",
+    "no_effect",
+];
+
+/// How a lint should be reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintLevel {
+    /// Do not report the lint
+    Allow,
+    /// Report the lint as a warning
+    Warn,
+    /// Report the lint as an error
+    Deny,
+}
+
+impl LintLevel {
+    /// Gets the lint level from its name, as used by annotations and the project config
+    pub fn from_name(name: &str) -> Option<LintLevel> {
+        match name {
+            "allow" => Some(LintLevel::Allow),
+            "warn" => Some(LintLevel::Warn),
+            "deny" => Some(LintLevel::Deny),
+            _ => None,
+        }
+    }
+}
+
+/// Settings that control which optional diagnostics the analyzer reports
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnalyzerOptions {
+    /// The level of each lint, any lint not listed is a warning
+    pub lint_levels: HashMap<String, LintLevel>,
+    /// Report every warning that is not explicitly allowed as an error
+    pub deny_warnings: bool,
+}
@@ -0,0 +1,18 @@
+use gneurshk_parser::{ImportCollection, ImportModule, ImportModules, ImportStmt};
+
+/// Returns the names an import statement makes available to the program
+///
+/// Wildcard imports are not included since the names they bring in are unknown
+pub(crate) fn get_imported_names(import: &ImportStmt) -> Vec<String> {
+    match import {
+        ImportStmt::Module(ImportModule { module, alias }) => {
+            vec![alias.clone().unwrap_or_else(|| module.clone())]
+        }
+        ImportStmt::Modules(ImportModules { modules: items })
+        | ImportStmt::Collection(ImportCollection { items, .. }) => items
+            .iter()
+            .map(|(name, alias)| alias.clone().unwrap_or_else(|| name.clone()))
+            .collect(),
+        ImportStmt::Everything(_) => Vec::new(),
+    }
+}
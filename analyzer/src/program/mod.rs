@@ -1,10 +1,16 @@
 use crate::{
     errors::{SematicError, SematicWarning},
     function::{AnalyzedFunction, FunctionAnalyzer},
+    levels::apply_lint_levels,
+    options::AnalyzerOptions,
+    program::imports::get_imported_names,
     scope::{Function, Scope},
 };
-use gneurshk_parser::{FunctionDeclaration, Program};
-use std::collections::HashMap;
+use gneurshk_parser::{FunctionDeclaration, Program, types::DataType};
+use std::collections::{HashMap, HashSet};
+
+mod imports;
+mod type_aliases;
 
 #[derive(Debug)]
 pub struct AnalyzedProgram {
@@ -40,6 +46,13 @@ impl AnalyzedProgram {
 pub struct ProgramAnalyzer {
     pub(crate) scope: Box<Scope>,
     pub(crate) functions: HashMap<String, Function>,
+    pub(crate) options: AnalyzerOptions,
+    /// The names of every function called from within each function
+    pub(crate) calls: HashMap<String, HashSet<String>>,
+    /// Whether each imported name has been used
+    pub(crate) imports: HashMap<String, bool>,
+    /// The type every type alias refers to
+    pub(crate) type_aliases: HashMap<String, DataType>,
 
     pub(crate) errors: Vec<SematicError>,
     pub(crate) warnings: Vec<SematicWarning>,
@@ -47,32 +60,86 @@ pub struct ProgramAnalyzer {
 
 impl ProgramAnalyzer {
     pub fn analyze(program: Program) -> AnalyzedProgram {
+        Self::analyze_with_options(program, AnalyzerOptions::default())
+    }
+
+    pub fn analyze_with_options(program: Program, options: AnalyzerOptions) -> AnalyzedProgram {
         // Create an analyzer instance
         let mut analyzer = ProgramAnalyzer {
             scope: Box::new(Scope::new(None)),
             functions: HashMap::new(),
+            options,
+            calls: HashMap::new(),
+            imports: HashMap::new(),
+            type_aliases: HashMap::new(),
             errors: Vec::new(),
             warnings: Vec::new(),
         };
 
+        // Register all imported names
+        let imported_names: Vec<String> = program
+            .imports
+            .iter()
+            .flat_map(get_imported_names)
+            .collect();
+
+        for name in &imported_names {
+            analyzer.imports.insert(name.clone(), false);
+        }
+
+        // Resolve the type aliases before they are used in any signature
+        analyzer.register_type_aliases(&program.type_aliases);
+
         // Register all function signatures
         for function in program.functions.clone() {
             let FunctionDeclaration {
                 name,
+                type_params,
                 params,
                 return_type,
+                variadic,
                 ..
-            } = function;
+            } = analyzer.resolve_function_types(function);
+
+            // Keep the first definition and report any later ones
+            if analyzer.functions.contains_key(&name) {
+                analyzer.errors.push(SematicError::FunctionRedefined(name));
+                continue;
+            }
 
             analyzer.functions.insert(
                 name,
                 Function {
+                    type_params,
                     return_type,
                     params,
+                    variadic,
                 },
             );
         }
 
+        let function_names: Vec<String> = program
+            .functions
+            .iter()
+            .map(|function| function.name.clone())
+            .collect();
+
+        let extern_functions: HashSet<String> = program
+            .functions
+            .iter()
+            .filter(|function| function.has_annotation("extern"))
+            .map(|function| function.name.clone())
+            .collect();
+
+        // Tests and exported functions are called from outside of the program
+        let mut entry_points = vec!["main".to_string()];
+
+        for function in &program.functions {
+            if function.has_annotation("test") || function.has_annotation("export") {
+                entry_points.push(function.name.clone());
+            }
+        }
+
         // Analyze each function
         let analyzed_functions: Vec<AnalyzedFunction> = program
             .functions
@@ -81,12 +148,49 @@ impl ProgramAnalyzer {
             .collect();
 
         // Check for unused variables before exiting the scope
+        let mut warnings = Vec::new();
+
         for variable in analyzer.scope.get_unused_variables() {
-            analyzer
-                .warnings
-                .push(SematicWarning::UnusedVariable(variable.name));
+            warnings.push(SematicWarning::UnusedVariable(variable.name));
+        }
+
+        // Check for functions that can never be called from the entry point
+        if analyzer.functions.contains_key("main") {
+            let reachable = analyzer.get_reachable_functions(entry_points);
+
+            for (name, function) in function_names.into_iter().zip(&analyzed_functions) {
+                // External functions are only declarations, so they are never reported
+                if !reachable.contains(&name) && !extern_functions.contains(&name) {
+                    // The function's own annotations decide how it is reported
+                    let mut unused = apply_lint_levels(
+                        &function.lint_levels,
+                        analyzer.options.deny_warnings,
+                        vec![SematicWarning::UnusedFunction(name)],
+                        &mut analyzer.errors,
+                    );
+
+                    analyzer.warnings.append(&mut unused);
+                }
+            }
+        }
+
+        // Check for imports that are never referenced
+        for name in imported_names {
+            if analyzer.imports.get(&name) == Some(&false) {
+                warnings.push(SematicWarning::UnusedImport(name));
+            }
         }
 
+        // Apply the project wide lint levels
+        let mut warnings = apply_lint_levels(
+            &analyzer.options.lint_levels,
+            analyzer.options.deny_warnings,
+            warnings,
+            &mut analyzer.errors,
+        );
+
+        analyzer.warnings.append(&mut warnings);
+
         // Return a static analyzed program
         AnalyzedProgram {
             analyzed_functions,
@@ -95,3 +199,106 @@ impl ProgramAnalyzer {
         }
     }
 }
+
+impl ProgramAnalyzer {
+    /// Returns every function that can be called, directly or indirectly, from the given functions
+    fn get_reachable_functions(&self, entries: Vec<String>) -> HashSet<String> {
+        let mut reachable: HashSet<String> = entries.iter().cloned().collect();
+        let mut pending = entries;
+
+        while let Some(name) = pending.pop() {
+            for callee in self.calls.get(&name).into_iter().flatten() {
+                if reachable.insert(callee.clone()) {
+                    pending.push(callee.clone());
+                }
+            }
+        }
+
+        reachable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        errors::{SematicError, SematicWarning},
+        program::{AnalyzedProgram, ProgramAnalyzer},
+    };
+    use gneurshk_lexer::lex;
+    use gneurshk_parser::parse;
+
+    /// Helper function for testing the analyze function
+    fn lex_then_analyze(input: &'static str) -> AnalyzedProgram {
+        let tokens = lex(input).expect("Failed to lex");
+
+        match parse(&mut tokens.clone()) {
+            Ok(result) => ProgramAnalyzer::analyze(result),
+            Err(e) => panic!("Parsing error: {e}"),
+        }
+    }
+
+    #[test]
+    fn duplicate_functions() {
+        let source = include_str!("../../tests/program/duplicate_functions.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::FunctionRedefined("mango".to_string())]
+        );
+    }
+
+    #[test]
+    fn duplicate_parameters() {
+        let source = include_str!("../../tests/program/duplicate_parameters.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::DuplicateParameter(
+                "nectarine".to_string(),
+                "a".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn unused_functions() {
+        let source = include_str!("../../tests/program/unused_functions.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_warnings(),
+            vec![
+                SematicWarning::UnusedFunction("orange".to_string()),
+                SematicWarning::UnusedFunction("papaya".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn unused_imports() {
+        let source = include_str!("../../tests/program/unused_imports.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(analyzed.get_all_errors(), vec![]);
+        assert_eq!(
+            analyzed.get_all_warnings(),
+            vec![SematicWarning::UnusedImport("cosine".to_string())]
+        );
+    }
+
+    #[test]
+    fn unused_parameters() {
+        let source = include_str!("../../tests/program/unused_parameters.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_warnings(),
+            vec![SematicWarning::UnusedParameter(
+                "raspberry".to_string(),
+                "unused".to_string()
+            )]
+        );
+    }
+}
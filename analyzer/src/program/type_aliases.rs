@@ -0,0 +1,272 @@
+use crate::{
+    errors::SematicError,
+    program::ProgramAnalyzer,
+    suggestions::{PRIMITIVE_TYPES, find_similar_name},
+};
+use gneurshk_parser::{
+    FunctionDeclaration, FunctionParam, TypeAlias,
+    types::{DataType, FunctionType},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+impl ProgramAnalyzer {
+    /// Resolves every type alias to the type it refers to, reporting cycles and unknown types
+    pub(crate) fn register_type_aliases(&mut self, type_aliases: &[TypeAlias]) {
+        let mut declared: HashMap<&str, &TypeAlias> = HashMap::new();
+
+        for type_alias in type_aliases {
+            // Aliases can't replace built-in types or other aliases
+            if declared.contains_key(type_alias.name.as_str())
+                || DataType::from_str(&type_alias.name).is_ok()
+            {
+                self.errors.push(SematicError::TypeAliasRedefined(
+                    type_alias.name.clone(),
+                    type_alias.span.clone(),
+                ));
+                continue;
+            }
+
+            declared.insert(&type_alias.name, type_alias);
+        }
+
+        for type_alias in type_aliases {
+            // Skip the aliases that were already reported as redefined
+            if !declared
+                .get(type_alias.name.as_str())
+                .is_some_and(|declared| std::ptr::eq(*declared, type_alias))
+            {
+                continue;
+            }
+
+            match resolve_type_alias(&declared, type_alias) {
+                Ok(Some(data_type)) => {
+                    self.type_aliases.insert(type_alias.name.clone(), data_type);
+                }
+                Ok(None) => {}
+                Err(error) => self.errors.push(error),
+            }
+        }
+    }
+
+    /// Replaces a type alias with the type it refers to
+    pub(crate) fn resolve_type(&self, data_type: &DataType) -> DataType {
+        match data_type {
+            DataType::Custom(name) => self
+                .type_aliases
+                .get(name)
+                .cloned()
+                .unwrap_or_else(|| data_type.clone()),
+            DataType::Optional(inner) => DataType::Optional(Box::new(self.resolve_type(inner))),
+            DataType::Result { value, error } => DataType::Result {
+                value: Box::new(self.resolve_type(value)),
+                error: Box::new(self.resolve_type(error)),
+            },
+            DataType::Function(FunctionType {
+                params,
+                return_type,
+            }) => DataType::Function(FunctionType {
+                params: params
+                    .iter()
+                    .map(|param| self.resolve_type(param))
+                    .collect(),
+                return_type: return_type
+                    .as_ref()
+                    .map(|return_type| Box::new(self.resolve_type(return_type))),
+            }),
+            _ => data_type.clone(),
+        }
+    }
+
+    /// Resolves the type aliases in the signature of a function, leaving its type parameters as they are
+    pub(crate) fn resolve_function_types(
+        &self,
+        function: FunctionDeclaration,
+    ) -> FunctionDeclaration {
+        let resolve = |data_type: &DataType| match data_type {
+            DataType::Custom(name) if function.type_params.contains(name) => data_type.clone(),
+            _ => self.resolve_type(data_type),
+        };
+
+        let params = function
+            .params
+            .iter()
+            .map(|param| FunctionParam {
+                data_type: resolve(&param.data_type),
+                ..param.clone()
+            })
+            .collect();
+        let return_type = function.return_type.as_ref().map(resolve);
+
+        FunctionDeclaration {
+            params,
+            return_type,
+            ..function
+        }
+    }
+}
+
+/// Follows a chain of aliases until it reaches a type that isn't an alias
+///
+/// Problems further along the chain are only reported by the alias they belong to
+fn resolve_type_alias(
+    declared: &HashMap<&str, &TypeAlias>,
+    type_alias: &TypeAlias,
+) -> Result<Option<DataType>, SematicError> {
+    let mut visited = vec![type_alias.name.as_str()];
+    let mut current = type_alias;
+
+    loop {
+        let data_type = match current.types.as_slice() {
+            [data_type] => data_type,
+            _ if std::ptr::eq(current, type_alias) => {
+                return Err(SematicError::UnsupportedUnionType(
+                    type_alias.name.clone(),
+                    type_alias.span.clone(),
+                ));
+            }
+            _ => return Ok(None),
+        };
+
+        let DataType::Custom(name) = data_type else {
+            // Optionals, results and function types can't contain the alias itself either
+            if refers_to_alias(declared, data_type, &type_alias.name, &mut HashSet::new()) {
+                return Err(SematicError::TypeAliasCycle(
+                    type_alias.name.clone(),
+                    type_alias.span.clone(),
+                ));
+            }
+
+            return Ok(Some(data_type.clone()));
+        };
+
+        if visited.contains(&name.as_str()) {
+            // Only the aliases that are part of the cycle report it
+            if name == &type_alias.name {
+                return Err(SematicError::TypeAliasCycle(
+                    type_alias.name.clone(),
+                    type_alias.span.clone(),
+                ));
+            }
+
+            return Ok(None);
+        }
+
+        current = match declared.get(name.as_str()) {
+            Some(next) => next,
+            None if std::ptr::eq(current, type_alias) => {
+                let candidates = PRIMITIVE_TYPES.into_iter().chain(declared.keys().copied());
+
+                return Err(SematicError::UnknownType(
+                    name.clone(),
+                    find_similar_name(name, candidates),
+                    type_alias.span.clone(),
+                ));
+            }
+            None => return Ok(None),
+        };
+
+        visited.push(name);
+    }
+}
+
+/// Whether a type refers to the alias, either directly or through the types and aliases inside of it
+fn refers_to_alias<'a>(
+    declared: &HashMap<&str, &'a TypeAlias>,
+    data_type: &'a DataType,
+    alias_name: &str,
+    visited: &mut HashSet<&'a str>,
+) -> bool {
+    let inner_types: Vec<&DataType> = match data_type {
+        DataType::Custom(name) if name == alias_name => return true,
+        // Every alias only has to be followed once
+        DataType::Custom(name) if visited.insert(name) => match declared.get(name.as_str()) {
+            Some(next) => next.types.iter().collect(),
+            None => vec![],
+        },
+        DataType::Optional(inner) => vec![inner],
+        DataType::Result { value, error } => vec![value, error],
+        DataType::Function(FunctionType {
+            params,
+            return_type,
+        }) => params.iter().chain(return_type.as_deref()).collect(),
+        _ => vec![],
+    };
+
+    inner_types
+        .into_iter()
+        .any(|inner_type| refers_to_alias(declared, inner_type, alias_name, visited))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        errors::SematicError,
+        program::{AnalyzedProgram, ProgramAnalyzer},
+    };
+    use gneurshk_lexer::lex;
+    use gneurshk_parser::{BinaryOperator, parse, types::DataType};
+
+    /// Helper function for testing the analyze function
+    fn lex_then_analyze(input: &'static str) -> AnalyzedProgram {
+        let tokens = lex(input).expect("Failed to lex");
+
+        match parse(&mut tokens.clone()) {
+            Ok(result) => ProgramAnalyzer::analyze(result),
+            Err(e) => panic!("Parsing error: {e}"),
+        }
+    }
+
+    #[test]
+    fn type_aliases() {
+        let source = include_str!("../../tests/type_aliases/type_aliases.iv");
+        let analyzed = lex_then_analyze(source);
+
+        // Aliases are interchangeable with the type they refer to
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![SematicError::UnsupportedOperator(
+                DataType::Float32,
+                BinaryOperator::Add,
+                DataType::Int32
+            )]
+        );
+    }
+
+    #[test]
+    fn invalid_type_aliases() {
+        let source = include_str!("../../tests/type_aliases/invalid_type_aliases.iv");
+        let analyzed = lex_then_analyze(source);
+
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![
+                SematicError::TypeAliasRedefined("Int32".to_string(), 5..10),
+                SematicError::TypeAliasRedefined("Apple".to_string(), 42..47),
+                SematicError::TypeAliasCycle("Apple".to_string(), 24..29),
+                SematicError::TypeAliasCycle("Pear".to_string(), 61..65),
+                SematicError::UnknownType("Banan".to_string(), None, 79..83),
+                SematicError::UnsupportedUnionType("Fruit".to_string(), 97..102),
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_type_alias_cycles() {
+        let source = include_str!("../../tests/type_aliases/nested_type_alias_cycles.iv");
+        let analyzed = lex_then_analyze(source);
+
+        // Aliases that only use a cycle aren't part of it
+        assert_eq!(
+            analyzed.get_all_errors(),
+            vec![
+                SematicError::TypeAliasCycle("Maybe".to_string(), 5..10),
+                SematicError::TypeAliasCycle("Left".to_string(), 25..29),
+                SematicError::TypeAliasCycle("Right".to_string(), 59..64),
+                SematicError::TypeAliasCycle("Callback".to_string(), 77..85),
+            ]
+        );
+    }
+}
@@ -1,9 +1,20 @@
-use gneurshk_parser::{FunctionParam, types::DataType};
-use std::collections::HashMap;
+use gneurshk_parser::{
+    FunctionParam,
+    types::{DataType, FunctionType},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
+
+/// The names of every initialized variable in a scope chain, ordered from the outermost scope
+pub type Initialization = Vec<HashSet<String>>;
 
 #[derive(Clone, Debug)]
 pub struct Scope {
     pub(crate) parent: Option<Box<Scope>>,
+    /// Whether the scope holds the parameters of an anonymous function, which captures outer variables
+    pub(crate) closure: bool,
 
     variables: HashMap<String, Variable>,
 }
@@ -15,18 +26,41 @@ pub struct Variable {
     pub(crate) mutable: bool,
     pub(crate) used: bool,
     pub(crate) initialized: bool,
+    /// Whether the variable is a parameter of the function
+    pub(crate) parameter: bool,
+    /// The location where the variable was declared
+    pub(crate) span: Range<usize>,
 }
 
 #[derive(Clone, Debug)]
 pub struct Function {
+    /// The names of the type parameters of a generic function
+    pub(crate) type_params: Vec<String>,
     pub(crate) return_type: Option<DataType>,
     pub(crate) params: Vec<FunctionParam>,
+    /// Whether the function accepts extra arguments after its parameters
+    pub(crate) variadic: bool,
+}
+
+impl Function {
+    /// Gets the type of the function when it is used as a value
+    pub fn function_type(&self) -> FunctionType {
+        FunctionType {
+            params: self
+                .params
+                .iter()
+                .map(|param| param.data_type.clone())
+                .collect(),
+            return_type: self.return_type.clone().map(Box::new),
+        }
+    }
 }
 
 impl Scope {
     pub fn new(parent: Option<Box<Scope>>) -> Self {
         Self {
             parent,
+            closure: false,
 
             variables: HashMap::new(),
         }
@@ -36,6 +70,10 @@ impl Scope {
         self.variables.insert(name, variable);
     }
 
+    pub fn has_local_variable(&self, name: &String) -> bool {
+        self.variables.contains_key(name)
+    }
+
     pub fn get_variable(&self, name: &String) -> Option<Variable> {
         self.variables.get(name).cloned().or_else(|| {
             self.parent
@@ -52,11 +90,73 @@ impl Scope {
         })
     }
 
+    /// Checks if a variable is declared outside of the anonymous function that the scope belongs to
+    pub fn is_captured(&self, name: &String) -> bool {
+        if self.variables.contains_key(name) {
+            return false;
+        }
+
+        match &self.parent {
+            Some(parent) if self.closure => parent.get_variable(name).is_some(),
+            Some(parent) => parent.is_captured(name),
+            None => false,
+        }
+    }
+
+    /// Returns every unused variable in this scope, except those prefixed with an underscore
     pub fn get_unused_variables(&self) -> Vec<Variable> {
         self.variables
             .iter()
-            .filter(|(_, variable)| !variable.used)
+            .filter(|(_, variable)| !variable.used && !variable.name.starts_with('_'))
             .map(|(_, var)| var.clone())
             .collect::<Vec<Variable>>()
     }
+
+    /// Captures which variables are currently initialized throughout the scope chain
+    pub fn get_initialization(&self) -> Initialization {
+        let mut initialization = match &self.parent {
+            Some(parent) => parent.get_initialization(),
+            None => Vec::new(),
+        };
+
+        initialization.push(
+            self.variables
+                .values()
+                .filter(|variable| variable.initialized)
+                .map(|variable| variable.name.clone())
+                .collect(),
+        );
+
+        initialization
+    }
+
+    /// Restores a previously captured initialization state throughout the scope chain
+    ///
+    /// Scopes that are deeper than the current one are ignored
+    pub fn set_initialization(&mut self, initialization: &Initialization) {
+        if let Some(initialized) = initialization.get(self.depth()) {
+            for variable in self.variables.values_mut() {
+                variable.initialized = initialized.contains(&variable.name);
+            }
+        }
+
+        if let Some(parent) = self.parent.as_mut() {
+            parent.set_initialization(initialization);
+        }
+    }
+
+    fn depth(&self) -> usize {
+        match &self.parent {
+            Some(parent) => parent.depth() + 1,
+            None => 0,
+        }
+    }
+}
+
+/// Merges two initialization states so only variables initialized in both remain initialized
+pub fn merge_initialization(left: Initialization, right: Initialization) -> Initialization {
+    left.into_iter()
+        .zip(right)
+        .map(|(left, right)| left.intersection(&right).cloned().collect())
+        .collect()
 }
@@ -0,0 +1,83 @@
+/// The names of the built-in types
+pub(crate) const PRIMITIVE_TYPES: [&str; 12] = [
+    "Int8", "Int16", "Int32", "Int64", "UInt8", "UInt16", "UInt32", "UInt64", "Float32", "Float64",
+    "String", "Boolean",
+];
+
+/// Finds the candidate that is closest to a misspelled name, if it is close enough to be a typo
+pub(crate) fn find_similar_name<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<String> {
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+/// Counts the insertions, deletions, substitutions and swaps of neighbouring characters
+/// needed to turn one string into the other
+fn edit_distance(from: &str, to: &str) -> usize {
+    let from: Vec<char> = from.chars().collect();
+    let to: Vec<char> = to.chars().collect();
+
+    // The distances between every prefix of `from` and every prefix of `to`
+    let mut distances = vec![vec![0; to.len() + 1]; from.len() + 1];
+
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=from.len() {
+        for j in 1..=to.len() {
+            let cost = usize::from(from[i - 1] != to[j - 1]);
+
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+
+            // Swapped characters are a common typo, such as `Int23`
+            if i > 1 && j > 1 && from[i - 1] == to[j - 2] && from[i - 2] == to[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[from.len()][to.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PRIMITIVE_TYPES, edit_distance, find_similar_name};
+
+    #[test]
+    fn distances() {
+        assert_eq!(edit_distance("Int32", "Int32"), 0);
+        assert_eq!(edit_distance("Int23", "Int32"), 1);
+        assert_eq!(edit_distance("Flot32", "Float32"), 1);
+        assert_eq!(edit_distance("", "String"), 6);
+    }
+
+    #[test]
+    fn similar_names() {
+        assert_eq!(
+            find_similar_name("Int23", PRIMITIVE_TYPES),
+            Some("Int32".to_string())
+        );
+        assert_eq!(
+            find_similar_name("boolean", PRIMITIVE_TYPES),
+            Some("Boolean".to_string())
+        );
+        assert_eq!(find_similar_name("Apple", PRIMITIVE_TYPES), None);
+    }
+}
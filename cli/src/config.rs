@@ -0,0 +1,136 @@
+use anyhow::{Result, anyhow};
+use gneurshk_analyzer::options::{AnalyzerOptions, LINT_NAMES, LintLevel};
+use gneurshk_compiler::output::linker::Linker;
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+/// The name of the project config file
+pub(crate) const CONFIG_FILE_NAME: &str = "gneurshk.toml";
+
+/// The directory of the build cache, relative to the project root
+const CACHE_DIR: &str = "target/gneurshk";
+
+/// The environment variable that selects the linker
+const LINKER_ENV_VAR: &str = "GNEURSHK_LINKER";
+
+/// Loads the analyzer options from the project config closest to the given source file
+///
+/// The config can set the level of each lint in its `[lints]` table:
+///
+/// ```toml
+/// [lints]
+/// unused_variable = "allow"
+/// no_effect = "deny"
+/// ```
+pub(crate) fn load_analyzer_options(path: &Path, deny_warnings: bool) -> Result<AnalyzerOptions> {
+    let mut options = AnalyzerOptions {
+        deny_warnings,
+        ..Default::default()
+    };
+
+    let Some((config_path, config)) = find_config(path)? else {
+        return Ok(options);
+    };
+
+    let Some(lints) = config.get("lints") else {
+        return Ok(options);
+    };
+
+    let lints = lints.as_table().ok_or_else(|| {
+        anyhow!(
+            "Expected 'lints' to be a table in {}",
+            config_path.display()
+        )
+    })?;
+
+    // Read the level of each lint
+    for (name, level) in lints {
+        if !LINT_NAMES.contains(&name.as_str()) {
+            return Err(anyhow!(
+                "Unknown lint '{name}' in {}",
+                config_path.display()
+            ));
+        }
+
+        let level = level
+            .as_str()
+            .and_then(LintLevel::from_name)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Expected the level of lint '{name}' to be 'allow', 'warn' or 'deny' in {}",
+                    config_path.display()
+                )
+            })?;
+
+        options.lint_levels.insert(name.clone(), level);
+    }
+
+    Ok(options)
+}
+
+/// Finds the linker for the given source file
+///
+/// The linker given on the command line is used first, then the one in the `GNEURSHK_LINKER`
+/// environment variable, and then the one in the `[build]` table of the project config:
+///
+/// ```toml
+/// [build]
+/// linker = "clang"
+/// ```
+pub(crate) fn load_linker(path: &Path, linker: Option<&str>) -> Result<Linker> {
+    if let Some(linker) = linker {
+        return Ok(Linker::from_name(linker));
+    }
+
+    if let Ok(linker) = std::env::var(LINKER_ENV_VAR) {
+        return Ok(Linker::from_name(&linker));
+    }
+
+    let Some((config_path, config)) = find_config(path)? else {
+        return Ok(Linker::default());
+    };
+
+    let Some(linker) = config.get("build").and_then(|build| build.get("linker")) else {
+        return Ok(Linker::default());
+    };
+
+    let linker = linker.as_str().ok_or_else(|| {
+        anyhow!(
+            "Expected 'build.linker' to be a string in {}",
+            config_path.display()
+        )
+    })?;
+
+    Ok(Linker::from_name(linker))
+}
+
+/// Finds the build cache directory for the given source file
+///
+/// It is placed in the directory of the project config, or in the current directory when there is none
+pub(crate) fn find_cache_dir(path: &Path) -> PathBuf {
+    let project_root = path
+        .ancestors()
+        .skip(1)
+        .find(|directory| directory.join(CONFIG_FILE_NAME).is_file())
+        .unwrap_or(Path::new(""));
+
+    project_root.join(CACHE_DIR)
+}
+
+/// Finds and reads the project config closest to the given source file
+fn find_config(path: &Path) -> Result<Option<(PathBuf, toml::Table)>> {
+    let Some(config_path) = path
+        .ancestors()
+        .skip(1)
+        .map(|directory| directory.join(CONFIG_FILE_NAME))
+        .find(|config_path| config_path.is_file())
+    else {
+        return Ok(None);
+    };
+
+    let config = read_to_string(&config_path)?.parse::<toml::Table>()?;
+
+    Ok(Some((config_path, config)))
+}
@@ -1,18 +1,33 @@
 use crate::{
-    steps::{analyze_program, build, create_ast, tokenize},
+    config::{find_cache_dir, load_analyzer_options, load_linker},
+    steps::{
+        Emit, RunDirectory, analyze_program, build, create_ast, format_semantic_error,
+        format_semantic_warning, get_output_name, run_in_process, tokenize,
+    },
     watcher::run_with_flags,
 };
+use anyhow::Result;
 use clap::{
-    Arg, ArgAction, Command,
+    Arg, ArgAction, ArgMatches, Command,
     builder::{
         Styles,
         styling::{AnsiColor, Color, Style},
     },
 };
 use console::style;
+use gneurshk_compiler::{
+    PANIC_EXIT_CODE,
+    options::{CompilerOptions, CrateType},
+};
 use indicatif::{ProgressBar, ProgressStyle};
-use std::{fs::read_to_string, path::Path, time::Duration};
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+    process::ExitStatus,
+    time::Duration,
+};
 
+mod config;
 mod steps;
 mod watcher;
 
@@ -71,11 +86,66 @@ fn main() {
                         .required(false)
                         .action(ArgAction::SetTrue)
                         .long("ir"),
+                )
+                .arg(
+                    Arg::new("output-optimized-ir")
+                        .help("Outputs the LLVM IR file after the optimization passes ran")
+                        .required(false)
+                        .action(ArgAction::SetTrue)
+                        .long("optimized-ir"),
+                )
+                .arg(
+                    Arg::new("deny-warnings")
+                        .help("Treats every warning as an error")
+                        .required(false)
+                        .action(ArgAction::SetTrue)
+                        .long("deny-warnings"),
+                )
+                .arg(
+                    Arg::new("release")
+                        .help("Leaves out the runtime checks for overflow and division by zero")
+                        .required(false)
+                        .action(ArgAction::SetTrue)
+                        .long("release"),
+                )
+                .arg(
+                    Arg::new("debug")
+                        .help("Emits debug information for debuggers such as gdb and lldb")
+                        .required(false)
+                        .action(ArgAction::SetTrue)
+                        .long("debug")
+                        .short('g'),
+                )
+                .arg(
+                    Arg::new("optimization")
+                        .help("Sets the optimization level: 0, 1, 2, 3, s or z")
+                        .required(false)
+                        .action(ArgAction::Set)
+                        .value_name("LEVEL")
+                        .value_parser(["0", "1", "2", "3", "s", "z"])
+                        .default_value("0")
+                        .short('O'),
+                )
+                .arg(
+                    Arg::new("linker")
+                        .help("Links with gcc, clang, lld or the linker at the path")
+                        .required(false)
+                        .action(ArgAction::Set)
+                        .value_name("LINKER")
+                        .long("linker")
+                        .conflicts_with("jit"),
+                )
+                .arg(
+                    Arg::new("jit")
+                        .help("Runs the program in process instead of linking it")
+                        .required(false)
+                        .action(ArgAction::SetTrue)
+                        .long("jit"),
                 ),
         )
         .subcommand(
             Command::new("build")
-                .about("Compiles a file into an executable")
+                .about("Compiles a file into an executable or library")
                 .arg(
                     Arg::new("file")
                         .help("The file to build")
@@ -89,6 +159,109 @@ fn main() {
                         .required(false)
                         .action(ArgAction::SetTrue)
                         .long("ir"),
+                )
+                .arg(
+                    Arg::new("output-optimized-ir")
+                        .help("Outputs the LLVM IR file after the optimization passes ran")
+                        .required(false)
+                        .action(ArgAction::SetTrue)
+                        .long("optimized-ir"),
+                )
+                .arg(
+                    Arg::new("deny-warnings")
+                        .help("Treats every warning as an error")
+                        .required(false)
+                        .action(ArgAction::SetTrue)
+                        .long("deny-warnings"),
+                )
+                .arg(
+                    Arg::new("release")
+                        .help("Leaves out the runtime checks for overflow and division by zero")
+                        .required(false)
+                        .action(ArgAction::SetTrue)
+                        .long("release"),
+                )
+                .arg(
+                    Arg::new("debug")
+                        .help("Emits debug information for debuggers such as gdb and lldb")
+                        .required(false)
+                        .action(ArgAction::SetTrue)
+                        .long("debug")
+                        .short('g'),
+                )
+                .arg(
+                    Arg::new("optimization")
+                        .help("Sets the optimization level: 0, 1, 2, 3, s or z")
+                        .required(false)
+                        .action(ArgAction::Set)
+                        .value_name("LEVEL")
+                        .value_parser(["0", "1", "2", "3", "s", "z"])
+                        .default_value("0")
+                        .short('O'),
+                )
+                .arg(
+                    Arg::new("linker")
+                        .help("Links with gcc, clang, lld or the linker at the path")
+                        .required(false)
+                        .action(ArgAction::Set)
+                        .value_name("LINKER")
+                        .long("linker"),
+                )
+                .arg(
+                    Arg::new("target")
+                        .help("Compiles for the target triple instead of the host")
+                        .required(false)
+                        .action(ArgAction::Set)
+                        .value_name("TRIPLE")
+                        .long("target"),
+                )
+                .arg(
+                    Arg::new("target-cpu")
+                        .help("Generates code for the CPU, native selects the host's CPU")
+                        .required(false)
+                        .action(ArgAction::Set)
+                        .value_name("CPU")
+                        .default_value("generic")
+                        .long("target-cpu"),
+                )
+                .arg(
+                    Arg::new("target-features")
+                        .help("Enables or disables CPU features, such as +avx2,-sse4.1")
+                        .required(false)
+                        .action(ArgAction::Set)
+                        .value_name("FEATURES")
+                        .default_value("")
+                        .long("target-features"),
+                )
+                .arg(
+                    Arg::new("emit")
+                        .help("The kinds of output to write: asm, llvm-bc, llvm-ir, obj or link")
+                        .required(false)
+                        .action(ArgAction::Set)
+                        .value_name("KINDS")
+                        .value_parser(["asm", "llvm-bc", "llvm-ir", "obj", "link"])
+                        .value_delimiter(',')
+                        .default_value("link")
+                        .long("emit"),
+                )
+                .arg(
+                    Arg::new("crate-type")
+                        .help("Builds an executable, a static library or a shared library")
+                        .required(false)
+                        .action(ArgAction::Set)
+                        .value_name("TYPE")
+                        .value_parser(["bin", "staticlib", "cdylib"])
+                        .default_value("bin")
+                        .long("crate-type"),
+                )
+                .arg(
+                    Arg::new("output")
+                        .help("Where to write the output, instead of target/gneurshk")
+                        .required(false)
+                        .action(ArgAction::Set)
+                        .value_name("PATH")
+                        .long("output")
+                        .short('o'),
                 ),
         )
         .subcommand(
@@ -130,6 +303,13 @@ fn main() {
                         .action(ArgAction::SetTrue)
                         .long("watch")
                         .short('w'),
+                )
+                .arg(
+                    Arg::new("deny-warnings")
+                        .help("Treats every warning as an error")
+                        .required(false)
+                        .action(ArgAction::SetTrue)
+                        .long("deny-warnings"),
                 ),
         )
         .get_matches();
@@ -144,7 +324,15 @@ fn main() {
 
             // Get the flags from the arguments
             let is_watching = query_matches.get_flag("watch");
-            let output_ir = query_matches.get_flag("output-ir");
+            let deny_warnings = query_matches.get_flag("deny-warnings");
+            let use_jit = query_matches.get_flag("jit");
+
+            // Executables have to be linked to run them, the JIT compiler runs the program directly
+            let mut emit = get_ir_emits(query_matches);
+
+            if !use_jit {
+                emit.push(Emit::Link);
+            }
 
             // Run the build command with the command flags
             run_with_flags(
@@ -159,12 +347,78 @@ fn main() {
                         }
                     };
 
+                    // Load the project config
+                    let options = match load_analyzer_options(path, deny_warnings) {
+                        Ok(options) => options,
+                        Err(e) => {
+                            eprintln!("{} {}", style("Error:").red().bright(), e);
+                            return;
+                        }
+                    };
+
+                    // Read the compiler flags
+                    let compiler_options =
+                        match create_compiler_options(path, &source, query_matches) {
+                            Ok(compiler_options) => compiler_options,
+                            Err(e) => {
+                                eprintln!("{} {}", style("Error:").red().bright(), e);
+                                return;
+                            }
+                        };
+
+                    let output_name = get_output_name(path);
+                    let output_path = find_cache_dir(path).join(&output_name);
+
+                    // Run the program in this process without linking it
+                    if use_jit {
+                        let pb = create_progress_bar();
+
+                        match run_in_process(
+                            &source,
+                            options,
+                            compiler_options,
+                            &emit,
+                            &output_path,
+                            pb.clone(),
+                        ) {
+                            Ok(exit_code) => report_exit_code(exit_code),
+                            Err(e) => {
+                                pb.finish_and_clear();
+
+                                eprintln!("{} {}", style("Error:").red().bright(), e);
+                            }
+                        }
+
+                        return;
+                    }
+
+                    // Link the executable in a temporary directory that is removed after the run
+                    let run_directory = match RunDirectory::create() {
+                        Ok(run_directory) => run_directory,
+                        Err(e) => {
+                            eprintln!("{} {}", style("Error:").red().bright(), e);
+                            return;
+                        }
+                    };
+
                     // Create the progress bar
                     let pb = create_progress_bar();
 
                     // Build the source code
-                    match build(&source, output_ir, pb.clone()) {
+
+                    match build(
+                        &source,
+                        options,
+                        compiler_options,
+                        &emit,
+                        &output_path,
+                        &run_directory.path().join(&output_name),
+                        pb.clone(),
+                    ) {
                         Ok(executable_path) => {
+                            let executable_path =
+                                executable_path.expect("Running always links the program");
+
                             pb.finish_with_message("Running executable");
 
                             // Run the executable
@@ -176,7 +430,12 @@ fn main() {
                                 .spawn()
                                 .unwrap();
 
-                            child.wait().unwrap();
+                            match child.wait() {
+                                Ok(status) => report_exit_status(status),
+                                Err(e) => {
+                                    eprintln!("{} {}", style("Error:").red().bright(), e)
+                                }
+                            }
                         }
                         Err(e) => {
                             pb.finish_and_clear();
@@ -196,7 +455,29 @@ fn main() {
             let path: &Path = path.as_ref();
 
             // Get the flags from the arguments
-            let output_ir = query_matches.get_flag("output-ir");
+            let deny_warnings = query_matches.get_flag("deny-warnings");
+
+            // Write the outputs to the build cache unless a path is given
+            let output_path = match query_matches.get_one::<String>("output") {
+                Some(output_path) => PathBuf::from(output_path),
+                None => find_cache_dir(path).join(get_output_name(path)),
+            };
+
+            // The --ir flags are shorthands for emitting the LLVM IR
+            let mut emit = get_ir_emits(query_matches);
+
+            for kind in query_matches
+                .get_many::<String>("emit")
+                .expect("Argument 'emit' has a default value")
+            {
+                let kind = kind
+                    .parse()
+                    .expect("Argument 'emit' only accepts known kinds");
+
+                if !emit.contains(&kind) {
+                    emit.push(kind);
+                }
+            }
 
             // Read the file
             let source = match read_to_string(path) {
@@ -207,18 +488,49 @@ fn main() {
                 }
             };
 
+            // Load the project config
+            let options = match load_analyzer_options(path, deny_warnings) {
+                Ok(options) => options,
+                Err(e) => {
+                    eprintln!("{} {}", style("Error:").red().bright(), e);
+                    std::process::exit(1);
+                }
+            };
+
+            // Read the compiler flags
+            let compiler_options = match create_compiler_options(path, &source, query_matches) {
+                Ok(compiler_options) => compiler_options,
+                Err(e) => {
+                    eprintln!("{} {}", style("Error:").red().bright(), e);
+                    std::process::exit(1);
+                }
+            };
+
             // Create the progress bar
             let pb = create_progress_bar();
 
             // Build the source code
-            match build(&source, output_ir, pb.clone()) {
-                Ok(_) => {
-                    pb.finish_with_message("Successfully built executable");
+
+            match build(
+                &source,
+                options,
+                compiler_options,
+                &emit,
+                &output_path,
+                &output_path,
+                pb.clone(),
+            ) {
+                Ok(Some(linked_path)) => {
+                    pb.finish_with_message(format!("Successfully built {}", linked_path.display()));
+                }
+                Ok(None) => {
+                    pb.finish_with_message("Successfully built");
                 }
                 Err(e) => {
                     pb.finish_and_clear();
 
                     eprintln!("{} {}", style("Error:").red().bright(), e);
+                    std::process::exit(1);
                 }
             };
         }
@@ -298,8 +610,9 @@ fn main() {
                 .expect("Argument 'file' is required");
             let path: &Path = path.as_ref();
 
-            // Get the watching flag from the arguments
+            // Get the flags from the arguments
             let is_watching = query_matches.get_flag("watch");
+            let deny_warnings = query_matches.get_flag("deny-warnings");
 
             // Check the source code for errors
             run_with_flags(
@@ -314,11 +627,20 @@ fn main() {
                         }
                     };
 
+                    // Load the project config
+                    let options = match load_analyzer_options(path, deny_warnings) {
+                        Ok(options) => options,
+                        Err(e) => {
+                            eprintln!("{} {}", style("Error:").red().bright(), e);
+                            return;
+                        }
+                    };
+
                     // Create the progress bar
                     let pb = create_progress_bar();
 
                     // Analyze the program
-                    match analyze_program(&source, pb.clone()) {
+                    match analyze_program(&source, options, pb.clone()) {
                         Ok((_ast, analyzed)) => {
                             pb.finish_and_clear();
 
@@ -333,14 +655,23 @@ fn main() {
                                     eprintln!(
                                         "{} {}",
                                         style("Warning:").yellow().bright(),
-                                        warning
+                                        format_semantic_warning(&source, &warning)
                                     );
                                 }
 
-                                for error in errors {
-                                    eprintln!("{} {}", style("Error:").red().bright(), error);
+                                for error in &errors {
+                                    eprintln!(
+                                        "{} {}",
+                                        style("Error:").red().bright(),
+                                        format_semantic_error(&source, error)
+                                    );
                                 }
                             }
+
+                            // Let CI fail the check when there are errors
+                            if !is_watching && !errors.is_empty() {
+                                std::process::exit(1);
+                            }
                         }
                         Err(error) => {
                             pb.finish_and_clear();
@@ -357,6 +688,85 @@ fn main() {
     }
 }
 
+/// Creates the compiler options from the flags of the `run` or `build` command
+fn create_compiler_options(
+    path: &Path,
+    source: &str,
+    query_matches: &ArgMatches,
+) -> Result<CompilerOptions> {
+    let optimization = query_matches
+        .get_one::<String>("optimization")
+        .expect("Argument 'optimization' has a default value")
+        .parse()?;
+    let linker = load_linker(
+        path,
+        query_matches
+            .get_one::<String>("linker")
+            .map(String::as_str),
+    )?;
+
+    // Only the build command can compile for other targets and libraries, run always makes a host executable
+    let get_target_arg = |name: &str| query_matches.try_get_one::<String>(name).ok().flatten();
+    let crate_type = match get_target_arg("crate-type") {
+        Some(crate_type) => crate_type.parse()?,
+        None => CrateType::Bin,
+    };
+
+    Ok(CompilerOptions {
+        safety_checks: !query_matches.get_flag("release"),
+        source_name: path.display().to_string(),
+        source: source.to_string(),
+        debug_info: query_matches.get_flag("debug"),
+        optimization,
+        target: get_target_arg("target").cloned(),
+        target_cpu: get_target_arg("target-cpu")
+            .cloned()
+            .unwrap_or_else(|| "generic".to_string()),
+        target_features: get_target_arg("target-features")
+            .cloned()
+            .unwrap_or_default(),
+        linker,
+        crate_type,
+    })
+}
+
+/// Gets the LLVM IR outputs that are requested by the --ir and --optimized-ir flags
+fn get_ir_emits(query_matches: &ArgMatches) -> Vec<Emit> {
+    let mut emit = Vec::new();
+
+    if query_matches.get_flag("output-ir") {
+        emit.push(Emit::LlvmIr);
+    }
+
+    if query_matches.get_flag("output-optimized-ir") {
+        emit.push(Emit::OptimizedLlvmIr);
+    }
+
+    emit
+}
+
+/// Tells the user why the program stopped if it didn't exit successfully
+fn report_exit_status(status: ExitStatus) {
+    match status.code() {
+        Some(code) => report_exit_code(code),
+        None => eprintln!(
+            "{} The program was terminated by a signal",
+            style("Error:").red().bright()
+        ),
+    }
+}
+
+/// Tells the user why the program stopped if its exit code isn't zero
+fn report_exit_code(code: i32) {
+    let message = match code {
+        0 => return,
+        code if code as u64 == PANIC_EXIT_CODE => "The program panicked".to_string(),
+        code => format!("The program exited with code {code}"),
+    };
+
+    eprintln!("{} {}", style("Error:").red().bright(), message);
+}
+
 fn create_progress_bar() -> Box<ProgressBar> {
     let pb = ProgressBar::new_spinner();
     pb.enable_steady_tick(Duration::from_millis(80));
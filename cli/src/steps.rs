@@ -1,11 +1,64 @@
 use anyhow::{Result, anyhow};
 use console::style;
-use gneurshk_analyzer::program::{AnalyzedProgram, ProgramAnalyzer};
-use gneurshk_compiler::output::{executable::compile_to_executable, ir::create_llvm_ir_file};
+use gneurshk_analyzer::{
+    errors::{SematicError, SematicWarning},
+    options::AnalyzerOptions,
+    program::{AnalyzedProgram, ProgramAnalyzer},
+};
+use gneurshk_compiler::{
+    options::{CompilerOptions, CrateType},
+    output::{
+        assembly::create_assembly_file_with_options,
+        bitcode::create_bitcode_file_with_options,
+        executable::compile_to_executable_with_options,
+        ir::{create_llvm_ir_file_with_options, create_optimized_llvm_ir_file},
+        jit::run_jit_with_options,
+        library::compile_to_library_with_options,
+        object::create_object_file_with_options,
+    },
+};
 use gneurshk_lexer::{TokenStream, lex};
 use gneurshk_parser::{Program, parse};
 use indicatif::ProgressBar;
-use std::path::PathBuf;
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// The kinds of files the build can output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Emit {
+    /// Assembly (.s)
+    Asm,
+    /// LLVM bitcode (.bc)
+    LlvmBc,
+    /// LLVM IR (.ll)
+    LlvmIr,
+    /// LLVM IR after the optimization passes ran (.opt.ll)
+    OptimizedLlvmIr,
+    /// An object file (.o)
+    Obj,
+    /// An executable or library, depending on the crate type
+    Link,
+}
+
+impl FromStr for Emit {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "asm" => Ok(Emit::Asm),
+            "llvm-bc" => Ok(Emit::LlvmBc),
+            "llvm-ir" => Ok(Emit::LlvmIr),
+            "obj" => Ok(Emit::Obj),
+            "link" => Ok(Emit::Link),
+            _ => Err(anyhow!(
+                "Unknown output kind '{value}', expected asm, llvm-bc, llvm-ir, obj or link"
+            )),
+        }
+    }
+}
 
 #[allow(clippy::boxed_local)]
 pub(crate) fn tokenize(source: &str, pb: Box<ProgressBar>) -> Result<TokenStream<'_>> {
@@ -27,6 +80,7 @@ pub(crate) fn create_ast(source: &str, pb: Box<ProgressBar>) -> Result<Program>
 
 pub(crate) fn analyze_program(
     source: &str,
+    options: AnalyzerOptions,
     pb: Box<ProgressBar>,
 ) -> Result<(Program, AnalyzedProgram)> {
     // Create the AST
@@ -35,14 +89,71 @@ pub(crate) fn analyze_program(
     // Analyze the AST
     pb.set_message("Analyzing...");
 
-    let analyzed_program = ProgramAnalyzer::analyze(ast.clone());
+    let analyzed_program = ProgramAnalyzer::analyze_with_options(ast.clone(), options);
 
     Ok((ast, analyzed_program))
 }
 
-pub(crate) fn build(source: &str, output_ir: bool, pb: Box<ProgressBar>) -> Result<PathBuf> {
+/// Analyzes the program, then writes the requested outputs next to the output path
+/// and links the program at the linked path
+///
+/// # Returns
+/// The path to the executable or library, if it was linked
+pub(crate) fn build(
+    source: &str,
+    options: AnalyzerOptions,
+    compiler_options: CompilerOptions,
+    emit: &[Emit],
+    output_path: &Path,
+    linked_path: &Path,
+    pb: Box<ProgressBar>,
+) -> Result<Option<PathBuf>> {
+    let ast = analyze_for_build(source, options, pb.clone())?;
+
+    write_outputs(ast, compiler_options, emit, output_path, linked_path, pb)
+}
+
+/// Analyzes the program, then writes the requested outputs next to the output path
+/// and runs the program in this process instead of linking it
+///
+/// # Returns
+/// The exit code of the program
+pub(crate) fn run_in_process(
+    source: &str,
+    options: AnalyzerOptions,
+    compiler_options: CompilerOptions,
+    emit: &[Emit],
+    output_path: &Path,
+    pb: Box<ProgressBar>,
+) -> Result<i32> {
+    let ast = analyze_for_build(source, options, pb.clone())?;
+
+    write_outputs(
+        ast.clone(),
+        compiler_options.clone(),
+        emit,
+        output_path,
+        output_path,
+        pb.clone(),
+    )?;
+
+    // The spinner would be drawn over the output of the program
+    pb.finish_with_message("Running in process");
+
+    run_jit_with_options(ast, compiler_options)
+}
+
+/// Analyzes the program and prints its warnings
+///
+/// # Returns
+/// The AST, or an error if the program has semantic errors
+fn analyze_for_build(
+    source: &str,
+    options: AnalyzerOptions,
+    pb: Box<ProgressBar>,
+) -> Result<Program> {
     // Analyze the program
-    let ast = match analyze_program(source, pb.clone()) {
+    match analyze_program(source, options, pb.clone()) {
         Ok((ast, analyzed)) => {
             // Cancel the build if there are any semantic errors
             let all_errors = analyzed.get_all_errors();
@@ -51,7 +162,11 @@ pub(crate) fn build(source: &str, output_ir: bool, pb: Box<ProgressBar>) -> Resu
             if !all_errors.is_empty() {
                 // Print the errors
                 for error in &all_errors {
-                    pb.println(format!("{} {}", style("Error:").red().bright(), error));
+                    pb.println(format!(
+                        "{} {}",
+                        style("Error:").red().bright(),
+                        format_semantic_error(source, error)
+                    ));
                 }
 
                 // Cancel the build due to the errors
@@ -66,29 +181,163 @@ pub(crate) fn build(source: &str, output_ir: bool, pb: Box<ProgressBar>) -> Resu
                 pb.println(format!(
                     "{} {}",
                     style("Warning:").yellow().bright(),
-                    warning
+                    format_semantic_warning(source, &warning)
                 ));
             }
 
             // Return the AST
-            ast
+            Ok(ast)
         }
-        Err(e) => {
-            return Err(e);
+        Err(e) => Err(e),
+    }
+}
+
+/// Writes the requested outputs next to the output path and links the program at the linked path
+///
+/// # Returns
+/// The path to the executable or library, if it was linked
+#[allow(clippy::boxed_local)]
+fn write_outputs(
+    ast: Program,
+    compiler_options: CompilerOptions,
+    emit: &[Emit],
+    output_path: &Path,
+    linked_path: &Path,
+    pb: Box<ProgressBar>,
+) -> Result<Option<PathBuf>> {
+    // Make sure the directories of the outputs exist
+    for path in [output_path, linked_path] {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| anyhow!("Failed to create output directory: {}", e))?;
         }
-    };
+    }
 
     // Create the LLVM IR file
-    if output_ir {
+    if emit.contains(&Emit::LlvmIr) {
         pb.set_message("Creating LLVM IR file...");
 
-        create_llvm_ir_file(ast.clone(), "output".as_ref())?;
+        create_llvm_ir_file_with_options(ast.clone(), output_path, compiler_options.clone())?;
     }
 
-    // Create the executable
-    pb.set_message("Compiling to executable...");
+    // Create the LLVM IR file after the optimization passes
+    if emit.contains(&Emit::OptimizedLlvmIr) {
+        pb.set_message("Creating optimized LLVM IR file...");
+
+        create_optimized_llvm_ir_file(ast.clone(), output_path, compiler_options.clone())?;
+    }
+
+    // Create the LLVM bitcode file
+    if emit.contains(&Emit::LlvmBc) {
+        pb.set_message("Creating LLVM bitcode file...");
+
+        create_bitcode_file_with_options(ast.clone(), output_path, compiler_options.clone())?;
+    }
+
+    // Create the assembly file
+    if emit.contains(&Emit::Asm) {
+        pb.set_message("Creating assembly file...");
+
+        create_assembly_file_with_options(ast.clone(), output_path, compiler_options.clone())?;
+    }
+
+    // Create the object file, linking creates and removes its own
+    if emit.contains(&Emit::Obj) {
+        pb.set_message("Creating object file...");
+
+        create_object_file_with_options(ast.clone(), output_path, compiler_options.clone())?;
+    }
+
+    if !emit.contains(&Emit::Link) {
+        return Ok(None);
+    }
+
+    // Create the executable or library
+    let linked_path = match compiler_options.crate_type {
+        CrateType::Bin => {
+            pb.set_message("Compiling to executable...");
+
+            compile_to_executable_with_options(ast, linked_path, compiler_options)?
+        }
+        CrateType::Staticlib | CrateType::Cdylib => {
+            pb.set_message("Compiling to library...");
+
+            compile_to_library_with_options(ast, linked_path, compiler_options)?
+        }
+    };
+
+    Ok(Some(linked_path))
+}
+
+/// Gets the name of the outputs of the source file, which is the file name without its extension
+pub(crate) fn get_output_name(path: &Path) -> PathBuf {
+    path.file_stem()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("output"))
+}
+
+/// A temporary directory for the executables of the run command, which is removed when it is dropped
+///
+/// Each process gets its own directory, so runs of the same file don't overwrite each other
+pub(crate) struct RunDirectory {
+    path: PathBuf,
+}
+
+impl RunDirectory {
+    pub(crate) fn create() -> Result<Self> {
+        let path = std::env::temp_dir().join(format!("gneurshk-run-{}", std::process::id()));
+
+        std::fs::create_dir_all(&path)
+            .map_err(|e| anyhow!("Failed to create temporary directory: {}", e))?;
+
+        Ok(Self { path })
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for RunDirectory {
+    fn drop(&mut self) {
+        // Nothing depends on the directory being gone, so failures are ignored
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Formats a semantic error along with its location in the source code
+pub(crate) fn format_semantic_error(source: &str, error: &SematicError) -> String {
+    let message = match error.span() {
+        Some(span) => format!("{} {}", error, style(format_location(source, span)).dim()),
+        None => error.to_string(),
+    };
+
+    // Point to the binding the error is about
+    match error.declaration_span() {
+        Some(span) => format!(
+            "{}\n  {}",
+            message,
+            style(format!("declared {}", format_location(source, span))).dim()
+        ),
+        None => message,
+    }
+}
+
+/// Formats a semantic warning along with its location in the source code
+pub(crate) fn format_semantic_warning(source: &str, warning: &SematicWarning) -> String {
+    match warning.span() {
+        Some(span) => format!("{} {}", warning, style(format_location(source, span)).dim()),
+        None => warning.to_string(),
+    }
+}
 
-    let executable_path = compile_to_executable(ast.clone(), "output".as_ref())?;
+/// Converts a span into a human readable line and column
+fn format_location(source: &str, span: Range<usize>) -> String {
+    let before = &source[..span.start.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|c| *c != '\n').count() + 1;
 
-    Ok(executable_path)
+    format!("at line {line}, column {column}")
 }
@@ -12,10 +12,21 @@ impl<'ctx> Codegen<'ctx> {
             MemberExpressionBase::Identifier(identifier) => {
                 let name = identifier.name;
 
-                let new_value = self.build_expression(value)?;
-                let pointer = self.scope.get_variable(&name)?.pointer;
+                let variable = self.scope.get_variable(&name)?;
+                let new_value = self.build_expression_as(value, variable.data_type)?;
 
-                self.builder.build_store(pointer, new_value).unwrap();
+                // Take over the new value before releasing the old one, in case they are the same
+                self.build_retain(new_value);
+
+                let old_value = self
+                    .builder
+                    .build_load(variable.data_type, variable.pointer, "old_value")
+                    .unwrap();
+
+                self.builder
+                    .build_store(variable.pointer, new_value)
+                    .unwrap();
+                self.build_release(old_value);
 
                 Some(new_value)
             }
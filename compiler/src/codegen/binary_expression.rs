@@ -1,21 +1,34 @@
 use crate::codegen::Codegen;
-use gneurshk_parser::{BinaryOperator, Expression};
+use crate::codegen::checks::CheckedOperation;
+use gneurshk_parser::{BinaryExpression, BinaryOperator};
 use inkwell::IntPredicate;
 use inkwell::values::{BasicValueEnum, IntValue};
+use std::ops::Range;
 
 impl<'ctx> Codegen<'ctx> {
     pub(crate) fn build_binary_expression(
         &mut self,
-        left: Expression,
-        right: Expression,
-        operator: BinaryOperator,
+        expr: BinaryExpression,
     ) -> Option<BasicValueEnum<'ctx>> {
-        let left_value = self.build_expression(left)?;
-        let right_value = self.build_expression(right)?;
+        // Failed safety checks point at the whole expression
+        let span = expr.span();
+        let BinaryExpression {
+            left,
+            right,
+            operator,
+        } = expr;
+
+        // Unsigned integers overflow, divide and compare differently
+        let is_unsigned = self
+            .get_expression_data_type(&left)
+            .is_some_and(|data_type| self.is_unsigned_type(&data_type));
+
+        let left_value = self.build_expression(*left)?;
+        let right_value = self.build_expression(*right)?;
 
         match (left_value, right_value) {
             (BasicValueEnum::IntValue(left_value), BasicValueEnum::IntValue(right_value)) => {
-                self.build_int_int_expression(left_value, right_value, operator)
+                self.build_int_int_expression(left_value, right_value, operator, is_unsigned, span)
             }
             _ => {
                 panic!(
@@ -32,32 +45,70 @@ impl<'ctx> Codegen<'ctx> {
         left_value: IntValue<'ctx>,
         right_value: IntValue<'ctx>,
         operator: BinaryOperator,
+        is_unsigned: bool,
+        span: Option<Range<usize>>,
     ) -> Option<BasicValueEnum<'ctx>> {
+        let predicate = |signed: IntPredicate, unsigned: IntPredicate| {
+            if is_unsigned { unsigned } else { signed }
+        };
+
         let result = match operator {
-            BinaryOperator::Add => self
-                .builder
-                .build_int_add(left_value, right_value, "add")
-                .unwrap(),
-            BinaryOperator::Subtract => self
-                .builder
-                .build_int_sub(left_value, right_value, "sub")
-                .unwrap(),
-            BinaryOperator::Multiply => self
-                .builder
-                .build_int_mul(left_value, right_value, "mul")
-                .unwrap(),
-            BinaryOperator::Divide => self
-                .builder
-                .build_int_signed_div(left_value, right_value, "div")
-                .unwrap(),
-            BinaryOperator::Modulus => self
-                .builder
-                .build_int_signed_rem(left_value, right_value, "rem")
-                .unwrap(),
+            BinaryOperator::Add => self.build_checked_int_operation(
+                CheckedOperation::Add,
+                left_value,
+                right_value,
+                is_unsigned,
+                span,
+            ),
+            BinaryOperator::Subtract => self.build_checked_int_operation(
+                CheckedOperation::Subtract,
+                left_value,
+                right_value,
+                is_unsigned,
+                span,
+            ),
+            BinaryOperator::Multiply => self.build_checked_int_operation(
+                CheckedOperation::Multiply,
+                left_value,
+                right_value,
+                is_unsigned,
+                span,
+            ),
+            BinaryOperator::Divide => {
+                self.build_division_checks(left_value, right_value, is_unsigned, span, false);
+
+                if is_unsigned {
+                    self.builder
+                        .build_int_unsigned_div(left_value, right_value, "div")
+                        .unwrap()
+                } else {
+                    self.builder
+                        .build_int_signed_div(left_value, right_value, "div")
+                        .unwrap()
+                }
+            }
+            BinaryOperator::Modulus => {
+                self.build_division_checks(left_value, right_value, is_unsigned, span, true);
+
+                if is_unsigned {
+                    self.builder
+                        .build_int_unsigned_rem(left_value, right_value, "rem")
+                        .unwrap()
+                } else {
+                    self.builder
+                        .build_int_signed_rem(left_value, right_value, "rem")
+                        .unwrap()
+                }
+            }
             BinaryOperator::GreaterThan => {
                 let cmp = self
                     .builder
-                    .build_int_compare(IntPredicate::SGT, left_value, right_value, "gt")
+                    .build_int_compare(
+                        predicate(IntPredicate::SGT, IntPredicate::UGT),
+                        left_value,
+                        right_value,
+                        "gt",
+                    )
                     .unwrap();
                 self.builder
                     .build_int_z_extend(cmp, self.context.i32_type(), "gt_ext")
@@ -66,7 +117,12 @@ impl<'ctx> Codegen<'ctx> {
             BinaryOperator::GreaterThanEqual => {
                 let cmp = self
                     .builder
-                    .build_int_compare(IntPredicate::SGE, left_value, right_value, "gte")
+                    .build_int_compare(
+                        predicate(IntPredicate::SGE, IntPredicate::UGE),
+                        left_value,
+                        right_value,
+                        "gte",
+                    )
                     .unwrap();
                 self.builder
                     .build_int_z_extend(cmp, self.context.i32_type(), "gte_ext")
@@ -93,7 +149,12 @@ impl<'ctx> Codegen<'ctx> {
             BinaryOperator::LessThanEqual => {
                 let cmp = self
                     .builder
-                    .build_int_compare(IntPredicate::SLE, left_value, right_value, "lte")
+                    .build_int_compare(
+                        predicate(IntPredicate::SLE, IntPredicate::ULE),
+                        left_value,
+                        right_value,
+                        "lte",
+                    )
                     .unwrap();
                 self.builder
                     .build_int_z_extend(cmp, self.context.i32_type(), "lte_ext")
@@ -102,7 +163,12 @@ impl<'ctx> Codegen<'ctx> {
             BinaryOperator::LessThan => {
                 let cmp = self
                     .builder
-                    .build_int_compare(IntPredicate::SLT, left_value, right_value, "lt")
+                    .build_int_compare(
+                        predicate(IntPredicate::SLT, IntPredicate::ULT),
+                        left_value,
+                        right_value,
+                        "lt",
+                    )
                     .unwrap();
                 self.builder
                     .build_int_z_extend(cmp, self.context.i32_type(), "lt_ext")
@@ -1,4 +1,4 @@
-use crate::codegen::{Codegen, scope::AllocationKind};
+use crate::codegen::Codegen;
 use gneurshk_parser::Block;
 use inkwell::values::BasicValueEnum;
 
@@ -21,15 +21,27 @@ impl<'ctx> Codegen<'ctx> {
                 break;
             }
 
+            // Temporary values that nothing took over are freed
+            if let Some(value) = last_value {
+                self.build_release_unowned(value);
+            }
+
+            // Point the instructions of the statement to its line in the source code
+            self.set_debug_location(stmt.span().as_ref());
+
             last_value = self.build_stmt(stmt);
         }
 
-        // Delete variables falling out of scope
-        for local_var in self.scope.get_local_variables() {
-            // NOTE: No heap variables are ever defined so this is unused and untested
-            if local_var.alloc == AllocationKind::Heap {
-                let _ = self.builder.build_free(local_var.pointer);
-            }
+        // Release the references of the bindings falling out of scope, unless the block already
+        // jumped away and released them there
+        if self
+            .builder
+            .get_insert_block()
+            .unwrap()
+            .get_terminator()
+            .is_none()
+        {
+            self.release_scopes(self.scope.depth() - 1, last_value);
         }
 
         self.exit_scope();
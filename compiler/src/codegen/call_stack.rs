@@ -0,0 +1,128 @@
+use crate::codegen::Codegen;
+use inkwell::AddressSpace;
+use inkwell::module::Linkage;
+use inkwell::types::StructType;
+use inkwell::values::PointerValue;
+use std::ops::Range;
+
+const STACK_TOP: &str = "gneurshk.stack_top";
+
+/// The call stack that is printed when the program panics
+///
+/// Every function pushes a frame onto a linked list of frames when it is called and pops it
+/// before it returns. A frame points to a description of the call or panic its function is at.
+impl<'ctx> Codegen<'ctx> {
+    /// A frame holds a pointer to the frame of the caller and a pointer to the location string
+    pub(crate) fn get_frame_type(&self) -> StructType<'ctx> {
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+
+        self.context
+            .struct_type(&[ptr_type.into(), ptr_type.into()], false)
+    }
+
+    pub(crate) fn add_call_stack(&self) {
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+
+        let stack_top = self
+            .module
+            .add_global(ptr_type, Some(AddressSpace::default()), STACK_TOP);
+        stack_top.set_initializer(&ptr_type.const_null());
+        stack_top.set_linkage(Linkage::Internal);
+    }
+
+    pub(crate) fn get_stack_top(&self) -> PointerValue<'ctx> {
+        self.module
+            .get_global(STACK_TOP)
+            .unwrap()
+            .as_pointer_value()
+    }
+
+    /// Pushes a frame for the function that is being built
+    ///
+    /// # Returns
+    /// The frame, which has to be popped before every return of the function
+    pub(crate) fn build_frame_push(&self) -> PointerValue<'ctx> {
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let frame_type = self.get_frame_type();
+
+        let frame = self.builder.build_alloca(frame_type, "frame").unwrap();
+        let parent = self
+            .builder
+            .build_load(ptr_type, self.get_stack_top(), "parent")
+            .unwrap();
+
+        let parent_ptr = self
+            .builder
+            .build_struct_gep(frame_type, frame, 0, "parent")
+            .unwrap();
+        let location_ptr = self
+            .builder
+            .build_struct_gep(frame_type, frame, 1, "location")
+            .unwrap();
+
+        self.builder.build_store(parent_ptr, parent).unwrap();
+        self.builder
+            .build_store(location_ptr, ptr_type.const_null())
+            .unwrap();
+        self.builder
+            .build_store(self.get_stack_top(), frame)
+            .unwrap();
+
+        frame
+    }
+
+    /// Pops the frame of the current function, making its caller the top of the stack again
+    pub(crate) fn build_frame_pop(&self) {
+        let Some(frame) = self.frame else {
+            return;
+        };
+
+        let parent_ptr = self
+            .builder
+            .build_struct_gep(self.get_frame_type(), frame, 0, "parent")
+            .unwrap();
+        let parent = self
+            .builder
+            .build_load(
+                self.context.ptr_type(AddressSpace::default()),
+                parent_ptr,
+                "parent",
+            )
+            .unwrap();
+
+        self.builder
+            .build_store(self.get_stack_top(), parent)
+            .unwrap();
+    }
+
+    /// Records where the current function is, before it calls another function or panics
+    pub(crate) fn build_frame_location(&self, span: Option<&Range<usize>>) {
+        let Some(frame) = self.frame else {
+            return;
+        };
+
+        let function_name = self
+            .builder
+            .get_insert_block()
+            .and_then(|block| block.get_parent())
+            .map(|function| function.get_name().to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let location = match span {
+            Some(span) => format!(
+                "    at {function_name} ({})\n",
+                self.options.format_location(span)
+            ),
+            None => format!("    at {function_name}\n"),
+        };
+
+        let location_ptr = self
+            .builder
+            .build_struct_gep(self.get_frame_type(), frame, 1, "location")
+            .unwrap();
+
+        self.builder
+            .build_store(location_ptr, self.build_constant_string(&location))
+            .unwrap();
+    }
+}
@@ -0,0 +1,184 @@
+use crate::codegen::Codegen;
+use inkwell::IntPredicate;
+use inkwell::intrinsics::Intrinsic;
+use inkwell::values::IntValue;
+use std::ops::Range;
+
+/// The integer operations that can overflow, along with the LLVM intrinsic that detects it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CheckedOperation {
+    Add,
+    Subtract,
+    Multiply,
+}
+
+impl CheckedOperation {
+    /// Unsigned integers overflow at different bounds, so they have their own intrinsics
+    fn intrinsic_name(self, is_unsigned: bool) -> &'static str {
+        match (self, is_unsigned) {
+            (CheckedOperation::Add, false) => "llvm.sadd.with.overflow",
+            (CheckedOperation::Subtract, false) => "llvm.ssub.with.overflow",
+            (CheckedOperation::Multiply, false) => "llvm.smul.with.overflow",
+            (CheckedOperation::Add, true) => "llvm.uadd.with.overflow",
+            (CheckedOperation::Subtract, true) => "llvm.usub.with.overflow",
+            (CheckedOperation::Multiply, true) => "llvm.umul.with.overflow",
+        }
+    }
+
+    fn overflow_message(self) -> &'static str {
+        match self {
+            CheckedOperation::Add => "attempt to add with overflow",
+            CheckedOperation::Subtract => "attempt to subtract with overflow",
+            CheckedOperation::Multiply => "attempt to multiply with overflow",
+        }
+    }
+}
+
+/// Runtime safety checks, which stop the program with the location of the failed operation
+///
+/// They are left out when the safety checks are disabled in the compiler options
+impl<'ctx> Codegen<'ctx> {
+    /// Builds an integer operation that panics when the result overflows
+    pub(crate) fn build_checked_int_operation(
+        &self,
+        operation: CheckedOperation,
+        left: IntValue<'ctx>,
+        right: IntValue<'ctx>,
+        is_unsigned: bool,
+        span: Option<Range<usize>>,
+    ) -> IntValue<'ctx> {
+        if !self.options.safety_checks {
+            let result = match operation {
+                CheckedOperation::Add => self.builder.build_int_add(left, right, "add"),
+                CheckedOperation::Subtract => self.builder.build_int_sub(left, right, "sub"),
+                CheckedOperation::Multiply => self.builder.build_int_mul(left, right, "mul"),
+            };
+
+            return result.unwrap();
+        }
+
+        let intrinsic = Intrinsic::find(operation.intrinsic_name(is_unsigned)).unwrap();
+        let function = intrinsic
+            .get_declaration(&self.module, &[left.get_type().into()])
+            .unwrap();
+
+        let result = self
+            .builder
+            .build_call(function, &[left.into(), right.into()], "checked")
+            .unwrap()
+            .try_as_basic_value()
+            .unwrap_basic()
+            .into_struct_value();
+
+        let value = self
+            .builder
+            .build_extract_value(result, 0, "result")
+            .unwrap()
+            .into_int_value();
+        let overflowed = self
+            .builder
+            .build_extract_value(result, 1, "overflowed")
+            .unwrap()
+            .into_int_value();
+
+        self.build_check(overflowed, span, operation.overflow_message());
+
+        value
+    }
+
+    /// Checks the divisor of a division or remainder before it is calculated
+    ///
+    /// Dividing by zero is undefined, and so is dividing the smallest signed integer by -1 as the result doesn't fit
+    pub(crate) fn build_division_checks(
+        &self,
+        left: IntValue<'ctx>,
+        right: IntValue<'ctx>,
+        is_unsigned: bool,
+        span: Option<Range<usize>>,
+        is_remainder: bool,
+    ) {
+        if !self.options.safety_checks {
+            return;
+        }
+
+        let int_type = right.get_type();
+
+        let is_zero = self
+            .builder
+            .build_int_compare(IntPredicate::EQ, right, int_type.const_zero(), "is_zero")
+            .unwrap();
+
+        let message = if is_remainder {
+            "attempt to calculate the remainder with a divisor of zero"
+        } else {
+            "attempt to divide by zero"
+        };
+
+        self.build_check(is_zero, span.clone(), message);
+
+        // Unsigned integers don't have a -1 to divide by
+        if is_unsigned {
+            return;
+        }
+
+        let min_value = int_type.const_int(1 << (int_type.get_bit_width() - 1), false);
+        let is_min = self
+            .builder
+            .build_int_compare(IntPredicate::EQ, left, min_value, "is_min")
+            .unwrap();
+        let is_minus_one = self
+            .builder
+            .build_int_compare(
+                IntPredicate::EQ,
+                right,
+                int_type.const_all_ones(),
+                "is_minus_one",
+            )
+            .unwrap();
+        let overflowed = self
+            .builder
+            .build_and(is_min, is_minus_one, "overflowed")
+            .unwrap();
+
+        let message = if is_remainder {
+            "attempt to calculate the remainder with overflow"
+        } else {
+            "attempt to divide with overflow"
+        };
+
+        self.build_check(overflowed, span, message);
+    }
+
+    /// Panics with the message and the location of the span when the condition is true
+    fn build_check(&self, failed: IntValue<'ctx>, span: Option<Range<usize>>, message: &str) {
+        let current_function = self
+            .builder
+            .get_insert_block()
+            .unwrap()
+            .get_parent()
+            .unwrap();
+
+        let failed_block = self
+            .context
+            .append_basic_block(current_function, "--check-failed");
+        let passed_block = self
+            .context
+            .append_basic_block(current_function, "--check-passed");
+
+        self.builder
+            .build_conditional_branch(failed, failed_block, passed_block)
+            .unwrap();
+
+        self.builder.position_at_end(failed_block);
+
+        let prefix = match &span {
+            Some(span) => format!("Panic at {}: ", self.options.format_location(span)),
+            None => "Panic: ".to_string(),
+        };
+
+        self.build_frame_location(span.as_ref());
+        self.build_panic(&prefix, self.build_constant_string(message));
+
+        self.builder.position_at_end(passed_block);
+    }
+}
@@ -0,0 +1,430 @@
+use crate::codegen::Codegen;
+use gneurshk_parser::types::{DataType, FunctionType};
+use gneurshk_parser::{
+    Block, ElseBranch, Expression, FunctionCall, Identifier, IfStatement, Lambda,
+    MemberExpressionBase, Return, Stmt, VariableDeclaration,
+};
+use inkwell::AddressSpace;
+use inkwell::module::Linkage;
+use inkwell::types::{BasicTypeEnum, StructType};
+use inkwell::values::{BasicMetadataValueEnum, BasicValueEnum, FunctionValue, PointerValue};
+use std::ops::Range;
+
+/// The bindings a closure copied from the function that created it
+pub(crate) struct ClosureEnvironment<'ctx> {
+    pub(crate) struct_type: StructType<'ctx>,
+    pub(crate) captures: Vec<Capture<'ctx>>,
+}
+
+pub(crate) struct Capture<'ctx> {
+    pub(crate) name: String,
+    pub(crate) data_type: BasicTypeEnum<'ctx>,
+    /// The declared type of the captured binding, if it is known
+    pub(crate) declared_type: Option<DataType>,
+    /// The signature of the captured binding if it holds a function value itself
+    pub(crate) function_type: Option<FunctionType>,
+}
+
+impl<'ctx> Codegen<'ctx> {
+    /// Builds an anonymous function as a closure, copying the bindings it uses into a heap object
+    pub(crate) fn build_lambda(&mut self, lambda: Lambda) -> Option<BasicValueEnum<'ctx>> {
+        let function_type = lambda.function_type();
+
+        // Only the bindings of the enclosing function that the body refers to are captured
+        let mut names = Vec::new();
+        collect_block_names(&lambda.block, &mut names);
+
+        let mut captures = Vec::new();
+        let mut capture_values = Vec::new();
+
+        for name in names {
+            let is_param = lambda.params.iter().any(|param| param.name == name);
+            let is_captured = captures
+                .iter()
+                .any(|capture: &Capture| capture.name == name);
+
+            if is_param || is_captured {
+                continue;
+            }
+
+            // Functions are global, so they don't need to be captured
+            let Some(value) = self.load_binding(&name) else {
+                continue;
+            };
+
+            captures.push(Capture {
+                function_type: self.scope.get_function_type(&name),
+                declared_type: self.scope.get_data_type(&name),
+                data_type: value.get_type(),
+                name,
+            });
+            capture_values.push(value);
+        }
+
+        // Closures without captures don't need an environment
+        let mut environment_ptr = self.context.ptr_type(AddressSpace::default()).const_null();
+        let mut environment = None;
+
+        if !captures.is_empty() {
+            // Environments are heap objects, so they start with the object header
+            let mut field_types: Vec<BasicTypeEnum> = vec![self.get_object_header_type().into()];
+            field_types.extend(captures.iter().map(|capture| capture.data_type));
+
+            let struct_type = self.context.struct_type(&field_types, false);
+            let drop_function = self.build_environment_drop(struct_type, &captures);
+
+            environment_ptr = self.build_object_allocation(struct_type, drop_function);
+
+            // The environment owns the captured values for as long as the closure is alive
+            for (i, (capture, value)) in captures.iter().zip(capture_values).enumerate() {
+                let field_ptr = self
+                    .builder
+                    .build_struct_gep(struct_type, environment_ptr, i as u32 + 1, &capture.name)
+                    .unwrap();
+
+                self.builder.build_store(field_ptr, value).unwrap();
+                self.build_retain(value);
+            }
+
+            environment = Some(ClosureEnvironment {
+                struct_type,
+                captures,
+            });
+        }
+
+        // Name the function after the one that creates it
+        let parent_name = self
+            .builder
+            .get_insert_block()
+            .and_then(|block| block.get_parent())
+            .map(|function| function.get_name().to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let function = self.module.add_function(
+            &format!("{parent_name}.lambda"),
+            self.get_closure_fn_type(&function_type),
+            Some(Linkage::Internal),
+        );
+
+        self.build_function_body(
+            function,
+            lambda.span,
+            lambda.params,
+            lambda.return_type,
+            *lambda.block,
+            environment,
+        );
+
+        Some(self.build_closure(function, environment_ptr))
+    }
+
+    /// Builds the function that releases the captured values once the environment is freed
+    ///
+    /// # Returns
+    /// None if the environment doesn't hold references to other heap objects
+    fn build_environment_drop(
+        &self,
+        struct_type: StructType<'ctx>,
+        captures: &[Capture<'ctx>],
+    ) -> Option<FunctionValue<'ctx>> {
+        if !captures
+            .iter()
+            .any(|capture| self.is_reference_type(capture.data_type))
+        {
+            return None;
+        }
+
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let function = self.module.add_function(
+            "environment.drop",
+            self.context.void_type().fn_type(&[ptr_type.into()], false),
+            Some(Linkage::Internal),
+        );
+
+        let entry_block = self.context.append_basic_block(function, "entry");
+        let previous_block = self.builder.get_insert_block();
+        let previous_location = self.builder.get_current_debug_location();
+
+        self.builder.position_at_end(entry_block);
+        self.restore_debug_location(None);
+
+        let environment_ptr = function.get_first_param().unwrap().into_pointer_value();
+
+        for (i, capture) in captures.iter().enumerate() {
+            if !self.is_reference_type(capture.data_type) {
+                continue;
+            }
+
+            let field_ptr = self
+                .builder
+                .build_struct_gep(struct_type, environment_ptr, i as u32 + 1, &capture.name)
+                .unwrap();
+            let value = self
+                .builder
+                .build_load(capture.data_type, field_ptr, &capture.name)
+                .unwrap();
+
+            self.build_release(value);
+        }
+
+        self.builder.build_return(None).unwrap();
+
+        if let Some(previous_block) = previous_block {
+            self.builder.position_at_end(previous_block);
+        }
+
+        self.restore_debug_location(previous_location);
+
+        Some(function)
+    }
+
+    /// Turns a declared function into a function value, through a wrapper that ignores the environment
+    pub(crate) fn build_function_value(&mut self, name: &str) -> Option<BasicValueEnum<'ctx>> {
+        let function = self.scope.get_function(name, &[])?;
+        let function_type = self.function_types.get(name)?.clone();
+
+        let wrapper_name = format!("{}.closure", function.get_name().to_string_lossy());
+        let wrapper = match self.module.get_function(&wrapper_name) {
+            Some(wrapper) => wrapper,
+            None => self.build_closure_wrapper(function, &function_type, &wrapper_name),
+        };
+
+        let environment_ptr = self.context.ptr_type(AddressSpace::default()).const_null();
+
+        Some(self.build_closure(wrapper, environment_ptr))
+    }
+
+    fn build_closure_wrapper(
+        &mut self,
+        function: FunctionValue<'ctx>,
+        function_type: &FunctionType,
+        name: &str,
+    ) -> FunctionValue<'ctx> {
+        let wrapper = self.module.add_function(
+            name,
+            self.get_closure_fn_type(function_type),
+            Some(Linkage::Internal),
+        );
+
+        let entry_block = self.context.append_basic_block(wrapper, "entry");
+        let previous_block = self.builder.get_insert_block();
+        let previous_location = self.builder.get_current_debug_location();
+
+        self.builder.position_at_end(entry_block);
+        self.restore_debug_location(None);
+
+        // Every parameter except the environment is passed on
+        let args: Vec<BasicMetadataValueEnum<'ctx>> = wrapper
+            .get_params()
+            .into_iter()
+            .take(function_type.params.len())
+            .map(Into::into)
+            .collect();
+
+        let call_result = self.builder.build_call(function, &args, "call").unwrap();
+
+        // External functions without a return type don't produce a value
+        let return_value = match function.get_type().get_return_type() {
+            Some(_) => call_result.try_as_basic_value().unwrap_basic(),
+            None => self.context.i32_type().const_int(0, true).into(),
+        };
+
+        self.builder.build_return(Some(&return_value)).unwrap();
+
+        if let Some(previous_block) = previous_block {
+            self.builder.position_at_end(previous_block);
+        }
+
+        self.restore_debug_location(previous_location);
+
+        wrapper
+    }
+
+    fn build_closure(
+        &self,
+        function: FunctionValue<'ctx>,
+        environment_ptr: PointerValue<'ctx>,
+    ) -> BasicValueEnum<'ctx> {
+        let closure = self.get_closure_type().into_struct_type().const_zero();
+        let function_ptr = function.as_global_value().as_pointer_value();
+
+        let closure = self
+            .builder
+            .build_insert_value(closure, function_ptr, 0, "function")
+            .unwrap()
+            .into_struct_value();
+        let closure = self
+            .builder
+            .build_insert_value(closure, environment_ptr, 1, "closure")
+            .unwrap();
+
+        closure.into_struct_value().into()
+    }
+
+    /// Calls a function value through its function pointer, passing its environment after the arguments
+    pub(crate) fn build_closure_call(
+        &mut self,
+        name: String,
+        args: Vec<Expression>,
+        span: Range<usize>,
+    ) -> Option<BasicValueEnum<'ctx>> {
+        let function_type = self.scope.get_function_type(&name)?;
+        let closure = self.load_binding(&name)?.into_struct_value();
+
+        let function_ptr = self
+            .builder
+            .build_extract_value(closure, 0, "function")
+            .unwrap()
+            .into_pointer_value();
+        let environment_ptr = self
+            .builder
+            .build_extract_value(closure, 1, "environment")
+            .unwrap();
+
+        let mut arg_values: Vec<BasicMetadataValueEnum<'ctx>> = Vec::new();
+
+        for (arg, param) in args.into_iter().zip(&function_type.params) {
+            let value = self.build_expression_as(arg, self.get_value_type(param))?;
+            arg_values.push(value.into());
+        }
+
+        arg_values.push(environment_ptr.into());
+
+        self.build_frame_location(Some(&span));
+
+        let call_result = self
+            .builder
+            .build_indirect_call(
+                self.get_closure_fn_type(&function_type),
+                function_ptr,
+                &arg_values,
+                &format!("call_{name}"),
+            )
+            .unwrap();
+
+        Some(call_result.try_as_basic_value().unwrap_basic())
+    }
+
+    /// Gets the signature of an expression that creates a function value, if it is known
+    pub(crate) fn get_expression_function_type(&self, expr: &Expression) -> Option<FunctionType> {
+        match expr {
+            Expression::Lambda(lambda) => Some(lambda.function_type()),
+            Expression::Identifier(Identifier { name, .. }) => self
+                .scope
+                .get_function_type(name)
+                .or_else(|| self.function_types.get(name).cloned()),
+            Expression::FunctionCall(call) => {
+                let function_type = self
+                    .scope
+                    .get_function_type(&call.name)
+                    .or_else(|| self.function_types.get(&call.name).cloned())?;
+
+                self.resolve_function_type(&function_type.return_type?)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Collects every name that a block refers to, including those in nested anonymous functions
+fn collect_block_names(block: &Block, names: &mut Vec<String>) {
+    for stmt in &block.body {
+        collect_statement_names(stmt, names);
+    }
+}
+
+fn collect_statement_names(stmt: &Stmt, names: &mut Vec<String>) {
+    match stmt {
+        Stmt::Assignment(assignment) => {
+            collect_member_names(&assignment.member, names);
+            collect_expression_names(&assignment.value, names);
+        }
+        Stmt::VariableDeclaration(VariableDeclaration::Mutable {
+            value: Some(value), ..
+        })
+        | Stmt::VariableDeclaration(VariableDeclaration::Constant { value, .. })
+        | Stmt::Return(Return {
+            value: Some(value), ..
+        }) => collect_expression_names(value, names),
+        Stmt::Block(block) => collect_block_names(block, names),
+        Stmt::IfStatement(if_stmt) => collect_if_names(if_stmt, names),
+        Stmt::Loop(loop_stmt) => collect_block_names(&loop_stmt.block, names),
+        Stmt::BinaryExpression(expr) => {
+            collect_expression_names(&expr.left, names);
+            collect_expression_names(&expr.right, names);
+        }
+        Stmt::UnaryExpression(expr) => collect_expression_names(&expr.value, names),
+        Stmt::Cast(cast) => collect_expression_names(&cast.value, names),
+        Stmt::Try(try_expr) => collect_expression_names(&try_expr.value, names),
+        Stmt::Lambda(lambda) => collect_block_names(&lambda.block, names),
+        Stmt::Identifier(identifier) => names.push(identifier.name.clone()),
+        Stmt::FunctionCall(call) => collect_call_names(call, names),
+        Stmt::MemberAccess(member_access) => collect_member_names(&member_access.base, names),
+        Stmt::VariableDeclaration(VariableDeclaration::Mutable { value: None, .. })
+        | Stmt::Return(Return { value: None, .. })
+        | Stmt::Integer(_)
+        | Stmt::Float(_)
+        | Stmt::Boolean(_)
+        | Stmt::String(_)
+        | Stmt::None(_)
+        | Stmt::Continue(_)
+        | Stmt::Break(_) => {}
+    }
+}
+
+fn collect_expression_names(expr: &Expression, names: &mut Vec<String>) {
+    match expr {
+        Expression::Block(block) => collect_block_names(block, names),
+        Expression::IfStatement(if_stmt) => collect_if_names(if_stmt, names),
+        Expression::BinaryExpression(expr) => {
+            collect_expression_names(&expr.left, names);
+            collect_expression_names(&expr.right, names);
+        }
+        Expression::UnaryExpression(expr) => collect_expression_names(&expr.value, names),
+        Expression::Cast(cast) => collect_expression_names(&cast.value, names),
+        Expression::Try(try_expr) => collect_expression_names(&try_expr.value, names),
+        Expression::Lambda(lambda) => collect_block_names(&lambda.block, names),
+        Expression::Identifier(identifier) => names.push(identifier.name.clone()),
+        Expression::FunctionCall(call) => collect_call_names(call, names),
+        Expression::MemberAccess(member_access) => collect_member_names(&member_access.base, names),
+        Expression::Integer(_)
+        | Expression::Float(_)
+        | Expression::Boolean(_)
+        | Expression::String(_)
+        | Expression::None(_) => {}
+    }
+}
+
+fn collect_call_names(call: &FunctionCall, names: &mut Vec<String>) {
+    // Calls can go through a binding that holds a function value
+    names.push(call.name.clone());
+
+    for arg in &call.args {
+        collect_expression_names(arg, names);
+    }
+
+    for arg in &call.named_args {
+        collect_expression_names(&arg.value, names);
+    }
+}
+
+fn collect_if_names(if_stmt: &IfStatement, names: &mut Vec<String>) {
+    collect_expression_names(&if_stmt.condition, names);
+    collect_block_names(&if_stmt.if_block, names);
+
+    match if_stmt.else_statement.as_deref() {
+        Some(ElseBranch::Block(block)) => collect_block_names(block, names),
+        Some(ElseBranch::IfStatement(if_stmt)) => collect_if_names(if_stmt, names),
+        None => {}
+    }
+}
+
+fn collect_member_names(member: &MemberExpressionBase, names: &mut Vec<String>) {
+    match member {
+        MemberExpressionBase::Identifier(identifier) => names.push(identifier.name.clone()),
+        MemberExpressionBase::FunctionCall(call) => collect_call_names(call, names),
+        MemberExpressionBase::MemberAccess(member_access) => {
+            collect_member_names(&member_access.base, names)
+        }
+    }
+}
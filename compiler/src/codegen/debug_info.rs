@@ -0,0 +1,304 @@
+use crate::codegen::Codegen;
+use crate::options::OptimizationLevel;
+use gneurshk_parser::types::DataType;
+use inkwell::AddressSpace;
+use inkwell::debug_info::{
+    AsDIScope, DICompileUnit, DIFlags, DIFlagsConstants, DILocation, DIScope, DIType,
+    DWARFEmissionKind, DWARFSourceLanguage, DebugInfoBuilder, debug_metadata_version,
+};
+use inkwell::module::{FlagBehavior, Linkage};
+use inkwell::types::BasicTypeEnum;
+use inkwell::values::{BasicValueEnum, FunctionValue, PointerValue};
+use std::ops::Range;
+use std::path::Path;
+
+// The DWARF encodings of the basic types
+const DW_ATE_BOOLEAN: u32 = 0x02;
+const DW_ATE_FLOAT: u32 = 0x04;
+const DW_ATE_SIGNED: u32 = 0x05;
+const DW_ATE_SIGNED_CHAR: u32 = 0x06;
+const DW_ATE_UNSIGNED: u32 = 0x08;
+
+/// The builder and compile unit of the DWARF debug information in the module
+pub(crate) struct DebugInfo<'ctx> {
+    builder: DebugInfoBuilder<'ctx>,
+    compile_unit: DICompileUnit<'ctx>,
+}
+
+/// Debug information, which lets debuggers map the program back to the source code
+///
+/// It is only emitted when it is enabled in the compiler options
+impl<'ctx> Codegen<'ctx> {
+    pub(crate) fn add_debug_info(&mut self) {
+        if !self.options.debug_info {
+            return;
+        }
+
+        self.module.add_basic_value_flag(
+            "Debug Info Version",
+            FlagBehavior::Warning,
+            self.context
+                .i32_type()
+                .const_int(debug_metadata_version() as u64, false),
+        );
+
+        // Debuggers look for the source file relative to the directory of the compile unit
+        let source_path = Path::new(&self.options.source_name);
+        let file_name = source_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let directory = std::path::absolute(source_path)
+            .ok()
+            .and_then(|path| path.parent().map(|parent| parent.display().to_string()))
+            .unwrap_or_else(|| ".".to_string());
+
+        let (builder, compile_unit) = self.module.create_debug_info_builder(
+            true,
+            DWARFSourceLanguage::C,
+            &file_name,
+            &directory,
+            "gneurshk",
+            self.options.optimization != OptimizationLevel::O0,
+            "",
+            0,
+            "",
+            DWARFEmissionKind::Full,
+            0,
+            false,
+            false,
+            "",
+            "",
+        );
+
+        self.debug_info = Some(DebugInfo {
+            builder,
+            compile_unit,
+        });
+    }
+
+    /// Resolves the references between the debug information, which has to happen before the module is emitted
+    pub(crate) fn finalize_debug_info(&self) {
+        if let Some(debug_info) = &self.debug_info {
+            debug_info.builder.finalize();
+        }
+    }
+
+    /// Describes the function whose body is being built
+    ///
+    /// # Returns
+    /// The scope of the function, which the locations and variables inside of it belong to
+    pub(crate) fn build_function_debug_info(
+        &self,
+        function: FunctionValue<'ctx>,
+        span: &Range<usize>,
+    ) -> Option<DIScope<'ctx>> {
+        let debug_info = self.debug_info.as_ref()?;
+        let file = debug_info.compile_unit.get_file();
+        let (line, _) = self.options.line_and_column(span);
+
+        let subroutine_type =
+            debug_info
+                .builder
+                .create_subroutine_type(file, None, &[], DIFlags::PUBLIC);
+
+        let subprogram = debug_info.builder.create_function(
+            debug_info.compile_unit.as_debug_info_scope(),
+            &function.get_name().to_string_lossy(),
+            None,
+            file,
+            line as u32,
+            subroutine_type,
+            function.get_linkage() == Linkage::Internal,
+            true,
+            line as u32,
+            DIFlags::PUBLIC,
+            self.options.optimization != OptimizationLevel::O0,
+        );
+
+        function.set_subprogram(subprogram);
+
+        Some(subprogram.as_debug_info_scope())
+    }
+
+    /// Makes the instructions that are built next point to the span in the source code
+    pub(crate) fn set_debug_location(&self, span: Option<&Range<usize>>) {
+        if let Some(location) = span.and_then(|span| self.get_debug_location(span)) {
+            self.builder.set_current_debug_location(location);
+        }
+    }
+
+    /// Sets the location of the instructions that are built next, or clears it when there is none
+    ///
+    /// Functions without debug information can't have instructions that point into other functions
+    pub(crate) fn restore_debug_location(&self, location: Option<DILocation<'ctx>>) {
+        match location {
+            Some(location) => self.builder.set_current_debug_location(location),
+            None => self.builder.unset_current_debug_location(),
+        }
+    }
+
+    fn get_debug_location(&self, span: &Range<usize>) -> Option<DILocation<'ctx>> {
+        let debug_info = self.debug_info.as_ref()?;
+        let (line, column) = self.options.line_and_column(span);
+
+        Some(debug_info.builder.create_debug_location(
+            self.context,
+            line as u32,
+            column as u32,
+            self.debug_scope?,
+            None,
+        ))
+    }
+
+    /// Describes a variable that is stored behind the pointer, so debuggers can print it
+    ///
+    /// Parameters are numbered from 1, other variables don't have a number
+    pub(crate) fn build_variable_debug_info(
+        &self,
+        name: &str,
+        pointer: PointerValue<'ctx>,
+        stored_type: BasicTypeEnum<'ctx>,
+        data_type: Option<&DataType>,
+        span: &Range<usize>,
+        arg_number: Option<u32>,
+    ) {
+        let Some(debug_info) = &self.debug_info else {
+            return;
+        };
+        let Some(scope) = self.debug_scope else {
+            return;
+        };
+        let Some(location) = self.get_debug_location(span) else {
+            return;
+        };
+
+        // Most values are still stored as an i32, so the declared type is only used when it matches
+        let data_type = match data_type {
+            Some(data_type) if self.get_llvm_type(data_type) == stored_type => {
+                Some(data_type.clone())
+            }
+            _ => self.get_data_type(stored_type),
+        };
+        let Some(debug_type) = data_type.and_then(|data_type| self.get_debug_type(&data_type))
+        else {
+            return;
+        };
+
+        let file = debug_info.compile_unit.get_file();
+        let (line, _) = self.options.line_and_column(span);
+
+        let variable = match arg_number {
+            Some(arg_number) => debug_info.builder.create_parameter_variable(
+                scope,
+                name,
+                arg_number,
+                file,
+                line as u32,
+                debug_type,
+                true,
+                DIFlags::ZERO,
+            ),
+            None => debug_info.builder.create_auto_variable(
+                scope,
+                name,
+                file,
+                line as u32,
+                debug_type,
+                true,
+                DIFlags::ZERO,
+                0,
+            ),
+        };
+
+        debug_info.builder.insert_declare_at_end(
+            pointer,
+            Some(variable),
+            None,
+            location,
+            self.builder.get_insert_block().unwrap(),
+        );
+    }
+
+    /// Describes a parameter that is used directly, by storing it on the stack where debuggers can find it
+    pub(crate) fn build_parameter_debug_info(
+        &self,
+        name: &str,
+        value: BasicValueEnum<'ctx>,
+        data_type: &DataType,
+        span: &Range<usize>,
+        arg_number: u32,
+    ) {
+        if self.debug_scope.is_none() {
+            return;
+        }
+
+        let pointer = self.builder.build_alloca(value.get_type(), name).unwrap();
+        self.builder.build_store(pointer, value).unwrap();
+
+        self.build_variable_debug_info(
+            name,
+            pointer,
+            value.get_type(),
+            Some(data_type),
+            span,
+            Some(arg_number),
+        );
+    }
+
+    /// Gets the debug type of a data type, if debuggers can show values of it
+    fn get_debug_type(&self, data_type: &DataType) -> Option<DIType<'ctx>> {
+        let debug_info = self.debug_info.as_ref()?;
+
+        let (size_in_bits, encoding) = match data_type {
+            DataType::Int8 => (8, DW_ATE_SIGNED),
+            DataType::Int16 => (16, DW_ATE_SIGNED),
+            DataType::Int32 => (32, DW_ATE_SIGNED),
+            DataType::Int64 => (64, DW_ATE_SIGNED),
+            DataType::UInt8 => (8, DW_ATE_UNSIGNED),
+            DataType::UInt16 => (16, DW_ATE_UNSIGNED),
+            DataType::UInt32 => (32, DW_ATE_UNSIGNED),
+            DataType::UInt64 => (64, DW_ATE_UNSIGNED),
+            DataType::Float32 => (32, DW_ATE_FLOAT),
+            DataType::Float64 => (64, DW_ATE_FLOAT),
+            DataType::Boolean => (8, DW_ATE_BOOLEAN),
+            // Strings are pointers to null terminated characters
+            DataType::String => {
+                let char_type = debug_info
+                    .builder
+                    .create_basic_type("char", 8, DW_ATE_SIGNED_CHAR, DIFlags::PUBLIC)
+                    .ok()?;
+
+                let string_type = debug_info.builder.create_pointer_type(
+                    "String",
+                    char_type.as_type(),
+                    64,
+                    0,
+                    AddressSpace::default(),
+                );
+
+                return Some(string_type.as_type());
+            }
+            DataType::Custom(name) => {
+                return self
+                    .type_aliases
+                    .get(name)
+                    .and_then(|data_type| self.get_debug_type(data_type));
+            }
+            // Optionals, results and function values don't have a description yet
+            _ => return None,
+        };
+
+        let basic_type = debug_info
+            .builder
+            .create_basic_type(
+                &data_type.to_string(),
+                size_in_bits,
+                encoding,
+                DIFlags::PUBLIC,
+            )
+            .ok()?;
+
+        Some(basic_type.as_type())
+    }
+}
@@ -1,4 +1,7 @@
-use crate::codegen::{Codegen, scope::{AllocationKind, Variable}};
+use crate::codegen::{
+    Codegen,
+    scope::{AllocationKind, Reference, Variable},
+};
 use gneurshk_parser::VariableDeclaration;
 use inkwell::values::BasicValueEnum;
 
@@ -7,40 +10,79 @@ impl<'ctx> Codegen<'ctx> {
         &mut self,
         variable_declaration: VariableDeclaration,
     ) -> Option<BasicValueEnum<'ctx>> {
-        // Get name and value
-        let (name, value) = match variable_declaration {
-            VariableDeclaration::Mutable { name, value, .. } => (name, value),
-            VariableDeclaration::Constant { name, value, .. } => (name, Some(value)),
+        // Get name, type and value
+        let (name, data_type, value, span) = match variable_declaration {
+            VariableDeclaration::Mutable {
+                name,
+                data_type,
+                value,
+                span,
+            } => (name, data_type, value, span),
+            VariableDeclaration::Constant {
+                name,
+                data_type,
+                value,
+                span,
+            } => (name, data_type, Some(value), span),
+        };
+
+        self.set_debug_location(Some(&span));
+
+        // Remember the signature of function values, so they can be called
+        let function_type = match (&data_type, &value) {
+            (Some(data_type), _) => self.resolve_function_type(data_type),
+            (None, Some(value)) => self.get_expression_function_type(value),
+            (None, None) => None,
+        };
+
+        // Remember the type of the value for debuggers, and to instantiate generic calls with it
+        let value_data_type = data_type.clone().or_else(|| {
+            value
+                .as_ref()
+                .and_then(|value| self.get_expression_data_type(value))
+        });
+
+        // Compile the initial value first, so variables without a type can take on its type
+        let declared_type = data_type
+            .as_ref()
+            .map(|data_type| self.get_value_type(data_type));
+
+        let init_value = match (value, declared_type) {
+            (Some(val), Some(declared_type)) => self.build_expression_as(val, declared_type),
+            (Some(val), None) => self.build_expression(val),
+            (None, _) => None,
         };
 
         // Create variable allocation
-        let i32_type = self.context.i32_type();
-
-        let ptr = self.builder.build_alloca(i32_type, &name).unwrap();
-
-        // If its initial value is provided, compile and store it
-        if let Some(val) = value {
-            if let Some(init_value) = self.build_expression(val) {
-                self.builder.build_store(ptr, init_value).unwrap();
-            } else {
-                // Default to 0 if no value provided
-                let zero = i32_type.const_int(0, false);
-                self.builder.build_store(ptr, zero).unwrap();
-            }
-        } else {
-            // Default to 0 if no initial value is provided
-            let zero = i32_type.const_int(0, false);
-            self.builder.build_store(ptr, zero).unwrap();
+        let var_type = init_value
+            .map(|init_value| init_value.get_type())
+            .or(declared_type)
+            .unwrap_or_else(|| self.context.i32_type().into());
+
+        let ptr = self.builder.build_alloca(var_type, &name).unwrap();
+
+        // Default to 0 if no initial value is provided
+        let init_value = init_value.unwrap_or_else(|| var_type.const_zero());
+        self.builder.build_store(ptr, init_value).unwrap();
+        self.build_variable_debug_info(&name, ptr, var_type, value_data_type.as_ref(), &span, None);
+
+        // Store variable in the current scope, which owns the value from now on
+        let variable = Variable {
+            pointer: ptr,
+            alloc: AllocationKind::Stack,
+            data_type: var_type,
+        };
+
+        self.scope.set_variable(name.clone(), variable.clone());
+        self.add_reference(Reference::Variable(variable), init_value);
+
+        if let Some(function_type) = function_type {
+            self.scope.set_function_type(&name, function_type);
         }
 
-        // Store variable in the current scope
-        self.scope.set_variable(
-            name.clone(),
-            Variable {
-                pointer: ptr,
-                alloc: AllocationKind::Stack,
-            },
-        );
+        if let Some(value_data_type) = value_data_type {
+            self.scope.set_data_type(&name, value_data_type);
+        }
 
         None
     }
@@ -1,40 +1,141 @@
 use crate::codegen::Codegen;
+use gneurshk_parser::types::DataType;
 use gneurshk_parser::{Expression, FunctionCall};
 use inkwell::values::{BasicMetadataValueEnum, BasicValueEnum};
+use std::ops::Range;
 
 impl<'ctx> Codegen<'ctx> {
     pub(crate) fn build_function_call(
         &mut self,
         function_call: FunctionCall,
     ) -> Option<BasicValueEnum<'ctx>> {
-        let name = function_call.name;
-        let args = function_call.args;
+        let FunctionCall {
+            name,
+            mut args,
+            named_args,
+            span,
+        } = function_call;
 
         // Handle built-in functions
         match name.as_str() {
             "println" => return self.build_println(args),
             "print" => return self.build_print(args),
+            "live_allocations" => return self.build_live_allocations(),
+            "panic" => return self.build_panic_call(args, span),
+            "assert" => return self.build_assert(args, span),
             _ => (),
         }
 
-        // Get the function from the scope
-        let function = self.scope.get_function(&name)?;
+        // Bindings that hold function values are called through their function pointer
+        if self.scope.get_function_type(&name).is_some() {
+            return self.build_closure_call(name, args, span);
+        }
+
+        // Fill in the remaining params with named arguments or their default values,
+        // defaults only use literals so they can be built at the call site
+        let params = self.function_params.get(&name).cloned().unwrap_or_default();
+
+        for param in params.iter().skip(args.len()) {
+            let value = named_args
+                .iter()
+                .find(|arg| arg.name == param.name)
+                .map(|arg| arg.value.clone())
+                .or_else(|| param.default_value.clone());
+
+            match value {
+                Some(value) => args.push(value),
+                None => unreachable!(
+                    "The analyzer reports the missing argument for '{}' of '{}'",
+                    param.name, name
+                ),
+            }
+        }
+
+        // Get the function from the scope, generic functions are instantiated once the argument types are known
+        let generic_declaration = self.generic_functions.get(&name).cloned();
+        let declared_function = match generic_declaration {
+            Some(_) => None,
+            None => Some(self.scope.get_function(&name, &[])?),
+        };
 
-        // Compile the arguments
+        // Generic functions are instantiated with the types of the arguments,
+        // which also tell how integers are extended to the parameter types
+        let arg_types: Vec<Option<DataType>> = args
+            .iter()
+            .map(|arg| self.get_expression_data_type(arg))
+            .collect();
+
+        // Compile the arguments, wrapping them into optional parameters when needed
         let mut arg_values = Vec::new();
-        for arg in args {
-            if let Some(value) = self.build_expression(arg) {
-                arg_values.push(value.into());
+        for (i, arg) in args.into_iter().enumerate() {
+            let param_type = declared_function
+                .and_then(|function| function.get_nth_param(i as u32))
+                .map(|param| param.get_type());
+
+            let value = match param_type {
+                Some(param_type) => self.build_expression_as(arg, param_type),
+                None => self.build_expression(arg),
+            };
+
+            if let Some(value) = value {
+                arg_values.push(value);
             }
         }
 
-        // Build the function call
+        let function = match declared_function {
+            Some(function) => function,
+            None => self.get_generic_instance(generic_declaration?, &arg_types)?,
+        };
+
+        // Arguments are converted to the exact parameter types of generic instances, external and
+        // exported functions, the extra arguments of variadic functions are promoted like in C
+        let arg_values: Vec<BasicMetadataValueEnum<'ctx>> = arg_values
+            .into_iter()
+            .zip(&arg_types)
+            .enumerate()
+            .map(|(i, (value, data_type))| {
+                let value = match function.get_nth_param(i as u32) {
+                    Some(param) => {
+                        self.build_numeric_conversion(value, param.get_type(), data_type.as_ref())
+                    }
+                    None => self.build_variadic_promotion(value, data_type.as_ref()),
+                };
+
+                value.into()
+            })
+            .collect();
+
+        // Build the function call, remembering where it was made for the call stack
+        self.build_frame_location(Some(&span));
+
         let call_result = self
             .builder
             .build_call(function, &arg_values, &format!("call_{}", name))
             .unwrap();
 
-        Some(call_result.try_as_basic_value().unwrap_basic())
+        // External functions without a return type don't produce a value
+        if function.get_type().get_return_type().is_none() {
+            return None;
+        }
+
+        let return_value = call_result.try_as_basic_value().unwrap_basic();
+
+        // External and exported functions return their exact C type, which is converted back to the value type,
+        // generic instances keep their exact return type
+        let return_type = self
+            .function_types
+            .get(&name)
+            .and_then(|function_type| function_type.return_type.clone())
+            .map(|return_type| *return_type);
+
+        match return_type {
+            Some(return_type) if declared_function.is_some() => {
+                let value_type = self.get_value_type(&return_type);
+
+                Some(self.build_int_conversion(return_value, value_type, Some(&return_type)))
+            }
+            _ => Some(return_value),
+        }
     }
 
     fn build_println(&mut self, args: Vec<Expression>) -> Option<BasicValueEnum<'ctx>> {
@@ -46,20 +147,20 @@ impl<'ctx> Codegen<'ctx> {
             if let Some(value) = self.build_expression(arg.clone()) {
                 // Depending on the type, add the appropriate format specifier
                 match value {
-                    BasicValueEnum::FloatValue(float_val) => {
+                    BasicValueEnum::FloatValue(_) => {
                         format_str.push_str("%f");
 
-                        // Convert f32 to f64 for printf
-                        let f64_type = self.context.f64_type();
-                        let double_val = self
-                            .builder
-                            .build_float_ext(float_val, f64_type, "f64_ext")
-                            .unwrap();
-
-                        arg_values.push(double_val.into());
+                        // Floats are passed to printf as a double
+                        arg_values.push(self.build_variadic_promotion(value, None).into());
                     }
-                    BasicValueEnum::IntValue(_) => {
-                        format_str.push_str("%d");
+                    BasicValueEnum::IntValue(int_val) => {
+                        // 64 bit integers need a wider format specifier
+                        if int_val.get_type().get_bit_width() == 64 {
+                            format_str.push_str("%lld");
+                        } else {
+                            format_str.push_str("%d");
+                        }
+
                         arg_values.push(value.into());
                     }
                     BasicValueEnum::PointerValue(_) => {
@@ -86,7 +187,7 @@ impl<'ctx> Codegen<'ctx> {
             .unwrap();
 
         // Get printf function
-        let printf_fn = self.scope.get_function("printf")?;
+        let printf_fn = self.scope.get_function("printf", &[])?;
 
         // Call printf
         let mut printf_args = vec![format_str_global.as_pointer_value().into()];
@@ -108,20 +209,20 @@ impl<'ctx> Codegen<'ctx> {
             if let Some(value) = self.build_expression(arg.clone()) {
                 // Depending on the type, add the appropriate format specifier
                 match value {
-                    BasicValueEnum::FloatValue(float_val) => {
+                    BasicValueEnum::FloatValue(_) => {
                         format_str.push_str("%f");
 
-                        // Convert f32 to f64 for printf
-                        let f64_type = self.context.f64_type();
-                        let double_val = self
-                            .builder
-                            .build_float_ext(float_val, f64_type, "f64_ext")
-                            .unwrap();
-
-                        arg_values.push(double_val.into());
+                        // Floats are passed to printf as a double
+                        arg_values.push(self.build_variadic_promotion(value, None).into());
                     }
-                    BasicValueEnum::IntValue(_) => {
-                        format_str.push_str("%d");
+                    BasicValueEnum::IntValue(int_val) => {
+                        // 64 bit integers need a wider format specifier
+                        if int_val.get_type().get_bit_width() == 64 {
+                            format_str.push_str("%lld");
+                        } else {
+                            format_str.push_str("%d");
+                        }
+
                         arg_values.push(value.into());
                     }
                     BasicValueEnum::PointerValue(_) => {
@@ -148,7 +249,7 @@ impl<'ctx> Codegen<'ctx> {
             .unwrap();
 
         // Get printf function
-        let printf_fn = self.scope.get_function("printf")?;
+        let printf_fn = self.scope.get_function("printf", &[])?;
 
         // Call printf
         let mut printf_args = vec![format_str_global.as_pointer_value().into()];
@@ -160,4 +261,62 @@ impl<'ctx> Codegen<'ctx> {
 
         None
     }
+
+    /// Builds the `panic(message)` built-in, which stops the program with the message and the call stack
+    fn build_panic_call(
+        &mut self,
+        args: Vec<Expression>,
+        span: Range<usize>,
+    ) -> Option<BasicValueEnum<'ctx>> {
+        let message = self
+            .build_expression(args.into_iter().next()?)?
+            .into_pointer_value();
+
+        self.build_frame_location(Some(&span));
+        self.build_panic("Panic: ", message);
+
+        None
+    }
+
+    /// Builds the `assert(condition, message)` built-in, which panics when the condition is false
+    ///
+    /// The message is only built once the assertion failed
+    fn build_assert(
+        &mut self,
+        args: Vec<Expression>,
+        span: Range<usize>,
+    ) -> Option<BasicValueEnum<'ctx>> {
+        let mut args = args.into_iter();
+        let condition = self.build_expression(args.next()?)?.into_int_value();
+
+        // Get current function
+        let current_function = self
+            .builder
+            .get_insert_block()
+            .unwrap()
+            .get_parent()
+            .unwrap();
+
+        let failed_block = self
+            .context
+            .append_basic_block(current_function, "--assert-failed");
+        let passed_block = self
+            .context
+            .append_basic_block(current_function, "--assert-passed");
+
+        self.builder
+            .build_conditional_branch(condition, passed_block, failed_block)
+            .unwrap();
+
+        self.builder.position_at_end(failed_block);
+
+        let message = self.build_expression(args.next()?)?.into_pointer_value();
+
+        self.build_frame_location(Some(&span));
+        self.build_panic("Assertion failed: ", message);
+
+        self.builder.position_at_end(passed_block);
+
+        None
+    }
 }
@@ -1,42 +1,139 @@
 use crate::codegen::Codegen;
-use crate::codegen::scope::{AllocationKind, Variable};
+use crate::codegen::closures::ClosureEnvironment;
+use crate::codegen::scope::{AllocationKind, Reference, Variable, mangle_function_name};
+use anyhow::{Result, anyhow};
 use gneurshk_parser::types::DataType;
-use gneurshk_parser::{Block, FunctionParam};
+use gneurshk_parser::{
+    Annotation, Block, Expression, FunctionDeclaration, FunctionParam, StringLit,
+};
+use inkwell::attributes::{Attribute, AttributeLoc};
+use inkwell::module::Linkage;
+use inkwell::types::{BasicMetadataTypeEnum, BasicType};
 use inkwell::values::{BasicValueEnum, FunctionValue};
+use std::ops::Range;
+
+/// The calling convention LLVM uses for C functions
+const C_CALL_CONVENTION: u32 = 0;
 
 impl<'ctx> Codegen<'ctx> {
     pub(crate) fn build_function_body(
         &mut self,
         function: FunctionValue<'ctx>,
+        span: Range<usize>,
         params: Vec<FunctionParam>,
-        _return_type: Option<DataType>,
-        block: Block,
+        return_type: Option<DataType>,
+        mut block: Block,
+        environment: Option<ClosureEnvironment<'ctx>>,
     ) -> Option<BasicValueEnum<'ctx>> {
-        // Get i32 type since functions only support that type TODO: Support other types
-        let i32_type = self.context.i32_type();
-
         // Create entry block
         let entry_block = self.context.append_basic_block(function, "entry");
         let previous_block = self.builder.get_insert_block();
+        let previous_location = self.builder.get_current_debug_location();
 
         self.builder.position_at_end(entry_block);
 
-        // Create new scope for the function
+        // Describe the function for debuggers, the instructions inside of it point into its scope
+        let debug_scope = self.build_function_debug_info(function, &span);
+        let outer_debug_scope = std::mem::replace(&mut self.debug_scope, debug_scope);
+
+        self.set_debug_location(Some(&span));
+
+        // Push the function onto the call stack, returns pop it again
+        let frame = self.build_frame_push();
+        let outer_frame = self.frame.replace(frame);
+
+        // Create new scope for the function, returns release every scope inside of it
+        let outer_function_depth = self.function_depth;
+
+        self.function_depth = self.scope.depth();
         self.enter_new_scope();
 
+        // Closures read the bindings they captured from the environment after their parameters,
+        // the environment owns them so they aren't released when the call returns
+        if let Some(environment) = environment {
+            let environment_ptr = function.get_last_param().unwrap().into_pointer_value();
+
+            for (i, capture) in environment.captures.into_iter().enumerate() {
+                let pointer = self
+                    .builder
+                    .build_struct_gep(
+                        environment.struct_type,
+                        environment_ptr,
+                        i as u32 + 1,
+                        &capture.name,
+                    )
+                    .unwrap();
+
+                self.scope.set_variable(
+                    &capture.name,
+                    Variable {
+                        pointer,
+                        alloc: AllocationKind::Heap,
+                        data_type: capture.data_type,
+                    },
+                );
+
+                if let Some(function_type) = capture.function_type {
+                    self.scope.set_function_type(&capture.name, function_type);
+                }
+
+                if let Some(declared_type) = capture.declared_type {
+                    self.scope.set_data_type(&capture.name, declared_type);
+                }
+            }
+        }
+
         // Create a variable for each parameter in the current scope
         for (i, param) in params.iter().enumerate() {
             let param_value = function.get_nth_param(i as u32).unwrap();
-            let ptr = self.builder.build_alloca(i32_type, &param.name).unwrap();
-            self.builder.build_store(ptr, param_value).unwrap();
 
-            self.scope.set_variable(
-                param.name.clone(),
-                Variable {
+            // Parameters without `mut` can't be reassigned, so they are used directly
+            if !param.mutable {
+                self.scope.set_value(param.name.clone(), param_value);
+                self.add_reference(Reference::Value(param_value), param_value);
+                self.build_parameter_debug_info(
+                    &param.name,
+                    param_value,
+                    &param.data_type,
+                    &param.span,
+                    i as u32 + 1,
+                );
+            } else {
+                let param_type = param_value.get_type();
+                let ptr = self.builder.build_alloca(param_type, &param.name).unwrap();
+                self.builder.build_store(ptr, param_value).unwrap();
+                self.build_variable_debug_info(
+                    &param.name,
+                    ptr,
+                    param_type,
+                    Some(&param.data_type),
+                    &param.span,
+                    Some(i as u32 + 1),
+                );
+
+                let variable = Variable {
                     pointer: ptr,
                     alloc: AllocationKind::Stack,
-                },
-            );
+                    data_type: param_type,
+                };
+
+                self.scope
+                    .set_variable(param.name.clone(), variable.clone());
+                self.add_reference(Reference::Variable(variable), param_value);
+            }
+
+            // Function values passed as arguments are called through their signature
+            if let Some(function_type) = self.resolve_function_type(&param.data_type) {
+                self.scope.set_function_type(&param.name, function_type);
+            }
+
+            self.scope
+                .set_data_type(&param.name, param.data_type.clone());
+        }
+
+        // Values at the end of the body that need the return type are built like a return statement
+        if return_type.is_some() {
+            block.return_untyped_value();
         }
 
         // Compile function body
@@ -46,55 +143,251 @@ impl<'ctx> Codegen<'ctx> {
         let current_block = self.builder.get_insert_block().unwrap();
 
         if current_block.get_terminator().is_none() {
+            self.release_scopes(self.function_depth, return_value);
+            self.build_frame_pop();
+
             // Add default return
             if let Some(return_value) = return_value {
-                self.builder
-                    .build_return(Some(&return_value.into_int_value()))
-                    .unwrap();
-            } else {
+                // Values are wrapped when the function returns an optional
+                let return_value = match function.get_type().get_return_type() {
+                    Some(return_type) => self.wrap_optional(return_value, return_type),
+                    None => return_value,
+                };
+
+                self.builder.build_return(Some(&return_value)).unwrap();
+            } else if return_type.is_none() {
                 // Default to 0 if no return value provided
                 // NOTE: This is a temporary solution and should be removed later when other types are added
                 let i32_type = self.context.i32_type();
                 self.builder
                     .build_return(Some(&i32_type.const_int(0, true)))
                     .unwrap();
+            } else {
+                // The analyzer guarantees that functions with a return type never fall off the end
+                self.builder.build_unreachable().unwrap();
             }
         }
 
         // Exit function scope
         self.exit_scope();
+        self.function_depth = outer_function_depth;
+        self.frame = outer_frame;
+        self.debug_scope = outer_debug_scope;
 
         // Restore previous block if it exists
         if let Some(prev_block) = previous_block {
             self.builder.position_at_end(prev_block);
         }
 
+        self.restore_debug_location(previous_location);
+
         None
     }
 
     pub(crate) fn build_function_declaration(
         &mut self,
-        name: String,
-        params: Vec<FunctionParam>,
+        function: &FunctionDeclaration,
+        type_args: &[DataType],
     ) -> FunctionValue<'ctx> {
-        // Get i32 type since functions only support that type TODO: Support other types
-        let i32_type = self.context.i32_type();
+        // Exported functions are called from C, so they use the exact types like generic instances do,
+        // other functions still work with the value types
+        let export = function.get_annotation("export");
+        let uses_exact_types = export.is_some() || !type_args.is_empty();
 
-        // Create vector of parameter types (assuming they are all i32)
-        let mut param_types = Vec::new();
-        for _ in &params {
-            param_types.push(i32_type.into());
-        }
+        let get_type = |data_type: &DataType| {
+            if uses_exact_types {
+                self.get_llvm_type(data_type)
+            } else {
+                self.get_value_type(data_type)
+            }
+        };
 
-        // Create function type
-        let fn_type = i32_type.fn_type(&param_types, false);
+        // Create vector of parameter types
+        let param_types: Vec<BasicMetadataTypeEnum> = function
+            .params
+            .iter()
+            .map(|param| get_type(&param.data_type).into())
+            .collect();
+
+        // Create function type, functions without a return type still return an i32
+        let return_type = match &function.return_type {
+            Some(return_type) => get_type(return_type),
+            None => self.context.i32_type().into(),
+        };
+        let fn_type = return_type.fn_type(&param_types, false);
+
+        // Exported functions can be given a different symbol name
+        let symbol_name = get_symbol_name(export, &mangle_function_name(&function.name, type_args));
+
+        // Only the entry point and exported functions are visible outside of the module
+        let linkage = if export.is_some() || function.name == "main" {
+            None
+        } else {
+            Some(Linkage::Internal)
+        };
 
         // Add function to module
-        let function = self.module.add_function(&name, fn_type, None);
+        let function_value = self.module.add_function(&symbol_name, fn_type, linkage);
+
+        // Name the parameters, so generated C headers can use the same names
+        for (param_value, param) in function_value.get_param_iter().zip(&function.params) {
+            param_value.set_name(&param.name);
+        }
+
+        // Apply the inlining annotations
+        if function.has_annotation("inline") {
+            self.add_function_attribute(function_value, "alwaysinline");
+        }
+
+        if function.has_annotation("noinline") {
+            self.add_function_attribute(function_value, "noinline");
+        }
+
+        // Store function in the current scope
+        self.scope
+            .set_function(function.name.clone(), type_args, function_value);
+
+        function_value
+    }
+
+    /// Gets the instantiation of a generic function for the types of the arguments,
+    /// building it the first time it is called with those types
+    pub(crate) fn get_generic_instance(
+        &mut self,
+        function: FunctionDeclaration,
+        arg_types: &[Option<DataType>],
+    ) -> Option<FunctionValue<'ctx>> {
+        // Infer each type argument from the first parameter that uses it
+        let type_args = function
+            .type_params
+            .iter()
+            .map(|type_param| {
+                let index = function
+                    .params
+                    .iter()
+                    .position(|param| param.data_type == DataType::Custom(type_param.clone()))?;
+
+                arg_types.get(index)?.clone()
+            })
+            .collect::<Option<Vec<DataType>>>()?;
+
+        if let Some(instance) = self.scope.get_function(&function.name, &type_args) {
+            return Some(instance);
+        }
+
+        // Replace the type parameters with the inferred types
+        let substitute = |data_type: &DataType| match data_type {
+            DataType::Custom(name) => match function.type_params.iter().position(|t| t == name) {
+                Some(index) => type_args[index].clone(),
+                None => data_type.clone(),
+            },
+            _ => data_type.clone(),
+        };
+
+        let params: Vec<FunctionParam> = function
+            .params
+            .iter()
+            .map(|param| FunctionParam {
+                data_type: substitute(&param.data_type),
+                ..param.clone()
+            })
+            .collect();
+        let return_type = function.return_type.as_ref().map(substitute);
+
+        let instance = FunctionDeclaration {
+            type_params: vec![],
+            params,
+            return_type,
+            ..function
+        };
+
+        // Declare the instance before building the body so it can call itself
+        let function_value = self.build_function_declaration(&instance, &type_args);
+
+        if let Some(block) = instance.block {
+            self.build_function_body(
+                function_value,
+                instance.span,
+                instance.params,
+                instance.return_type,
+                *block,
+                None,
+            );
+        }
+
+        Some(function_value)
+    }
+
+    /// Declares a function that is defined outside of the program, with its exact C signature
+    ///
+    /// Symbols that are already declared, such as the built-in printf, are reused if the signatures match
+    pub(crate) fn build_extern_declaration(
+        &mut self,
+        function: &FunctionDeclaration,
+    ) -> Result<FunctionValue<'ctx>> {
+        let symbol_name = get_symbol_name(function.get_annotation("extern"), &function.name);
+
+        // Create the function type from the declared types
+        let param_types: Vec<BasicMetadataTypeEnum> = function
+            .params
+            .iter()
+            .map(|param| self.get_llvm_type(&param.data_type).into())
+            .collect();
+
+        let fn_type = match &function.return_type {
+            Some(return_type) => self
+                .get_llvm_type(return_type)
+                .fn_type(&param_types, function.variadic),
+            None => self
+                .context
+                .void_type()
+                .fn_type(&param_types, function.variadic),
+        };
+
+        let function_value = match self.module.get_function(&symbol_name) {
+            // Calls are built against the existing declaration, so it has to have the same signature
+            Some(function_value) if function_value.get_type() != fn_type => {
+                return Err(anyhow!(
+                    "Conflicting declarations of external function '{}': '{}' is already declared as '{}'",
+                    function.name,
+                    symbol_name,
+                    function_value
+                        .get_type()
+                        .print_to_string()
+                        .to_string_lossy()
+                ));
+            }
+            Some(function_value) => function_value,
+            None => {
+                let function_value =
+                    self.module
+                        .add_function(&symbol_name, fn_type, Some(Linkage::External));
+
+                function_value.set_call_conventions(C_CALL_CONVENTION);
+
+                function_value
+            }
+        };
 
         // Store function in the current scope
-        self.scope.set_function(name.clone(), function);
+        self.scope
+            .set_function(function.name.clone(), &[], function_value);
+
+        Ok(function_value)
+    }
+
+    pub(crate) fn add_function_attribute(&self, function: FunctionValue<'ctx>, name: &str) {
+        let kind_id = Attribute::get_named_enum_kind_id(name);
+        let attribute = self.context.create_enum_attribute(kind_id, 0);
+
+        function.add_attribute(AttributeLoc::Function, attribute);
+    }
+}
 
-        function
+/// Gets the symbol name from an `@export` or `@extern` annotation, defaulting to the function's name
+fn get_symbol_name(annotation: Option<&Annotation>, name: &str) -> String {
+    match annotation.and_then(|annotation| annotation.args.first()) {
+        Some(Expression::String(StringLit { value, .. })) => value.clone(),
+        _ => name.to_string(),
     }
 }
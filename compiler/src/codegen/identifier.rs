@@ -9,10 +9,24 @@ impl<'ctx> Codegen<'ctx> {
     ) -> Option<BasicValueEnum<'ctx>> {
         let name = identifier.name;
 
-        let variable_pointer = self.scope.get_variable(&name)?.pointer;
+        // Names that aren't bound in the scope refer to a declared function
+        match self.load_binding(&name) {
+            Some(value) => Some(value),
+            None => self.build_function_value(&name),
+        }
+    }
+
+    /// Loads the current value of a variable or immutable binding
+    pub(crate) fn load_binding(&self, name: &str) -> Option<BasicValueEnum<'ctx>> {
+        // Immutable bindings don't have an allocation to load from
+        if let Some(value) = self.scope.get_value(name) {
+            return Some(value);
+        }
+
+        let variable = self.scope.get_variable(name)?;
         let loaded_value = self
             .builder
-            .build_load(self.context.i32_type(), variable_pointer, &name)
+            .build_load(variable.data_type, variable.pointer, name)
             .unwrap();
 
         Some(loaded_value)
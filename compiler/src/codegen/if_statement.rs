@@ -1,28 +1,43 @@
 use crate::codegen::Codegen;
-use gneurshk_parser::{Block, ElseBranch, Expression, IfStatement};
+use gneurshk_parser::types::DataType;
+use gneurshk_parser::{Block, ElseBranch, Expression, Identifier, IfStatement};
 use inkwell::{IntPredicate, values::BasicValueEnum};
 
 impl<'ctx> Codegen<'ctx> {
     pub(crate) fn build_if_statement(
         &mut self,
+        binding: Option<Identifier>,
         condition: Expression,
         block: Block,
         else_block: Option<ElseBranch>,
     ) -> Option<BasicValueEnum<'ctx>> {
         // Compile the condition
+        self.set_debug_location(condition.span().as_ref());
+
+        // The binding holds the value inside of the optional
+        let binding_type = binding.as_ref().and_then(|_| {
+            match self.resolve_alias(&self.get_expression_data_type(&condition)?) {
+                DataType::Optional(inner) => Some(*inner),
+                _ => None,
+            }
+        });
+
         let condition_value = self.build_expression(condition)?;
 
-        // Convert to boolean (non-zero is true)
-        let zero = self.context.i32_type().const_int(0, false);
-        let condition_bool = self
-            .builder
-            .build_int_compare(
-                IntPredicate::NE,
-                condition_value.into_int_value(),
-                zero,
-                "condition",
-            )
-            .unwrap();
+        let condition_bool = if binding.is_some() {
+            // Optionals are unwrapped when their tag is set
+            self.builder
+                .build_extract_value(condition_value.into_struct_value(), 0, "has_value")
+                .unwrap()
+                .into_int_value()
+        } else {
+            // Convert to boolean (non-zero is true), booleans are compared at their own width
+            let condition_value = condition_value.into_int_value();
+            let zero = condition_value.get_type().const_zero();
+            self.builder
+                .build_int_compare(IntPredicate::NE, condition_value, zero, "condition")
+                .unwrap()
+        };
 
         // Get current function
         let current_function = self
@@ -59,7 +74,26 @@ impl<'ctx> Codegen<'ctx> {
 
         // Build the then block
         self.builder.position_at_end(then_branch);
-        self.build_block(block);
+
+        if let Some(binding) = binding {
+            // The unwrapped value only exists inside the then block
+            let value = self
+                .builder
+                .build_extract_value(condition_value.into_struct_value(), 1, &binding.name)
+                .unwrap();
+
+            self.enter_new_scope();
+            self.scope.set_value(&binding.name, value);
+
+            if let Some(binding_type) = binding_type {
+                self.scope.set_data_type(&binding.name, binding_type);
+            }
+
+            self.build_block(block);
+            self.exit_scope();
+        } else {
+            self.build_block(block);
+        }
 
         // Only add the merge branch if the current block doesn't have a terminator
         let current_block = self.builder.get_insert_block().unwrap();
@@ -78,11 +112,13 @@ impl<'ctx> Codegen<'ctx> {
             match else_block {
                 ElseBranch::Block(block) => self.build_block(block),
                 ElseBranch::IfStatement(IfStatement {
+                    binding: binding2,
                     condition: condition2,
                     if_block: block2,
                     else_statement: else_block2,
-                    ..
-                }) => self.build_if_statement(*condition2, *block2, else_block2.map(|b| *b)),
+                }) => {
+                    self.build_if_statement(binding2, *condition2, *block2, else_block2.map(|b| *b))
+                }
             };
 
             // Only add the merge branch if the current block doesn't have a terminator
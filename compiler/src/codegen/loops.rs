@@ -28,6 +28,7 @@ impl<'ctx> Codegen<'ctx> {
         self.loop_stack.push(LoopContext {
             continue_target: loop_body,
             break_target: after_loop,
+            scope_depth: self.scope.depth(),
         });
         self.build_block(*loop_stmt.block);
         self.loop_stack.pop();
@@ -51,6 +52,7 @@ impl<'ctx> Codegen<'ctx> {
             .last()
             .expect("break statement outside of a loop");
 
+        self.release_scopes(loop_context.scope_depth, None);
         self.builder
             .build_unconditional_branch(loop_context.break_target)
             .unwrap();
@@ -64,6 +66,7 @@ impl<'ctx> Codegen<'ctx> {
             .last()
             .expect("continue statement outside of a loop");
 
+        self.release_scopes(loop_context.scope_depth, None);
         self.builder
             .build_unconditional_branch(loop_context.continue_target)
             .unwrap();
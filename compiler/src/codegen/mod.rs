@@ -1,20 +1,28 @@
+use crate::codegen::debug_info::DebugInfo;
 use crate::codegen::scope::Scope;
+use crate::options::{CompilerOptions, CrateType};
 use anyhow::{Result, anyhow};
+use gneurshk_parser::types::{DataType, FunctionType};
 use gneurshk_parser::{
-    Assignment, BinaryExpression, BooleanLit, Expression, FloatLit, FunctionDeclaration,
-    IfStatement, IntegerLit, Program, Return, Stmt, StringLit, UnaryExpression,
+    Assignment, BooleanLit, Expression, FloatLit, FunctionDeclaration, FunctionParam, IfStatement,
+    IntegerLit, Program, Return, Stmt, StringLit, UnaryExpression,
 };
 use inkwell::AddressSpace;
 use inkwell::basic_block::BasicBlock;
 use inkwell::builder::Builder;
 use inkwell::context::Context;
+use inkwell::debug_info::DIScope;
 use inkwell::module::Module;
-use inkwell::values::BasicValueEnum;
+use inkwell::values::{BasicValueEnum, PointerValue};
 use std::collections::HashMap;
 
 mod assignment;
 mod binary_expression;
 mod block;
+mod call_stack;
+mod checks;
+mod closures;
+mod debug_info;
 mod declaration;
 mod function_call;
 mod function_declaration;
@@ -22,37 +30,76 @@ mod identifier;
 mod if_statement;
 mod literal;
 mod loops;
+mod optional;
+mod results;
 mod return_statement;
+mod runtime;
 mod scope;
+mod types;
 mod unary_expression;
 
+pub(crate) use runtime::JIT_MAIN;
+pub use runtime::PANIC_EXIT_CODE;
+
 struct LoopContext<'ctx> {
     continue_target: BasicBlock<'ctx>,
     break_target: BasicBlock<'ctx>,
+    /// The depth of the scope around the loop, the scopes inside are released when jumping out
+    scope_depth: usize,
 }
 
 pub struct Codegen<'ctx> {
     context: &'ctx Context,
     module: Module<'ctx>,
     builder: Builder<'ctx>,
+    options: CompilerOptions,
 
     scope: Box<Scope<'ctx>>,
     loop_stack: Vec<LoopContext<'ctx>>,
+    /// The depth of the scope around the function being built, the scopes inside are released when returning
+    function_depth: usize,
+    /// The call stack frame of the function being built
+    frame: Option<PointerValue<'ctx>>,
+    /// The debug information of the module, if it is enabled
+    debug_info: Option<DebugInfo<'ctx>>,
+    /// The debug scope of the function being built
+    debug_scope: Option<DIScope<'ctx>>,
+
+    /// The params of every declared function, used to fill in omitted arguments
+    function_params: HashMap<String, Vec<FunctionParam>>,
+    /// The signature of every declared function, used when a function is turned into a value
+    function_types: HashMap<String, FunctionType>,
+    /// Generic functions, which are only built once they are called with concrete types
+    generic_functions: HashMap<String, FunctionDeclaration>,
+    /// The type every type alias refers to
+    type_aliases: HashMap<String, DataType>,
 }
 
 impl<'ctx> Codegen<'ctx> {
-    pub fn new(context: &'ctx Context, module_name: &str) -> Self {
+    pub fn new(context: &'ctx Context, module_name: &str, options: CompilerOptions) -> Self {
         let mut codegen = Self {
             context,
             module: context.create_module(module_name),
             builder: context.create_builder(),
+            options,
 
             scope: Box::new(Scope::new(None)),
             loop_stack: Vec::new(),
+            function_depth: 0,
+            frame: None,
+            debug_info: None,
+            debug_scope: None,
+
+            function_params: HashMap::new(),
+            function_types: HashMap::new(),
+            generic_functions: HashMap::new(),
+            type_aliases: HashMap::new(),
         };
 
         // Add built-in functions
+        codegen.add_debug_info();
         codegen.add_builtin_functions();
+        codegen.add_runtime_functions();
         codegen
     }
 
@@ -63,7 +110,7 @@ impl<'ctx> Codegen<'ctx> {
 
         let printf_type = i32_type.fn_type(&[i8_ptr_type.into()], true);
         let printf_function = self.module.add_function("printf", printf_type, None);
-        self.scope.set_function("printf", printf_function);
+        self.scope.set_function("printf", &[], printf_function);
     }
 
     pub fn get_module(&self) -> &Module<'ctx> {
@@ -71,17 +118,55 @@ impl<'ctx> Codegen<'ctx> {
     }
 
     pub fn compile(&mut self, program: Program) -> Result<()> {
+        // Register the type aliases, unions are rejected by the analyzer
+        for type_alias in &program.type_aliases {
+            if let [data_type] = type_alias.types.as_slice() {
+                self.type_aliases
+                    .insert(type_alias.name.clone(), data_type.clone());
+            }
+        }
+
         // Prebuild all function declarations so they can reference each other
         let mut functions = HashMap::new();
 
-        for function in program.functions.clone() {
-            let FunctionDeclaration { name, params, .. } = function;
+        for function in &program.functions {
+            // Default values are built at the call site, where the other parameters don't exist
+            if let Some(param) = function.params.iter().find(|param| {
+                param
+                    .default_value
+                    .as_ref()
+                    .is_some_and(|default_value| !default_value.is_constant())
+            }) {
+                return Err(anyhow!(
+                    "Default value for parameter '{}' of function '{}' should only use literals",
+                    param.name,
+                    function.name
+                ));
+            }
 
-            functions.insert(name.clone(), self.build_function_declaration(name, params));
+            self.function_params
+                .insert(function.name.clone(), function.params.clone());
+            self.function_types
+                .insert(function.name.clone(), function.function_type());
+
+            if !function.type_params.is_empty() {
+                self.generic_functions
+                    .insert(function.name.clone(), function.clone());
+                continue;
+            }
+
+            // External functions are declared with their exact C signature
+            let function_value = if function.has_annotation("extern") {
+                self.build_extern_declaration(function)?
+            } else {
+                self.build_function_declaration(function, &[])
+            };
+
+            functions.insert(function.name.clone(), function_value);
         }
 
-        // Check if the program has an entry point
-        if !functions.contains_key("main") {
+        // Check if the program has an entry point, libraries are only called through their exports
+        if self.options.crate_type == CrateType::Bin && !functions.contains_key("main") {
             return Err(anyhow!(
                 "No program entry point found. Please define a main function."
             ));
@@ -91,17 +176,28 @@ impl<'ctx> Codegen<'ctx> {
         for function in program.functions {
             let FunctionDeclaration {
                 name,
+                span,
                 params,
                 return_type,
                 block,
                 ..
             } = function;
 
-            let function = functions.remove(&name).unwrap();
+            // Generic functions are built when they are called
+            let Some(function) = functions.remove(&name) else {
+                continue;
+            };
+
+            // External functions are only declared
+            let Some(block) = block else {
+                continue;
+            };
 
-            self.build_function_body(function, params, return_type, *block);
+            self.build_function_body(function, span, params, return_type, *block, None);
         }
 
+        self.finalize_debug_info();
+
         Ok(())
     }
 
@@ -111,18 +207,15 @@ impl<'ctx> Codegen<'ctx> {
             Stmt::Assignment(Assignment { member, value }) => self.build_assignment(member, value),
             Stmt::Block(block) => self.build_block(block),
             Stmt::IfStatement(IfStatement {
+                binding,
                 condition,
                 if_block: block,
                 else_statement: else_block,
-            }) => self.build_if_statement(*condition, *block, else_block.map(|b| *b)),
+            }) => self.build_if_statement(binding, *condition, *block, else_block.map(|b| *b)),
             Stmt::Identifier(identifier) => self.build_identifier(identifier),
             Stmt::FunctionCall(function_call) => self.build_function_call(function_call),
             Stmt::MemberAccess(_) => todo!(),
-            Stmt::BinaryExpression(BinaryExpression {
-                left,
-                right,
-                operator,
-            }) => self.build_binary_expression(*left, *right, operator),
+            Stmt::BinaryExpression(expr) => self.build_binary_expression(expr),
             Stmt::UnaryExpression(UnaryExpression { value, operator }) => {
                 self.build_unary_expression(*value, operator)
             }
@@ -130,11 +223,15 @@ impl<'ctx> Codegen<'ctx> {
             Stmt::Float(FloatLit { value, .. }) => self.build_float(value),
             Stmt::String(StringLit { value, .. }) => self.build_global_string(value),
             Stmt::Boolean(BooleanLit { value, .. }) => self.build_boolean(value),
-            Stmt::Return(Return { value }) => self.build_return_statement(value),
+            // The analyzer only accepts none where an optional type is expected
+            Stmt::None(_) => None,
+            Stmt::Return(Return { value, .. }) => self.build_return_statement(value),
             Stmt::Loop(loop_stmt) => self.build_loop(loop_stmt),
-            Stmt::Break => self.build_break_statement(),
-            Stmt::Continue => self.build_continue_statement(),
-            Stmt::Cast(_) => todo!(),
+            Stmt::Break(_) => self.build_break_statement(),
+            Stmt::Continue(_) => self.build_continue_statement(),
+            Stmt::Cast(cast) => self.build_cast(cast),
+            Stmt::Try(try_expr) => self.build_try(try_expr),
+            Stmt::Lambda(lambda) => self.build_lambda(lambda),
         }
     }
 
@@ -143,11 +240,7 @@ impl<'ctx> Codegen<'ctx> {
             Expression::Identifier(identifier) => self.build_identifier(identifier),
             Expression::FunctionCall(function_call) => self.build_function_call(function_call),
             Expression::MemberAccess(_) => todo!(),
-            Expression::BinaryExpression(BinaryExpression {
-                left,
-                right,
-                operator,
-            }) => self.build_binary_expression(*left, *right, operator),
+            Expression::BinaryExpression(expr) => self.build_binary_expression(expr),
             Expression::UnaryExpression(UnaryExpression { value, operator }) => {
                 self.build_unary_expression(*value, operator)
             }
@@ -155,6 +248,9 @@ impl<'ctx> Codegen<'ctx> {
             Expression::Float(FloatLit { value, .. }) => self.build_float(value),
             Expression::String(StringLit { value, .. }) => self.build_global_string(value),
             Expression::Boolean(BooleanLit { value, .. }) => self.build_boolean(value),
+            Expression::Try(try_expr) => self.build_try(try_expr),
+            Expression::Lambda(lambda) => self.build_lambda(lambda),
+            Expression::Cast(cast) => self.build_cast(cast),
             _ => {
                 // TODO: Handle other expressions
                 None
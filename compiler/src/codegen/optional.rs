@@ -0,0 +1,58 @@
+use crate::codegen::Codegen;
+use gneurshk_parser::Expression;
+use inkwell::types::BasicTypeEnum;
+use inkwell::values::BasicValueEnum;
+
+impl<'ctx> Codegen<'ctx> {
+    /// Builds an expression that is stored where a value of the target type is expected,
+    /// wrapping it into an optional when needed
+    pub(crate) fn build_expression_as(
+        &mut self,
+        expr: Expression,
+        target: BasicTypeEnum<'ctx>,
+    ) -> Option<BasicValueEnum<'ctx>> {
+        // None and results only have a type once it is known where they are stored
+        match expr {
+            Expression::None(_) => return Some(self.build_none(target)),
+            Expression::FunctionCall(call) if call.is_result_constructor() => {
+                return self.build_result_constructor(call, target);
+            }
+            _ => {}
+        }
+
+        let value = self.build_expression(expr)?;
+
+        Some(self.wrap_optional(value, target))
+    }
+
+    /// Builds a missing optional value, which has its tag cleared
+    pub(crate) fn build_none(&self, target: BasicTypeEnum<'ctx>) -> BasicValueEnum<'ctx> {
+        target.into_struct_type().const_zero().into()
+    }
+
+    /// Wraps a value into an optional with its tag set, unless it already has the target type
+    pub(crate) fn wrap_optional(
+        &self,
+        value: BasicValueEnum<'ctx>,
+        target: BasicTypeEnum<'ctx>,
+    ) -> BasicValueEnum<'ctx> {
+        if !target.is_struct_type() || value.get_type() == target {
+            return value;
+        }
+
+        let optional = target.into_struct_type().const_zero();
+        let has_value = self.context.bool_type().const_int(1, false);
+
+        let optional = self
+            .builder
+            .build_insert_value(optional, has_value, 0, "has_value")
+            .unwrap()
+            .into_struct_value();
+        let optional = self
+            .builder
+            .build_insert_value(optional, value, 1, "optional")
+            .unwrap();
+
+        optional.into_struct_value().into()
+    }
+}
@@ -0,0 +1,98 @@
+use crate::codegen::Codegen;
+use gneurshk_parser::{FunctionCall, TryExpression};
+use inkwell::types::BasicTypeEnum;
+use inkwell::values::BasicValueEnum;
+
+impl<'ctx> Codegen<'ctx> {
+    /// Builds an `ok(...)` or `err(...)` call as a result of the target type
+    pub(crate) fn build_result_constructor(
+        &mut self,
+        call: FunctionCall,
+        target: BasicTypeEnum<'ctx>,
+    ) -> Option<BasicValueEnum<'ctx>> {
+        // Ok values have their tag set and are stored after it, errors are stored last
+        let (tag, index) = if call.name == "ok" { (1, 1) } else { (0, 2) };
+
+        let payload = self.build_expression(call.args.into_iter().next()?)?;
+        let result = target.into_struct_type().const_zero();
+        let tag = self.context.bool_type().const_int(tag, false);
+
+        let result = self
+            .builder
+            .build_insert_value(result, tag, 0, "tag")
+            .unwrap()
+            .into_struct_value();
+        let result = self
+            .builder
+            .build_insert_value(result, payload, index, &call.name)
+            .unwrap();
+
+        Some(result.into_struct_value().into())
+    }
+
+    /// Builds the '?' operator, which returns the error or none from the current function
+    /// and otherwise continues with the unwrapped value
+    pub(crate) fn build_try(&mut self, try_expr: TryExpression) -> Option<BasicValueEnum<'ctx>> {
+        let value = self.build_expression(*try_expr.value)?.into_struct_value();
+
+        let has_value = self
+            .builder
+            .build_extract_value(value, 0, "has_value")
+            .unwrap()
+            .into_int_value();
+
+        // Get current function
+        let current_function = self
+            .builder
+            .get_insert_block()
+            .unwrap()
+            .get_parent()
+            .unwrap();
+
+        // Create basic blocks
+        let return_branch = self
+            .context
+            .append_basic_block(current_function, "--try-return");
+        let continue_branch = self
+            .context
+            .append_basic_block(current_function, "--try-continue");
+
+        self.builder
+            .build_conditional_branch(has_value, continue_branch, return_branch)
+            .unwrap();
+
+        // Pass the error or none on to the caller
+        self.builder.position_at_end(return_branch);
+
+        let return_type = current_function
+            .get_type()
+            .get_return_type()?
+            .into_struct_type();
+        let mut early_return = return_type.const_zero();
+
+        // Results have an error after their value, optionals only have the value
+        if value.get_type().count_fields() == 3 {
+            let error = self.builder.build_extract_value(value, 2, "error").unwrap();
+
+            early_return = self
+                .builder
+                .build_insert_value(early_return, error, 2, "error")
+                .unwrap()
+                .into_struct_value();
+        }
+
+        self.release_scopes(self.function_depth, None);
+        self.build_frame_pop();
+        self.builder.build_return(Some(&early_return)).unwrap();
+
+        // Continue with the unwrapped value
+        self.builder.position_at_end(continue_branch);
+
+        let unwrapped = self
+            .builder
+            .build_extract_value(value, 1, "unwrapped")
+            .unwrap();
+
+        Some(unwrapped)
+    }
+}
@@ -8,11 +8,32 @@ impl<'ctx> Codegen<'ctx> {
         value: Option<Expression>,
     ) -> Option<BasicValueEnum<'ctx>> {
         if let Some(value) = value {
-            let return_value = self.build_expression(value)?;
-            self.builder
-                .build_return(Some(&return_value.into_int_value()))
-                .unwrap();
+            self.set_debug_location(value.span().as_ref());
+
+            // Get the return type of the current function
+            let return_type = self
+                .builder
+                .get_insert_block()
+                .and_then(|block| block.get_parent())
+                .and_then(|function| function.get_type().get_return_type());
+
+            // Integers are converted to the exact return type of generic instances and exported functions
+            let value_type = self.get_expression_data_type(&value);
+            let return_value = match return_type {
+                Some(return_type) => {
+                    let return_value = self.build_expression_as(value, return_type)?;
+
+                    self.build_int_conversion(return_value, return_type, value_type.as_ref())
+                }
+                None => self.build_expression(value)?,
+            };
+
+            self.release_scopes(self.function_depth, Some(return_value));
+            self.build_frame_pop();
+            self.builder.build_return(Some(&return_value)).unwrap();
         } else {
+            self.release_scopes(self.function_depth, None);
+            self.build_frame_pop();
             self.builder.build_return(None).unwrap();
         }
 
@@ -0,0 +1,660 @@
+use crate::codegen::Codegen;
+use inkwell::basic_block::BasicBlock;
+use inkwell::module::Linkage;
+use inkwell::types::{BasicTypeEnum, FunctionType, StructType};
+use inkwell::values::{BasicValueEnum, FunctionValue, IntValue, PointerValue};
+use inkwell::{AddressSpace, IntPredicate};
+
+const ALLOCATE: &str = "gneurshk.alloc";
+const RETAIN: &str = "gneurshk.retain";
+const RELEASE: &str = "gneurshk.release";
+const RELEASE_UNOWNED: &str = "gneurshk.release_unowned";
+const DISOWN: &str = "gneurshk.disown";
+const DESTROY: &str = "gneurshk.destroy";
+const LIVE_ALLOCATIONS: &str = "gneurshk.live_allocations";
+const PANIC: &str = "gneurshk.panic";
+
+/// The exit status of a program that stopped because of a panic
+pub const PANIC_EXIT_CODE: u64 = 101;
+
+/// The entry point of programs that are run in process, which returns to the host when they exit
+pub(crate) const JIT_MAIN: &str = "gneurshk.jit_main";
+
+/// The runtime that manages heap objects with reference counting
+///
+/// Every heap object starts with a header that holds its reference count and a drop function,
+/// which releases the references the object holds itself. Objects start without an owner,
+/// every binding that stores one retains it and releases it once it falls out of scope.
+/// The object is freed as soon as its last owner releases it.
+impl<'ctx> Codegen<'ctx> {
+    pub(crate) fn get_object_header_type(&self) -> StructType<'ctx> {
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+
+        self.context
+            .struct_type(&[self.context.i64_type().into(), ptr_type.into()], false)
+    }
+
+    /// Adds the runtime functions to the module, so every executable carries its own runtime
+    pub(crate) fn add_runtime_functions(&mut self) {
+        let i64_type = self.context.i64_type();
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let void_type = self.context.void_type();
+
+        // Counts the objects that are allocated and not yet freed, so leaks can be detected
+        let live_allocations =
+            self.module
+                .add_global(i64_type, Some(AddressSpace::default()), LIVE_ALLOCATIONS);
+        live_allocations.set_initializer(&i64_type.const_zero());
+        live_allocations.set_linkage(Linkage::Internal);
+
+        let object_fn_type = void_type.fn_type(&[ptr_type.into()], false);
+
+        let i32_type = self.context.i32_type();
+
+        self.module.add_function(
+            "write",
+            i64_type.fn_type(&[i32_type.into(), ptr_type.into(), i64_type.into()], false),
+            None,
+        );
+        self.module
+            .add_function("exit", void_type.fn_type(&[i32_type.into()], false), None);
+        self.module
+            .add_function("strlen", i64_type.fn_type(&[ptr_type.into()], false), None);
+
+        self.add_call_stack();
+        self.build_panic_function(void_type.fn_type(&[ptr_type.into(), ptr_type.into()], false));
+        self.build_destroy_function(object_fn_type);
+        self.build_allocate_function(ptr_type.fn_type(&[i64_type.into(), ptr_type.into()], false));
+        self.build_retain_function(object_fn_type);
+        self.build_release_function(object_fn_type);
+        self.build_release_unowned_function(object_fn_type);
+        self.build_disown_function(object_fn_type);
+
+        self.builder.clear_insertion_position();
+    }
+
+    /// Adds the entry point for running the program in process, which calls main and returns its exit code
+    ///
+    /// The host replaces `exit` with a function that stores the exit code and jumps back with `longjmp`,
+    /// so panics and calls to `exit` return from this function instead of ending the host's process
+    pub(crate) fn add_jit_entry_point(&self, main: FunctionValue<'ctx>) {
+        let i32_type = self.context.i32_type();
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+
+        let setjmp =
+            self.module
+                .add_function("_setjmp", i32_type.fn_type(&[ptr_type.into()], false), None);
+        self.add_function_attribute(setjmp, "returns_twice");
+
+        let function = self.module.add_function(
+            JIT_MAIN,
+            i32_type.fn_type(&[ptr_type.into(), ptr_type.into()], false),
+            None,
+        );
+
+        let jump_buffer = function.get_nth_param(0).unwrap().into_pointer_value();
+        let exit_code = function.get_nth_param(1).unwrap().into_pointer_value();
+
+        let entry = self.context.append_basic_block(function, "entry");
+        let run_block = self.context.append_basic_block(function, "run");
+        let exited_block = self.context.append_basic_block(function, "exited");
+
+        // Setjmp returns a second time once the program exits through the host
+        self.builder.position_at_end(entry);
+
+        let jumped = self
+            .builder
+            .build_call(setjmp, &[jump_buffer.into()], "jumped")
+            .unwrap()
+            .try_as_basic_value()
+            .unwrap_basic()
+            .into_int_value();
+        let has_exited = self
+            .builder
+            .build_int_compare(
+                IntPredicate::NE,
+                jumped,
+                i32_type.const_zero(),
+                "has_exited",
+            )
+            .unwrap();
+
+        self.builder
+            .build_conditional_branch(has_exited, exited_block, run_block)
+            .unwrap();
+
+        self.builder.position_at_end(run_block);
+
+        let main_code = self
+            .builder
+            .build_call(main, &[], "exit_code")
+            .unwrap()
+            .try_as_basic_value()
+            .unwrap_basic();
+
+        self.builder.build_return(Some(&main_code)).unwrap();
+
+        self.builder.position_at_end(exited_block);
+
+        let exit_code = self
+            .builder
+            .build_load(i32_type, exit_code, "exit_code")
+            .unwrap();
+
+        self.builder.build_return(Some(&exit_code)).unwrap();
+        self.builder.clear_insertion_position();
+    }
+
+    /// Allocates an object of the given type, whose first field is the object header
+    pub(crate) fn build_object_allocation(
+        &self,
+        struct_type: StructType<'ctx>,
+        drop_function: Option<FunctionValue<'ctx>>,
+    ) -> PointerValue<'ctx> {
+        let drop_ptr = match drop_function {
+            Some(function) => function.as_global_value().as_pointer_value(),
+            None => self.context.ptr_type(AddressSpace::default()).const_null(),
+        };
+
+        self.builder
+            .build_call(
+                self.module.get_function(ALLOCATE).unwrap(),
+                &[struct_type.size_of().unwrap().into(), drop_ptr.into()],
+                "object",
+            )
+            .unwrap()
+            .try_as_basic_value()
+            .unwrap_basic()
+            .into_pointer_value()
+    }
+
+    /// Checks if values of the type hold a reference to a heap object
+    pub(crate) fn is_reference_type(&self, data_type: BasicTypeEnum<'ctx>) -> bool {
+        data_type == self.get_closure_type()
+    }
+
+    /// Takes a reference to the heap object a value holds
+    pub(crate) fn build_retain(&self, value: BasicValueEnum<'ctx>) {
+        self.call_runtime_function(RETAIN, value);
+    }
+
+    /// Gives up a reference to the heap object a value holds, freeing the object if it was the last one
+    pub(crate) fn build_release(&self, value: BasicValueEnum<'ctx>) {
+        self.call_runtime_function(RELEASE, value);
+    }
+
+    /// Frees the heap object of a temporary value that was never stored anywhere
+    pub(crate) fn build_release_unowned(&self, value: BasicValueEnum<'ctx>) {
+        self.call_runtime_function(RELEASE_UNOWNED, value);
+    }
+
+    /// Gives up a reference without freeing the object, so it can be handed on as a temporary value
+    pub(crate) fn build_disown(&self, value: BasicValueEnum<'ctx>) {
+        self.call_runtime_function(DISOWN, value);
+    }
+
+    /// Builds the `live_allocations()` built-in, which returns the number of heap objects that are alive
+    pub(crate) fn build_live_allocations(&self) -> Option<BasicValueEnum<'ctx>> {
+        let live_allocations = self.module.get_global(LIVE_ALLOCATIONS)?;
+
+        let count = self
+            .builder
+            .build_load(
+                self.context.i64_type(),
+                live_allocations.as_pointer_value(),
+                "live_allocations",
+            )
+            .unwrap()
+            .into_int_value();
+
+        let count = self
+            .builder
+            .build_int_truncate(count, self.context.i32_type(), "live_allocations")
+            .unwrap();
+
+        Some(count.into())
+    }
+
+    fn call_runtime_function(&self, name: &str, value: BasicValueEnum<'ctx>) {
+        if !self.is_reference_type(value.get_type()) {
+            return;
+        }
+
+        // Function values keep their object in the environment pointer
+        let object = self
+            .builder
+            .build_extract_value(value.into_struct_value(), 1, "object")
+            .unwrap();
+
+        self.builder
+            .build_call(
+                self.module.get_function(name).unwrap(),
+                &[object.into()],
+                "",
+            )
+            .unwrap();
+    }
+
+    fn build_allocate_function(&self, fn_type: FunctionType<'ctx>) {
+        let function = self.add_runtime_function(ALLOCATE, fn_type);
+        let size = function.get_nth_param(0).unwrap().into_int_value();
+        let drop_function = function.get_nth_param(1).unwrap();
+
+        let object = self
+            .builder
+            .build_array_malloc(self.context.i8_type(), size, "object")
+            .unwrap();
+
+        let header_type = self.get_object_header_type();
+        let count_ptr = self
+            .builder
+            .build_struct_gep(header_type, object, 0, "count")
+            .unwrap();
+        let drop_ptr = self
+            .builder
+            .build_struct_gep(header_type, object, 1, "drop")
+            .unwrap();
+
+        self.builder
+            .build_store(count_ptr, self.context.i64_type().const_zero())
+            .unwrap();
+        self.builder.build_store(drop_ptr, drop_function).unwrap();
+        self.build_live_allocations_change(1);
+
+        self.builder.build_return(Some(&object)).unwrap();
+    }
+
+    fn build_retain_function(&self, fn_type: FunctionType<'ctx>) {
+        let function = self.add_runtime_function(RETAIN, fn_type);
+        let exit = self.build_null_check(function);
+        let object = function.get_first_param().unwrap().into_pointer_value();
+
+        let (count_ptr, count) = self.build_load_count(object);
+        let count = self
+            .builder
+            .build_int_add(count, self.context.i64_type().const_int(1, false), "count")
+            .unwrap();
+
+        self.builder.build_store(count_ptr, count).unwrap();
+        self.builder.build_unconditional_branch(exit).unwrap();
+    }
+
+    fn build_release_function(&self, fn_type: FunctionType<'ctx>) {
+        let function = self.add_runtime_function(RELEASE, fn_type);
+        let exit = self.build_null_check(function);
+        let object = function.get_first_param().unwrap().into_pointer_value();
+
+        let unowned = self.context.append_basic_block(function, "unowned");
+        let owned = self.context.append_basic_block(function, "owned");
+        let last = self.context.append_basic_block(function, "last");
+
+        // Releasing an object without an owner means it was released more often than retained
+        let (count_ptr, count) = self.build_load_count(object);
+        let is_unowned = self
+            .builder
+            .build_int_compare(
+                IntPredicate::EQ,
+                count,
+                self.context.i64_type().const_zero(),
+                "is_unowned",
+            )
+            .unwrap();
+
+        self.builder
+            .build_conditional_branch(is_unowned, unowned, owned)
+            .unwrap();
+
+        self.builder.position_at_end(unowned);
+        self.build_panic(
+            "Panic: ",
+            self.build_constant_string(
+                "a heap object was released more often than it was retained",
+            ),
+        );
+
+        self.builder.position_at_end(owned);
+
+        let count = self
+            .builder
+            .build_int_sub(count, self.context.i64_type().const_int(1, false), "count")
+            .unwrap();
+        self.builder.build_store(count_ptr, count).unwrap();
+
+        let is_last = self
+            .builder
+            .build_int_compare(
+                IntPredicate::EQ,
+                count,
+                self.context.i64_type().const_zero(),
+                "is_last",
+            )
+            .unwrap();
+
+        self.builder
+            .build_conditional_branch(is_last, last, exit)
+            .unwrap();
+
+        self.builder.position_at_end(last);
+        self.build_destroy_call(object);
+        self.builder.build_unconditional_branch(exit).unwrap();
+    }
+
+    fn build_release_unowned_function(&self, fn_type: FunctionType<'ctx>) {
+        let function = self.add_runtime_function(RELEASE_UNOWNED, fn_type);
+        let exit = self.build_null_check(function);
+        let object = function.get_first_param().unwrap().into_pointer_value();
+
+        let unowned = self.context.append_basic_block(function, "unowned");
+
+        let (_, count) = self.build_load_count(object);
+        let is_unowned = self
+            .builder
+            .build_int_compare(
+                IntPredicate::EQ,
+                count,
+                self.context.i64_type().const_zero(),
+                "is_unowned",
+            )
+            .unwrap();
+
+        self.builder
+            .build_conditional_branch(is_unowned, unowned, exit)
+            .unwrap();
+
+        self.builder.position_at_end(unowned);
+        self.build_destroy_call(object);
+        self.builder.build_unconditional_branch(exit).unwrap();
+    }
+
+    fn build_disown_function(&self, fn_type: FunctionType<'ctx>) {
+        let function = self.add_runtime_function(DISOWN, fn_type);
+        let exit = self.build_null_check(function);
+        let object = function.get_first_param().unwrap().into_pointer_value();
+
+        let (count_ptr, count) = self.build_load_count(object);
+        let count = self
+            .builder
+            .build_int_sub(count, self.context.i64_type().const_int(1, false), "count")
+            .unwrap();
+
+        self.builder.build_store(count_ptr, count).unwrap();
+        self.builder.build_unconditional_branch(exit).unwrap();
+    }
+
+    /// Builds the function that drops the references an object holds and frees it
+    fn build_destroy_function(&self, fn_type: FunctionType<'ctx>) {
+        let function = self.add_runtime_function(DESTROY, fn_type);
+        let object = function.get_first_param().unwrap().into_pointer_value();
+
+        let call_drop = self.context.append_basic_block(function, "call_drop");
+        let free = self.context.append_basic_block(function, "free");
+
+        let drop_ptr = self
+            .builder
+            .build_struct_gep(self.get_object_header_type(), object, 1, "drop")
+            .unwrap();
+        let drop_function = self
+            .builder
+            .build_load(
+                self.context.ptr_type(AddressSpace::default()),
+                drop_ptr,
+                "drop",
+            )
+            .unwrap()
+            .into_pointer_value();
+
+        let has_drop = self
+            .builder
+            .build_is_not_null(drop_function, "has_drop")
+            .unwrap();
+
+        self.builder
+            .build_conditional_branch(has_drop, call_drop, free)
+            .unwrap();
+
+        self.builder.position_at_end(call_drop);
+        self.builder
+            .build_indirect_call(fn_type, drop_function, &[object.into()], "")
+            .unwrap();
+        self.builder.build_unconditional_branch(free).unwrap();
+
+        self.builder.position_at_end(free);
+        self.builder.build_free(object).unwrap();
+        self.build_live_allocations_change(-1);
+        self.builder.build_return(None).unwrap();
+    }
+
+    fn add_runtime_function(&self, name: &str, fn_type: FunctionType<'ctx>) -> FunctionValue<'ctx> {
+        let function = self
+            .module
+            .add_function(name, fn_type, Some(Linkage::Internal));
+        let entry_block = self.context.append_basic_block(function, "entry");
+
+        self.builder.position_at_end(entry_block);
+
+        function
+    }
+
+    /// Skips the rest of the function for null objects, such as the environment of a function without captures
+    ///
+    /// # Returns
+    /// The block that returns from the function
+    fn build_null_check(&self, function: FunctionValue<'ctx>) -> BasicBlock<'ctx> {
+        let object = function.get_first_param().unwrap().into_pointer_value();
+
+        let body = self.context.append_basic_block(function, "object");
+        let exit = self.context.append_basic_block(function, "exit");
+
+        let is_null = self.builder.build_is_null(object, "is_null").unwrap();
+
+        self.builder
+            .build_conditional_branch(is_null, exit, body)
+            .unwrap();
+
+        self.builder.position_at_end(exit);
+        self.builder.build_return(None).unwrap();
+
+        self.builder.position_at_end(body);
+
+        exit
+    }
+
+    fn build_load_count(&self, object: PointerValue<'ctx>) -> (PointerValue<'ctx>, IntValue<'ctx>) {
+        let count_ptr = self
+            .builder
+            .build_struct_gep(self.get_object_header_type(), object, 0, "count")
+            .unwrap();
+        let count = self
+            .builder
+            .build_load(self.context.i64_type(), count_ptr, "count")
+            .unwrap()
+            .into_int_value();
+
+        (count_ptr, count)
+    }
+
+    fn build_destroy_call(&self, object: PointerValue<'ctx>) {
+        self.builder
+            .build_call(
+                self.module.get_function(DESTROY).unwrap(),
+                &[object.into()],
+                "",
+            )
+            .unwrap();
+    }
+
+    fn build_live_allocations_change(&self, change: i64) {
+        let live_allocations = self
+            .module
+            .get_global(LIVE_ALLOCATIONS)
+            .unwrap()
+            .as_pointer_value();
+        let i64_type = self.context.i64_type();
+
+        let count = self
+            .builder
+            .build_load(i64_type, live_allocations, "live_allocations")
+            .unwrap()
+            .into_int_value();
+        let count = self
+            .builder
+            .build_int_add(
+                count,
+                i64_type.const_int(change as u64, true),
+                "live_allocations",
+            )
+            .unwrap();
+
+        self.builder.build_store(live_allocations, count).unwrap();
+    }
+
+    /// Stops the program with the message and the call stack, the program can't continue safely
+    ///
+    /// The prefix is known at compile time and says where the panic happened,
+    /// while the message can be a string that is only known at runtime
+    pub(crate) fn build_panic(&self, prefix: &str, message: PointerValue<'ctx>) {
+        self.builder
+            .build_call(
+                self.module.get_function(PANIC).unwrap(),
+                &[self.build_constant_string(prefix).into(), message.into()],
+                "",
+            )
+            .unwrap();
+        self.builder.build_unreachable().unwrap();
+    }
+
+    /// Builds a pointer to a null terminated string that is known at compile time
+    pub(crate) fn build_constant_string(&self, value: &str) -> PointerValue<'ctx> {
+        self.builder
+            .build_global_string_ptr(value, "runtime_str")
+            .unwrap()
+            .as_pointer_value()
+    }
+
+    /// Builds the function that writes the message and the call stack to stderr,
+    /// then exits with the panic exit code
+    fn build_panic_function(&self, fn_type: FunctionType<'ctx>) {
+        let function = self.add_runtime_function(PANIC, fn_type);
+
+        // Panics never return and are rare, which lets LLVM move them out of the way
+        self.add_function_attribute(function, "noreturn");
+        self.add_function_attribute(function, "cold");
+
+        let prefix = function.get_nth_param(0).unwrap().into_pointer_value();
+        let message = function.get_nth_param(1).unwrap().into_pointer_value();
+
+        self.build_write_to_stderr(prefix);
+        self.build_write_to_stderr(message);
+        self.build_write_to_stderr(self.build_constant_string("\n"));
+
+        // Walk the call stack from the innermost frame outwards
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+        let frame_type = self.get_frame_type();
+
+        let current_frame = self
+            .builder
+            .build_alloca(ptr_type, "current_frame")
+            .unwrap();
+        let stack_top = self
+            .builder
+            .build_load(ptr_type, self.get_stack_top(), "stack_top")
+            .unwrap();
+        self.builder.build_store(current_frame, stack_top).unwrap();
+
+        let loop_block = self.context.append_basic_block(function, "frames");
+        let frame_block = self.context.append_basic_block(function, "frame");
+        let location_block = self.context.append_basic_block(function, "location");
+        let next_block = self.context.append_basic_block(function, "next");
+        let exit_block = self.context.append_basic_block(function, "exit");
+
+        self.builder.build_unconditional_branch(loop_block).unwrap();
+
+        self.builder.position_at_end(loop_block);
+
+        let frame = self
+            .builder
+            .build_load(ptr_type, current_frame, "frame")
+            .unwrap()
+            .into_pointer_value();
+        let is_last = self.builder.build_is_null(frame, "is_last").unwrap();
+
+        self.builder
+            .build_conditional_branch(is_last, exit_block, frame_block)
+            .unwrap();
+
+        // Frames that haven't called anything yet don't have a location
+        self.builder.position_at_end(frame_block);
+
+        let location_ptr = self
+            .builder
+            .build_struct_gep(frame_type, frame, 1, "location")
+            .unwrap();
+        let location = self
+            .builder
+            .build_load(ptr_type, location_ptr, "location")
+            .unwrap()
+            .into_pointer_value();
+        let has_location = self
+            .builder
+            .build_is_not_null(location, "has_location")
+            .unwrap();
+
+        self.builder
+            .build_conditional_branch(has_location, location_block, next_block)
+            .unwrap();
+
+        self.builder.position_at_end(location_block);
+        self.build_write_to_stderr(location);
+        self.builder.build_unconditional_branch(next_block).unwrap();
+
+        self.builder.position_at_end(next_block);
+
+        let parent_ptr = self
+            .builder
+            .build_struct_gep(frame_type, frame, 0, "parent")
+            .unwrap();
+        let parent = self
+            .builder
+            .build_load(ptr_type, parent_ptr, "parent")
+            .unwrap();
+
+        self.builder.build_store(current_frame, parent).unwrap();
+        self.builder.build_unconditional_branch(loop_block).unwrap();
+
+        self.builder.position_at_end(exit_block);
+        self.builder
+            .build_call(
+                self.module.get_function("exit").unwrap(),
+                &[self
+                    .context
+                    .i32_type()
+                    .const_int(PANIC_EXIT_CODE, false)
+                    .into()],
+                "",
+            )
+            .unwrap();
+        self.builder.build_unreachable().unwrap();
+    }
+
+    fn build_write_to_stderr(&self, string: PointerValue<'ctx>) {
+        let length = self
+            .builder
+            .build_call(
+                self.module.get_function("strlen").unwrap(),
+                &[string.into()],
+                "length",
+            )
+            .unwrap()
+            .try_as_basic_value()
+            .unwrap_basic();
+        let stderr = self.context.i32_type().const_int(2, false);
+
+        self.builder
+            .build_call(
+                self.module.get_function("write").unwrap(),
+                &[stderr.into(), string.into(), length.into()],
+                "",
+            )
+            .unwrap();
+    }
+}
@@ -1,10 +1,11 @@
 use crate::codegen::Codegen;
-use inkwell::values::{FunctionValue, PointerValue};
+use gneurshk_parser::types::{DataType, FunctionType};
+use inkwell::types::BasicTypeEnum;
+use inkwell::values::{BasicValueEnum, FunctionValue, PointerValue};
 use std::collections::HashMap;
 use std::convert::AsRef;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-#[allow(dead_code)]
 pub enum AllocationKind {
     Stack,
     Heap,
@@ -14,6 +15,15 @@ pub enum AllocationKind {
 pub struct Variable<'ctx> {
     pub pointer: PointerValue<'ctx>,
     pub alloc: AllocationKind,
+    /// The type of the value stored behind the pointer
+    pub data_type: BasicTypeEnum<'ctx>,
+}
+
+/// A binding that owns a reference to a heap object, which is released when it falls out of scope
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Reference<'ctx> {
+    Variable(Variable<'ctx>),
+    Value(BasicValueEnum<'ctx>),
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -21,7 +31,14 @@ pub struct Scope<'ctx> {
     parent: Option<Box<Scope<'ctx>>>,
 
     variables: HashMap<String, Variable<'ctx>>,
+    /// Bindings that can never be reassigned, so they don't need an allocation
+    values: HashMap<String, BasicValueEnum<'ctx>>,
+    /// The signatures of the bindings that hold function values, needed to call them indirectly
+    function_types: HashMap<String, FunctionType>,
+    /// The declared types of the bindings, needed to infer the type arguments of generic calls
+    data_types: HashMap<String, DataType>,
     functions: HashMap<String, FunctionValue<'ctx>>,
+    references: Vec<Reference<'ctx>>,
 }
 
 impl<'ctx> Scope<'ctx> {
@@ -30,15 +47,27 @@ impl<'ctx> Scope<'ctx> {
             parent,
 
             variables: HashMap::new(),
+            values: HashMap::new(),
+            function_types: HashMap::new(),
+            data_types: HashMap::new(),
             functions: HashMap::new(),
+            references: Vec::new(),
         }
     }
 
     pub fn set_variable(&mut self, id: impl AsRef<str>, variable: Variable<'ctx>) {
+        self.values.remove(id.as_ref());
+        self.function_types.remove(id.as_ref());
+        self.data_types.remove(id.as_ref());
         self.variables.insert(id.as_ref().into(), variable);
     }
 
     pub fn get_variable(&self, id: impl AsRef<str>) -> Option<Variable<'ctx>> {
+        // A value in this scope shadows variables from the outer scopes
+        if self.values.contains_key(id.as_ref()) {
+            return None;
+        }
+
         self.variables.get(id.as_ref()).cloned().or_else(|| {
             self.parent
                 .as_ref()
@@ -46,21 +75,139 @@ impl<'ctx> Scope<'ctx> {
         })
     }
 
-    pub fn get_local_variables(&self) -> Vec<Variable<'ctx>> {
-        self.variables.clone().into_values().collect()
+    pub fn set_value(&mut self, id: impl AsRef<str>, value: BasicValueEnum<'ctx>) {
+        self.variables.remove(id.as_ref());
+        self.function_types.remove(id.as_ref());
+        self.data_types.remove(id.as_ref());
+        self.values.insert(id.as_ref().into(), value);
     }
 
-    pub fn set_function(&mut self, id: impl AsRef<str>, function: FunctionValue<'ctx>) {
-        self.functions.insert(id.as_ref().into(), function);
+    pub fn get_value(&self, id: impl AsRef<str>) -> Option<BasicValueEnum<'ctx>> {
+        // A variable in this scope shadows values from the outer scopes
+        if self.variables.contains_key(id.as_ref()) {
+            return None;
+        }
+
+        self.values
+            .get(id.as_ref())
+            .cloned()
+            .or_else(|| self.parent.as_ref().and_then(|parent| parent.get_value(id)))
     }
 
-    pub fn get_function(&self, id: impl AsRef<str>) -> Option<FunctionValue<'ctx>> {
-        self.functions.get(id.as_ref()).cloned().or_else(|| {
-            self.parent
-                .as_ref()
-                .and_then(|parent| parent.get_function(id))
-        })
+    /// Marks a binding in this scope as holding a function value of the given type
+    pub fn set_function_type(&mut self, id: impl AsRef<str>, function_type: FunctionType) {
+        self.function_types
+            .insert(id.as_ref().into(), function_type);
+    }
+
+    pub fn get_function_type(&self, id: impl AsRef<str>) -> Option<FunctionType> {
+        if let Some(function_type) = self.function_types.get(id.as_ref()) {
+            return Some(function_type.clone());
+        }
+
+        // Other bindings in this scope shadow function values from the outer scopes
+        if self.variables.contains_key(id.as_ref()) || self.values.contains_key(id.as_ref()) {
+            return None;
+        }
+
+        self.parent
+            .as_ref()
+            .and_then(|parent| parent.get_function_type(id))
+    }
+
+    /// Marks a binding in this scope as holding a value of the given type
+    pub fn set_data_type(&mut self, id: impl AsRef<str>, data_type: DataType) {
+        self.data_types.insert(id.as_ref().into(), data_type);
+    }
+
+    pub fn get_data_type(&self, id: impl AsRef<str>) -> Option<DataType> {
+        if let Some(data_type) = self.data_types.get(id.as_ref()) {
+            return Some(data_type.clone());
+        }
+
+        // Other bindings in this scope shadow the types from the outer scopes
+        if self.variables.contains_key(id.as_ref()) || self.values.contains_key(id.as_ref()) {
+            return None;
+        }
+
+        self.parent
+            .as_ref()
+            .and_then(|parent| parent.get_data_type(id))
+    }
+
+    pub fn add_reference(&mut self, reference: Reference<'ctx>) {
+        self.references.push(reference);
+    }
+
+    /// Gets the references owned by this scope and its parents that are nested deeper than `depth`,
+    /// starting with the innermost scope
+    pub fn get_references(&self, depth: usize) -> Vec<Reference<'ctx>> {
+        let mut references = Vec::new();
+        let mut scope = Some(self);
+
+        while let Some(current) = scope
+            && current.depth() > depth
+        {
+            references.extend(current.references.iter().rev().cloned());
+            scope = current.parent.as_deref();
+        }
+
+        references
+    }
+
+    /// The number of scopes this scope is nested in
+    pub fn depth(&self) -> usize {
+        self.parent.as_ref().map_or(0, |parent| parent.depth() + 1)
     }
+
+    /// Stores a function by its mangled name, so each instantiation of a generic function is kept
+    ///
+    /// Functions are global, so they are always stored in the outermost scope
+    pub fn set_function(
+        &mut self,
+        id: impl AsRef<str>,
+        type_args: &[DataType],
+        function: FunctionValue<'ctx>,
+    ) {
+        match self.parent.as_mut() {
+            Some(parent) => parent.set_function(id, type_args, function),
+            None => {
+                self.functions
+                    .insert(mangle_function_name(id.as_ref(), type_args), function);
+            }
+        }
+    }
+
+    pub fn get_function(
+        &self,
+        id: impl AsRef<str>,
+        type_args: &[DataType],
+    ) -> Option<FunctionValue<'ctx>> {
+        let name = mangle_function_name(id.as_ref(), type_args);
+
+        let mut scope = Some(self);
+
+        while let Some(current) = scope {
+            if let Some(function) = current.functions.get(&name) {
+                return Some(*function);
+            }
+
+            scope = current.parent.as_deref();
+        }
+
+        None
+    }
+}
+
+/// Mangles the name of a generic function instantiation, such as `max[Int32]`
+pub fn mangle_function_name(name: &str, type_args: &[DataType]) -> String {
+    if type_args.is_empty() {
+        return name.to_string();
+    }
+
+    let type_args: Vec<String> = type_args.iter().map(DataType::to_string).collect();
+
+    format!("{name}[{}]", type_args.join(", "))
 }
 
 impl<'ctx> Codegen<'ctx> {
@@ -76,4 +223,59 @@ impl<'ctx> Codegen<'ctx> {
             self.scope = parent;
         }
     }
+
+    /// Makes the current scope an owner of the heap object the binding holds
+    pub(crate) fn add_reference(
+        &mut self,
+        reference: Reference<'ctx>,
+        value: BasicValueEnum<'ctx>,
+    ) {
+        if !self.is_reference_type(value.get_type()) {
+            return;
+        }
+
+        // Variables on the heap live inside an object that already owns their value
+        if let Reference::Variable(Variable {
+            alloc: AllocationKind::Heap,
+            ..
+        }) = reference
+        {
+            return;
+        }
+
+        self.build_retain(value);
+        self.scope.add_reference(reference);
+    }
+
+    /// Releases the references owned by the scopes nested deeper than `depth`
+    ///
+    /// The value leaving the scopes is kept alive and handed on without an owner,
+    /// so whoever receives it can take it over
+    pub(crate) fn release_scopes(&self, depth: usize, value: Option<BasicValueEnum<'ctx>>) {
+        let references = self.scope.get_references(depth);
+
+        if references.is_empty() {
+            return;
+        }
+
+        if let Some(value) = value {
+            self.build_retain(value);
+        }
+
+        for reference in references {
+            let reference = match reference {
+                Reference::Variable(variable) => self
+                    .builder
+                    .build_load(variable.data_type, variable.pointer, "reference")
+                    .unwrap(),
+                Reference::Value(value) => value,
+            };
+
+            self.build_release(reference);
+        }
+
+        if let Some(value) = value {
+            self.build_disown(value);
+        }
+    }
 }
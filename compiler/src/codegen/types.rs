@@ -0,0 +1,273 @@
+use crate::codegen::Codegen;
+use gneurshk_parser::types::{DataType, FunctionType};
+use gneurshk_parser::{
+    BinaryExpression, BinaryOperator, Expression, Identifier, UnaryExpression, UnaryOperator,
+};
+use inkwell::AddressSpace;
+use inkwell::types::{self, BasicMetadataTypeEnum, BasicType, BasicTypeEnum};
+use inkwell::values::BasicValueEnum;
+
+impl<'ctx> Codegen<'ctx> {
+    /// Gets the LLVM type used to represent a data type
+    pub(crate) fn get_llvm_type(&self, data_type: &DataType) -> BasicTypeEnum<'ctx> {
+        match data_type {
+            DataType::Int8 | DataType::UInt8 => self.context.i8_type().into(),
+            DataType::Int16 | DataType::UInt16 => self.context.i16_type().into(),
+            DataType::Int32 | DataType::UInt32 => self.context.i32_type().into(),
+            DataType::Int64 | DataType::UInt64 => self.context.i64_type().into(),
+            DataType::Float32 => self.context.f32_type().into(),
+            DataType::Float64 => self.context.f64_type().into(),
+            DataType::Boolean => self.context.bool_type().into(),
+            DataType::String => self.context.ptr_type(AddressSpace::default()).into(),
+            // The analyzer reports aliases that refer to themselves, so following them always ends
+            DataType::Custom(name) => match self.type_aliases.get(name) {
+                Some(data_type) => self.get_llvm_type(data_type),
+                None => unreachable!("The analyzer reports the unknown type '{name}'"),
+            },
+            DataType::Optional(inner) => self.get_tagged_type(&[self.get_llvm_type(inner)]),
+            DataType::Result { value, error } => {
+                self.get_tagged_type(&[self.get_llvm_type(value), self.get_llvm_type(error)])
+            }
+            DataType::Function(_) => self.get_closure_type(),
+        }
+    }
+
+    /// Gets the LLVM type of the values that functions and variables work with
+    ///
+    /// Only optionals, results and function values have their own layout so far,
+    /// every other value is still an i32
+    pub(crate) fn get_value_type(&self, data_type: &DataType) -> BasicTypeEnum<'ctx> {
+        match data_type {
+            DataType::Optional(inner) => self.get_tagged_type(&[self.get_value_type(inner)]),
+            DataType::Result { value, error } => {
+                self.get_tagged_type(&[self.get_value_type(value), self.get_value_type(error)])
+            }
+            DataType::Function(_) => self.get_closure_type(),
+            DataType::Custom(name) => match self.type_aliases.get(name) {
+                Some(data_type) => self.get_value_type(data_type),
+                None => self.context.i32_type().into(),
+            },
+            _ => self.context.i32_type().into(),
+        }
+    }
+
+    /// Optionals and results are a struct of a tag that is set when the value exists,
+    /// followed by the value and the error of results
+    fn get_tagged_type(&self, payload_types: &[BasicTypeEnum<'ctx>]) -> BasicTypeEnum<'ctx> {
+        let mut field_types = vec![self.context.bool_type().into()];
+        field_types.extend_from_slice(payload_types);
+
+        self.context.struct_type(&field_types, false).into()
+    }
+
+    /// Function values are a struct of a function pointer and a pointer to the captured environment
+    pub(crate) fn get_closure_type(&self) -> BasicTypeEnum<'ctx> {
+        let ptr_type = self.context.ptr_type(AddressSpace::default());
+
+        self.context
+            .struct_type(&[ptr_type.into(), ptr_type.into()], false)
+            .into()
+    }
+
+    /// Gets the LLVM type of the function behind a function value,
+    /// which takes the captured environment after its parameters
+    pub(crate) fn get_closure_fn_type(
+        &self,
+        function_type: &FunctionType,
+    ) -> types::FunctionType<'ctx> {
+        let mut param_types: Vec<BasicMetadataTypeEnum> = function_type
+            .params
+            .iter()
+            .map(|param| self.get_value_type(param).into())
+            .collect();
+
+        param_types.push(self.context.ptr_type(AddressSpace::default()).into());
+
+        // Like other functions, function values without a return type still return an i32
+        let return_type = match &function_type.return_type {
+            Some(return_type) => self.get_value_type(return_type),
+            None => self.context.i32_type().into(),
+        };
+
+        return_type.fn_type(&param_types, false)
+    }
+
+    /// Gets the signature of a function type, following type aliases
+    pub(crate) fn resolve_function_type(&self, data_type: &DataType) -> Option<FunctionType> {
+        match data_type {
+            DataType::Function(function_type) => Some(function_type.clone()),
+            DataType::Custom(name) => self
+                .type_aliases
+                .get(name)
+                .and_then(|data_type| self.resolve_function_type(data_type)),
+            _ => None,
+        }
+    }
+
+    /// Gets the data type represented by an LLVM type, for values without a declared type
+    ///
+    /// Integers without a declared type are signed, and pointers don't tell strings and
+    /// other objects apart, so they have no data type
+    pub(crate) fn get_data_type(&self, llvm_type: BasicTypeEnum<'ctx>) -> Option<DataType> {
+        match llvm_type {
+            BasicTypeEnum::IntType(int_type) => match int_type.get_bit_width() {
+                1 => Some(DataType::Boolean),
+                8 => Some(DataType::Int8),
+                16 => Some(DataType::Int16),
+                32 => Some(DataType::Int32),
+                64 => Some(DataType::Int64),
+                _ => None,
+            },
+            BasicTypeEnum::FloatType(float_type) if float_type == self.context.f32_type() => {
+                Some(DataType::Float32)
+            }
+            BasicTypeEnum::FloatType(_) => Some(DataType::Float64),
+            _ => None,
+        }
+    }
+
+    /// Follows type aliases until it reaches the type they stand for
+    pub(crate) fn resolve_alias(&self, data_type: &DataType) -> DataType {
+        match data_type {
+            DataType::Custom(name) => match self.type_aliases.get(name) {
+                Some(data_type) => self.resolve_alias(data_type),
+                None => data_type.clone(),
+            },
+            _ => data_type.clone(),
+        }
+    }
+
+    /// Whether values of the data type are unsigned integers, which are extended and compared without a sign
+    pub(crate) fn is_unsigned_type(&self, data_type: &DataType) -> bool {
+        matches!(
+            self.resolve_alias(data_type),
+            DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64
+        )
+    }
+
+    /// Converts an integer to the width of the target type, extending it by the signedness of its data type
+    ///
+    /// Literals and most other integers are still an i32, while generic instances and exported functions use the exact types
+    pub(crate) fn build_int_conversion(
+        &self,
+        value: BasicValueEnum<'ctx>,
+        target: BasicTypeEnum<'ctx>,
+        data_type: Option<&DataType>,
+    ) -> BasicValueEnum<'ctx> {
+        let (BasicValueEnum::IntValue(int_value), BasicTypeEnum::IntType(int_type)) =
+            (value, target)
+        else {
+            return value;
+        };
+
+        if int_value.get_type() == int_type {
+            return value;
+        }
+
+        // Booleans are extended with zeros as well, so true stays 1
+        let is_signed = int_value.get_type().get_bit_width() > 1
+            && !data_type.is_some_and(|data_type| self.is_unsigned_type(data_type));
+
+        self.builder
+            .build_int_cast_sign_flag(int_value, int_type, is_signed, "int_cast")
+            .unwrap()
+            .into()
+    }
+
+    /// Converts a float to the precision of the target type, other values are returned unchanged
+    pub(crate) fn build_float_conversion(
+        &self,
+        value: BasicValueEnum<'ctx>,
+        target: BasicTypeEnum<'ctx>,
+    ) -> BasicValueEnum<'ctx> {
+        let (BasicValueEnum::FloatValue(float_value), BasicTypeEnum::FloatType(float_type)) =
+            (value, target)
+        else {
+            return value;
+        };
+
+        if float_value.get_type() == float_type {
+            return value;
+        }
+
+        self.builder
+            .build_float_cast(float_value, float_type, "float_cast")
+            .unwrap()
+            .into()
+    }
+
+    /// Converts integers and floats to the exact target type, other values are returned unchanged
+    pub(crate) fn build_numeric_conversion(
+        &self,
+        value: BasicValueEnum<'ctx>,
+        target: BasicTypeEnum<'ctx>,
+        data_type: Option<&DataType>,
+    ) -> BasicValueEnum<'ctx> {
+        let value = self.build_int_conversion(value, target, data_type);
+
+        self.build_float_conversion(value, target)
+    }
+
+    /// Applies the C argument promotions to the extra arguments of variadic functions,
+    /// so floats are passed as a double and booleans and small integers as an int
+    pub(crate) fn build_variadic_promotion(
+        &self,
+        value: BasicValueEnum<'ctx>,
+        data_type: Option<&DataType>,
+    ) -> BasicValueEnum<'ctx> {
+        match value {
+            BasicValueEnum::FloatValue(_) => {
+                self.build_float_conversion(value, self.context.f64_type().into())
+            }
+            BasicValueEnum::IntValue(int_value) if int_value.get_type().get_bit_width() < 32 => {
+                self.build_int_conversion(value, self.context.i32_type().into(), data_type)
+            }
+            _ => value,
+        }
+    }
+
+    /// Gets the data type of an expression without building it, used to infer type arguments
+    ///
+    /// LLVM types can't tell signed and unsigned integers or strings and other pointers apart,
+    /// so the type is taken from the literals and the declared types of bindings and functions
+    pub(crate) fn get_expression_data_type(&self, expr: &Expression) -> Option<DataType> {
+        match expr {
+            Expression::Integer(_) => Some(DataType::Int32),
+            Expression::Float(_) => Some(DataType::Float32),
+            Expression::Boolean(_) => Some(DataType::Boolean),
+            Expression::String(_) => Some(DataType::String),
+            Expression::Identifier(Identifier { name, .. }) => self.scope.get_data_type(name),
+            Expression::Cast(cast) => Some(cast.data_type.clone()),
+            Expression::Lambda(lambda) => Some(DataType::Function(lambda.function_type())),
+            Expression::UnaryExpression(UnaryExpression { value, operator }) => match operator {
+                UnaryOperator::Not => Some(DataType::Boolean),
+                UnaryOperator::Negative => self.get_expression_data_type(value),
+            },
+            Expression::BinaryExpression(BinaryExpression { left, operator, .. }) => match operator
+            {
+                BinaryOperator::Add
+                | BinaryOperator::Subtract
+                | BinaryOperator::Multiply
+                | BinaryOperator::Divide
+                | BinaryOperator::Modulus => self.get_expression_data_type(left),
+                _ => Some(DataType::Boolean),
+            },
+            Expression::Try(try_expr) => {
+                match self.resolve_alias(&self.get_expression_data_type(&try_expr.value)?) {
+                    DataType::Optional(inner) => Some(*inner),
+                    DataType::Result { value, .. } => Some(*value),
+                    _ => None,
+                }
+            }
+            // The return type of generic functions depends on their type arguments
+            Expression::FunctionCall(call) if !self.generic_functions.contains_key(&call.name) => {
+                self.scope
+                    .get_function_type(&call.name)
+                    .or_else(|| self.function_types.get(&call.name).cloned())?
+                    .return_type
+                    .map(|return_type| *return_type)
+            }
+            _ => None,
+        }
+    }
+}
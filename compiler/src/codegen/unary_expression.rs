@@ -1,6 +1,8 @@
 use crate::codegen::Codegen;
-use gneurshk_parser::{Expression, UnaryOperator};
+use crate::codegen::checks::CheckedOperation;
+use gneurshk_parser::{CastExpression, Expression, UnaryOperator};
 use inkwell::values::BasicValueEnum;
+use std::ops::Range;
 
 impl<'ctx> Codegen<'ctx> {
     pub(crate) fn build_unary_expression(
@@ -8,14 +10,26 @@ impl<'ctx> Codegen<'ctx> {
         value: Expression,
         operator: UnaryOperator,
     ) -> Option<BasicValueEnum<'ctx>> {
+        let span = value.span();
         let operand = self.build_expression(value)?;
 
         match operator {
             UnaryOperator::Not => self.build_not_expression(operand),
-            UnaryOperator::Negative => self.build_negative_expression(operand),
+            UnaryOperator::Negative => self.build_negative_expression(operand, span),
         }
     }
 
+    /// Builds a cast, integers and floats are converted to the width of the target type
+    ///
+    /// Other values aren't converted yet, most of them are still stored as an i32
+    pub(crate) fn build_cast(&mut self, cast: CastExpression) -> Option<BasicValueEnum<'ctx>> {
+        let value_type = self.get_expression_data_type(&cast.value);
+        let value = self.build_expression(*cast.value)?;
+        let target = self.get_llvm_type(&cast.data_type);
+
+        Some(self.build_numeric_conversion(value, target, value_type.as_ref()))
+    }
+
     fn build_not_expression(
         &mut self,
         operand: BasicValueEnum<'ctx>,
@@ -40,10 +54,19 @@ impl<'ctx> Codegen<'ctx> {
     fn build_negative_expression(
         &mut self,
         operand: BasicValueEnum<'ctx>,
+        span: Option<Range<usize>>,
     ) -> Option<BasicValueEnum<'ctx>> {
         match operand {
             BasicValueEnum::IntValue(int_val) => {
-                let result = self.builder.build_int_neg(int_val, "neg").unwrap();
+                // Negating the smallest integer overflows, just like subtracting it from zero
+                let zero = int_val.get_type().const_zero();
+                let result = self.build_checked_int_operation(
+                    CheckedOperation::Subtract,
+                    zero,
+                    int_val,
+                    false,
+                    span,
+                );
                 Some(result.into())
             }
             BasicValueEnum::FloatValue(float_val) => {
@@ -1,2 +1,5 @@
 mod codegen;
+pub mod options;
 pub mod output;
+
+pub use codegen::PANIC_EXIT_CODE;
@@ -0,0 +1,151 @@
+use crate::output::linker::Linker;
+use anyhow::{Error, anyhow};
+use std::ops::Range;
+use std::str::FromStr;
+
+/// Settings that control the code the compiler generates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompilerOptions {
+    /// Stop the program with an error when integer arithmetic overflows or divides by zero
+    pub safety_checks: bool,
+    /// The name of the source file, shown when a safety check fails
+    pub source_name: String,
+    /// The source code, used to find the line and column of a failed safety check
+    pub source: String,
+    /// Emit DWARF debug information, so debuggers can step through the source code
+    pub debug_info: bool,
+    /// How much the generated code is optimized before it is emitted
+    pub optimization: OptimizationLevel,
+    /// The target triple to generate code for, such as `aarch64-unknown-linux-gnu`, defaulting to the host
+    pub target: Option<String>,
+    /// The CPU to generate code for, `native` selects the CPU and features of the host
+    pub target_cpu: String,
+    /// The CPU features to enable or disable, such as `+avx2,-sse4.1`
+    pub target_features: String,
+    /// The program that links the object file into an executable
+    pub linker: Linker,
+    /// Whether the program is built into an executable or a library
+    pub crate_type: CrateType,
+}
+
+impl Default for CompilerOptions {
+    fn default() -> Self {
+        Self {
+            safety_checks: true,
+            source_name: "main.iv".to_string(),
+            source: String::new(),
+            debug_info: false,
+            optimization: OptimizationLevel::default(),
+            target: None,
+            target_cpu: "generic".to_string(),
+            target_features: String::new(),
+            linker: Linker::default(),
+            crate_type: CrateType::default(),
+        }
+    }
+}
+
+impl CompilerOptions {
+    /// Converts a span into the location of the code in the source file, as `file:line:column`
+    pub fn format_location(&self, span: &Range<usize>) -> String {
+        let (line, column) = self.line_and_column(span);
+
+        format!("{}:{line}:{column}", self.source_name)
+    }
+
+    /// Finds the line and column where the span starts, both counting from 1
+    pub fn line_and_column(&self, span: &Range<usize>) -> (usize, usize) {
+        let before = self.source.get(..span.start).unwrap_or(&self.source);
+        let line = before.matches('\n').count() + 1;
+        let column = before.chars().rev().take_while(|c| *c != '\n').count() + 1;
+
+        (line, column)
+    }
+}
+
+/// The optimization levels, named after the `-O` flags that select them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OptimizationLevel {
+    /// Only the passes that are required, such as inlining functions marked `@inline`
+    #[default]
+    O0,
+    O1,
+    O2,
+    O3,
+    /// Optimizes for size
+    Os,
+    /// Optimizes for size even more aggressively, at the cost of speed
+    Oz,
+}
+
+impl OptimizationLevel {
+    /// Gets the pipeline of the new pass manager that optimizes the module
+    pub fn pass_pipeline(self) -> &'static str {
+        match self {
+            OptimizationLevel::O0 => "default<O0>",
+            OptimizationLevel::O1 => "default<O1>",
+            OptimizationLevel::O2 => "default<O2>",
+            OptimizationLevel::O3 => "default<O3>",
+            OptimizationLevel::Os => "default<Os>",
+            OptimizationLevel::Oz => "default<Oz>",
+        }
+    }
+
+    /// Gets the level the target machine optimizes the machine code with
+    pub fn codegen_level(self) -> inkwell::OptimizationLevel {
+        match self {
+            OptimizationLevel::O0 => inkwell::OptimizationLevel::None,
+            OptimizationLevel::O1 => inkwell::OptimizationLevel::Less,
+            OptimizationLevel::O2 | OptimizationLevel::Os | OptimizationLevel::Oz => {
+                inkwell::OptimizationLevel::Default
+            }
+            OptimizationLevel::O3 => inkwell::OptimizationLevel::Aggressive,
+        }
+    }
+}
+
+impl FromStr for OptimizationLevel {
+    type Err = Error;
+
+    /// Parses the value of an `-O` flag, such as `2` or `s`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "0" => Ok(OptimizationLevel::O0),
+            "1" => Ok(OptimizationLevel::O1),
+            "2" => Ok(OptimizationLevel::O2),
+            "3" => Ok(OptimizationLevel::O3),
+            "s" => Ok(OptimizationLevel::Os),
+            "z" => Ok(OptimizationLevel::Oz),
+            _ => Err(anyhow!(
+                "Unknown optimization level '{value}', expected 0, 1, 2, 3, s or z"
+            )),
+        }
+    }
+}
+
+/// The kinds of programs the compiler can build
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CrateType {
+    /// An executable, which needs a main function
+    #[default]
+    Bin,
+    /// A static library (.a) that exports the functions marked `@export`
+    Staticlib,
+    /// A shared library (.so, .dylib or .dll) that exports the functions marked `@export`
+    Cdylib,
+}
+
+impl FromStr for CrateType {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "bin" => Ok(CrateType::Bin),
+            "staticlib" => Ok(CrateType::Staticlib),
+            "cdylib" => Ok(CrateType::Cdylib),
+            _ => Err(anyhow!(
+                "Unknown crate type '{value}', expected bin, staticlib or cdylib"
+            )),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use crate::options::CompilerOptions;
+use crate::output::object::write_machine_code_file;
+use anyhow::Result;
+use gneurshk_parser::Program;
+use inkwell::targets::FileType;
+use std::path::{Path, PathBuf};
+
+/// Creates assembly files (.s) from the AST
+///
+/// # Returns
+/// The path to the assembly file
+pub fn create_assembly_file(ast: Program, output_path: &Path) -> Result<PathBuf> {
+    create_assembly_file_with_options(ast, output_path, CompilerOptions::default())
+}
+
+/// Creates assembly files (.s) from the AST, generating code with the given options
+///
+/// # Returns
+/// The path to the assembly file
+pub fn create_assembly_file_with_options(
+    ast: Program,
+    output_path: &Path,
+    options: CompilerOptions,
+) -> Result<PathBuf> {
+    write_machine_code_file(
+        ast,
+        &output_path.with_extension("s"),
+        options,
+        FileType::Assembly,
+    )
+}
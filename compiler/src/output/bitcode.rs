@@ -0,0 +1,49 @@
+use crate::codegen::Codegen;
+use crate::options::CompilerOptions;
+use crate::output::object::{create_target_machine, optimize_module};
+use anyhow::{Result, anyhow};
+use gneurshk_parser::Program;
+use inkwell::context::Context;
+use std::path::{Path, PathBuf};
+
+/// Creates LLVM bitcode files (.bc) from the AST
+///
+/// # Returns
+/// The path to the bitcode file
+pub fn create_bitcode_file(ast: Program, output_path: &Path) -> Result<PathBuf> {
+    create_bitcode_file_with_options(ast, output_path, CompilerOptions::default())
+}
+
+/// Creates LLVM bitcode files (.bc) from the AST, generating code with the given options
+///
+/// The bitcode is optimized like the code that goes into object files
+///
+/// # Returns
+/// The path to the bitcode file
+pub fn create_bitcode_file_with_options(
+    ast: Program,
+    output_path: &Path,
+    options: CompilerOptions,
+) -> Result<PathBuf> {
+    let context = Context::create();
+    let mut codegen = Codegen::new(&context, "main", options.clone());
+
+    codegen.compile(ast)?;
+
+    let module = codegen.get_module();
+
+    optimize_module(module, &create_target_machine(&options)?, &options)?;
+
+    // Write the bitcode to file
+    let bitcode_path = output_path.with_extension("bc");
+
+    if !module.write_bitcode_to_path(&bitcode_path) {
+        return Err(anyhow!(
+            "Failed to write bitcode file: {}",
+            bitcode_path.display()
+        ));
+    }
+
+    // Return the path to the bitcode file
+    Ok(bitcode_path)
+}
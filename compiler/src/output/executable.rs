@@ -1,39 +1,48 @@
-use crate::output::object::create_object_file;
+use crate::options::CompilerOptions;
+use crate::output::object::{create_object_file_with_options, get_target_triple};
 use anyhow::{Result, anyhow};
 use gneurshk_parser::Program;
 use std::path::{Path, PathBuf};
-use std::process::Command;
 
 /// Compiles the AST into an executable
 ///
 /// # Returns
 /// The path to the executable
 pub fn compile_to_executable(ast: Program, output_path: &Path) -> Result<PathBuf> {
+    compile_to_executable_with_options(ast, output_path, CompilerOptions::default())
+}
+
+/// Compiles the AST into an executable, generating code with the given options
+///
+/// # Returns
+/// The path to the executable
+pub fn compile_to_executable_with_options(
+    ast: Program,
+    output_path: &Path,
+    options: CompilerOptions,
+) -> Result<PathBuf> {
+    let target_triple = get_target_triple(&options)
+        .as_str()
+        .to_string_lossy()
+        .into_owned();
+    let linker = options.linker.clone();
+
     // First create an object file
-    let obj_path = create_object_file(ast, output_path)?;
+    let obj_path = create_object_file_with_options(ast, output_path, options)?;
 
     // Link the object file to create an executable
-    let output = Command::new("gcc")
-        .arg(&obj_path)
-        .arg("-o")
-        .arg(output_path)
-        .output()
-        .map_err(|e| anyhow!("Failed to run linker: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow!("Linker failed: {}", stderr));
-    }
+    linker.link(&obj_path, output_path, &target_triple)?;
 
     // Clean up the object file
     std::fs::remove_file(&obj_path)
         .map_err(|e| anyhow!("Failed to clean up object file: {}", e))?;
 
     // Add the correct extension for the executable
-    #[cfg(windows)]
-    let executable_path = output_path.with_extension("exe");
-    #[cfg(not(windows))]
-    let executable_path = output_path.to_path_buf();
+    let executable_path = if target_triple.contains("windows") {
+        output_path.with_extension("exe")
+    } else {
+        output_path.to_path_buf()
+    };
 
     // Return the path to the executable
     Ok(executable_path)
@@ -41,11 +50,29 @@ pub fn compile_to_executable(ast: Program, output_path: &Path) -> Result<PathBuf
 
 #[cfg(test)]
 mod tests {
-    use crate::output::executable::compile_to_executable;
+    use crate::options::{CompilerOptions, OptimizationLevel};
+    use crate::output::executable::compile_to_executable_with_options;
     use anyhow::{Result, anyhow};
     use std::path::PathBuf;
+    use std::process::Output;
 
     fn compile_and_run(source: &str, output_name: &str) -> Result<String> {
+        let output = compile_and_run_with_options(source, output_name, CompilerOptions::default())?;
+
+        // Return an error if the executable failed
+        if !output.status.success() {
+            return Err(anyhow!("Executable failed with status: {}", output.status));
+        }
+
+        // Return the output
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    fn compile_and_run_with_options(
+        source: &str,
+        output_name: &str,
+        options: CompilerOptions,
+    ) -> Result<Output> {
         let output_path = PathBuf::from(format!("out/{}", output_name));
         let output_path = output_path.as_path();
 
@@ -53,26 +80,25 @@ mod tests {
         std::fs::create_dir_all(output_path.parent().unwrap())
             .map_err(|e| anyhow!("Failed to create parent directory: {}", e))?;
 
-        // Compile the source code to an executable
-        let executable_path = compile_to_executable(
+        // Compile the source code to an executable, with failed checks pointing into the source
+        let options = CompilerOptions {
+            source_name: format!("{output_name}.iv"),
+            source: source.to_string(),
+            ..options
+        };
+
+        let executable_path = compile_to_executable_with_options(
             gneurshk_parser::parse(&mut gneurshk_lexer::lex(source).unwrap()).unwrap(),
             output_path,
+            options,
         )?;
 
         // Run the executable
         let path = std::path::absolute(&executable_path).unwrap();
 
-        let output = std::process::Command::new(&path)
+        std::process::Command::new(&path)
             .output()
-            .map_err(|e| anyhow!("Failed to run executable: {}", e))?;
-
-        // Return an error if the executable failed
-        if !output.status.success() {
-            return Err(anyhow!("Executable failed with status: {}", output.status));
-        }
-
-        // Return the output
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+            .map_err(|e| anyhow!("Failed to run executable: {}", e))
     }
 
     #[test]
@@ -189,4 +215,229 @@ mod tests {
             "Hi 1\r\nHey 1\r\nHey 2\r\nHi 2\r\nHey 1\r\nHey 2"
         );
     }
+
+    #[test]
+    fn closure_allocations() {
+        let source = include_str!("../../tests/closure_allocations.iv");
+        let output = compile_and_run(source, "closure_allocations").unwrap();
+
+        assert_eq!(output.trim(), "4\r\n12 6\r\n4\r\n0");
+    }
+
+    #[test]
+    fn closures_in_loops() {
+        let source = include_str!("../../tests/closures_in_loops.iv");
+        let output = compile_and_run(source, "closures_in_loops").unwrap();
+
+        assert_eq!(output.trim(), "50\r\n11\r\n0");
+    }
+
+    #[test]
+    fn debug_info() {
+        let source = include_str!("../../tests/closure_allocations.iv");
+        let options = CompilerOptions {
+            debug_info: true,
+            ..Default::default()
+        };
+        let output = compile_and_run_with_options(source, "debug_info", options).unwrap();
+
+        assert!(output.status.success());
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout).trim(),
+            "4\r\n12 6\r\n4\r\n0"
+        );
+    }
+
+    #[test]
+    fn optimization_levels() {
+        let source = include_str!("../../tests/fibonacci.iv");
+
+        for optimization in [OptimizationLevel::O3, OptimizationLevel::Oz] {
+            let options = CompilerOptions {
+                optimization,
+                ..Default::default()
+            };
+            let output_name = format!("fibonacci_{optimization:?}");
+            let output = compile_and_run_with_options(source, &output_name, options).unwrap();
+
+            assert!(output.status.success());
+            assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "267914296");
+        }
+    }
+
+    #[test]
+    fn integer_overflow() {
+        let source = include_str!("../../tests/integer_overflow.iv");
+        let output =
+            compile_and_run_with_options(source, "integer_overflow", CompilerOptions::default())
+                .unwrap();
+
+        assert_eq!(output.status.code(), Some(101));
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "Counting");
+        assert_eq!(
+            String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .collect::<Vec<_>>(),
+            vec![
+                "Panic at integer_overflow.iv:4:14: attempt to add with overflow",
+                "    at main (integer_overflow.iv:4:14)",
+            ]
+        );
+    }
+
+    #[test]
+    fn unchecked_integer_overflow() {
+        let source = include_str!("../../tests/integer_overflow.iv");
+        let options = CompilerOptions {
+            safety_checks: false,
+            ..Default::default()
+        };
+        let output =
+            compile_and_run_with_options(source, "unchecked_integer_overflow", options).unwrap();
+
+        assert!(output.status.success());
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout).trim(),
+            "Counting\r\n-2147483596"
+        );
+    }
+
+    #[test]
+    fn unsigned_overflow() {
+        let source = include_str!("../../tests/unsigned_overflow.iv");
+        let output =
+            compile_and_run_with_options(source, "unsigned_overflow", CompilerOptions::default())
+                .unwrap();
+
+        // Unsigned integers only overflow past their own bounds
+        assert_eq!(output.status.code(), Some(101));
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "1");
+        assert_eq!(
+            String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .collect::<Vec<_>>(),
+            vec![
+                "Panic at unsigned_overflow.iv:5:13: attempt to subtract with overflow",
+                "    at main (unsigned_overflow.iv:5:13)",
+            ]
+        );
+    }
+
+    #[test]
+    fn division_by_zero() {
+        let source = include_str!("../../tests/division_by_zero.iv");
+        let output =
+            compile_and_run_with_options(source, "division_by_zero", CompilerOptions::default())
+                .unwrap();
+
+        assert_eq!(output.status.code(), Some(101));
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "5");
+        assert_eq!(
+            String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .collect::<Vec<_>>(),
+            vec![
+                "Panic at division_by_zero.iv:2:5: attempt to divide by zero",
+                "    at divide (division_by_zero.iv:2:5)",
+                "    at main (division_by_zero.iv:7:13)",
+            ]
+        );
+    }
+
+    #[test]
+    fn panic() {
+        let source = include_str!("../../tests/panic.iv");
+        let output =
+            compile_and_run_with_options(source, "panic", CompilerOptions::default()).unwrap();
+
+        assert_eq!(output.status.code(), Some(101));
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "5");
+        assert_eq!(
+            String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .collect::<Vec<_>>(),
+            vec![
+                "Panic: not enough apples",
+                "    at pick (panic.iv:8:5)",
+                "    at main (panic.iv:13:13)",
+            ]
+        );
+    }
+
+    #[test]
+    fn extern_functions() {
+        let source = include_str!("../../tests/extern_functions.iv");
+        let output = compile_and_run(source, "extern_functions").unwrap();
+
+        assert_eq!(
+            output.lines().collect::<Vec<_>>(),
+            vec!["Hello from C", "7"]
+        );
+    }
+
+    #[test]
+    fn extern_conversions() {
+        let source = include_str!("../../tests/extern_conversions.iv");
+        let output = compile_and_run(source, "extern_conversions").unwrap();
+
+        // Arguments are converted to the C types, and floats are passed to printf as a double
+        assert_eq!(
+            output.lines().collect::<Vec<_>>(),
+            vec!["2.5", "7", "4.000000"]
+        );
+    }
+
+    #[test]
+    fn conflicting_extern_declaration() {
+        let source = include_str!("../../tests/conflicting_extern.iv");
+        let error = compile_and_run(source, "conflicting_extern").unwrap_err();
+
+        assert!(
+            error
+                .to_string()
+                .starts_with("Conflicting declarations of external function 'exit'")
+        );
+    }
+
+    #[test]
+    fn default_and_named_args() {
+        let source = include_str!("../../tests/default_and_named_args.iv");
+        let output = compile_and_run(source, "default_and_named_args").unwrap();
+
+        assert_eq!(
+            output.lines().collect::<Vec<_>>(),
+            vec!["-3", "-4", "6", "27"]
+        );
+    }
+
+    #[test]
+    fn generic_instances() {
+        let source = include_str!("../../tests/generic_instances.iv");
+        let output = compile_and_run(source, "generic_instances").unwrap();
+
+        assert_eq!(output.trim(), "2 4000000000 1.500000");
+    }
+
+    #[test]
+    fn optionals() {
+        let source = include_str!("../../tests/optionals.iv");
+        let output = compile_and_run(source, "optionals").unwrap();
+
+        assert_eq!(
+            output.lines().collect::<Vec<_>>(),
+            vec!["apple 3", "empty", "apple 7", "empty"]
+        );
+    }
+
+    #[test]
+    fn propagated_errors() {
+        let source = include_str!("../../tests/propagated_errors.iv");
+        let output = compile_and_run(source, "propagated_errors").unwrap();
+
+        // The error returns from both calls before they print anything, main still exits normally
+        assert_eq!(
+            output.lines().collect::<Vec<_>>(),
+            vec!["counted 3", "reported 4", "done"]
+        );
+    }
 }
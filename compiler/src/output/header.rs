@@ -0,0 +1,158 @@
+use crate::codegen::Codegen;
+use crate::options::CompilerOptions;
+use anyhow::{Result, anyhow};
+use gneurshk_parser::Program;
+use inkwell::context::Context;
+use inkwell::module::{Linkage, Module};
+use inkwell::types::BasicTypeEnum;
+use inkwell::values::FunctionValue;
+use std::path::{Path, PathBuf};
+
+/// Creates C header files (.h) declaring the exported functions of the AST
+///
+/// # Returns
+/// The path to the header file
+pub fn create_c_header_file(ast: Program, output_path: &Path) -> Result<PathBuf> {
+    create_c_header_file_with_options(ast, output_path, CompilerOptions::default())
+}
+
+/// Creates C header files (.h) declaring the exported functions of the AST, generating code with the given options
+///
+/// # Returns
+/// The path to the header file
+pub fn create_c_header_file_with_options(
+    ast: Program,
+    output_path: &Path,
+    options: CompilerOptions,
+) -> Result<PathBuf> {
+    let context = Context::create();
+    let mut codegen = Codegen::new(&context, "main", options);
+
+    codegen.compile(ast)?;
+
+    write_c_header_file(codegen.get_module(), output_path)
+}
+
+/// Writes the C header file (.h) declaring the exported functions of the compiled module
+///
+/// # Returns
+/// The path to the header file
+pub(crate) fn write_c_header_file(module: &Module, output_path: &Path) -> Result<PathBuf> {
+    let header_path = output_path.with_extension("h");
+    let guard_name = get_guard_name(&header_path);
+    let header = create_c_header(module, &guard_name);
+
+    std::fs::write(&header_path, header)
+        .map_err(|e| anyhow!("Failed to write C header file: {}", e))?;
+
+    // Return the path to the header file
+    Ok(header_path)
+}
+
+fn create_c_header(module: &Module, guard_name: &str) -> String {
+    let mut header = format!(
+        "#ifndef {guard_name}\n#define {guard_name}\n\n#include <stdbool.h>\n#include <stdint.h>\n\n#ifdef __cplusplus\nextern \"C\" {{\n#endif\n\n"
+    );
+
+    // Only exported functions are visible outside of the module, the entry point is left to the executable
+    let exported_functions = module.get_functions().filter(|function| {
+        function.count_basic_blocks() > 0
+            && function.get_linkage() == Linkage::External
+            && function.get_name().to_bytes() != b"main"
+    });
+
+    for function in exported_functions {
+        let name = function.get_name().to_string_lossy();
+
+        match get_c_declaration(function) {
+            Some(declaration) => header.push_str(&format!("{declaration};\n")),
+            None => header.push_str(&format!(
+                "/* {name} uses types that can't be described in C */\n"
+            )),
+        }
+    }
+
+    header.push_str(&format!(
+        "\n#ifdef __cplusplus\n}}\n#endif\n\n#endif /* {guard_name} */\n"
+    ));
+
+    header
+}
+
+/// Gets the C prototype of the function, if all of its types have a C equivalent
+fn get_c_declaration(function: FunctionValue) -> Option<String> {
+    let fn_type = function.get_type();
+
+    let return_type = match fn_type.get_return_type() {
+        Some(return_type) => get_c_type(return_type)?,
+        None => "void",
+    };
+
+    let params = fn_type
+        .get_param_types()
+        .into_iter()
+        .enumerate()
+        .map(|(i, param_type)| {
+            let param_type = BasicTypeEnum::try_from(param_type).ok()?;
+            let param_name = function
+                .get_nth_param(i as u32)
+                .map(|param| param.get_name().to_string_lossy().into_owned())
+                .filter(|name| !name.is_empty())
+                .unwrap_or_else(|| format!("arg{i}"));
+
+            Some(format!("{} {}", get_c_type(param_type)?, param_name))
+        })
+        .collect::<Option<Vec<String>>>()?;
+
+    let params = if params.is_empty() {
+        "void".to_string()
+    } else {
+        params.join(", ")
+    };
+
+    Some(format!(
+        "{} {}({})",
+        return_type,
+        function.get_name().to_string_lossy(),
+        params
+    ))
+}
+
+/// Gets the C type of an LLVM type, structs such as optionals and results don't have one
+fn get_c_type(llvm_type: BasicTypeEnum) -> Option<&'static str> {
+    match llvm_type {
+        BasicTypeEnum::IntType(int_type) => match int_type.get_bit_width() {
+            1 => Some("bool"),
+            8 => Some("int8_t"),
+            16 => Some("int16_t"),
+            32 => Some("int32_t"),
+            64 => Some("int64_t"),
+            _ => None,
+        },
+        BasicTypeEnum::FloatType(float_type) => match float_type.get_bit_width() {
+            32 => Some("float"),
+            64 => Some("double"),
+            _ => None,
+        },
+        // Strings are pointers to null terminated characters
+        BasicTypeEnum::PointerType(_) => Some("const char*"),
+        _ => None,
+    }
+}
+
+/// Gets the name of the include guard from the file name, such as `MATH_H` for `math.h`
+fn get_guard_name(header_path: &Path) -> String {
+    header_path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
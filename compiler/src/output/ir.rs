@@ -1,4 +1,6 @@
 use crate::codegen::Codegen;
+use crate::options::CompilerOptions;
+use crate::output::object::{create_target_machine, optimize_module};
 use anyhow::{Result, anyhow};
 use gneurshk_parser::Program;
 use inkwell::context::Context;
@@ -9,18 +11,56 @@ use std::path::{Path, PathBuf};
 /// # Returns
 /// The path to the LLVM IR file
 pub fn create_llvm_ir_file(ast: Program, output_path: &Path) -> Result<PathBuf> {
+    create_llvm_ir_file_with_options(ast, output_path, CompilerOptions::default())
+}
+
+/// Creates LLVM IR files (.ll) from the AST, generating code with the given options
+///
+/// # Returns
+/// The path to the LLVM IR file
+pub fn create_llvm_ir_file_with_options(
+    ast: Program,
+    output_path: &Path,
+    options: CompilerOptions,
+) -> Result<PathBuf> {
+    write_llvm_ir_file(ast, &output_path.with_extension("ll"), options, false)
+}
+
+/// Creates LLVM IR files (.opt.ll) from the AST after the optimization passes of the options ran,
+/// so they can be compared to the unoptimized IR
+///
+/// # Returns
+/// The path to the LLVM IR file
+pub fn create_optimized_llvm_ir_file(
+    ast: Program,
+    output_path: &Path,
+    options: CompilerOptions,
+) -> Result<PathBuf> {
+    write_llvm_ir_file(ast, &output_path.with_extension("opt.ll"), options, true)
+}
+
+fn write_llvm_ir_file(
+    ast: Program,
+    ir_path: &Path,
+    options: CompilerOptions,
+    optimize: bool,
+) -> Result<PathBuf> {
     let context = Context::create();
-    let mut codegen = Codegen::new(&context, "main");
+    let mut codegen = Codegen::new(&context, "main", options.clone());
 
     codegen.compile(ast)?;
 
-    // Write LLVM IR to file
     let module = codegen.get_module();
+
+    if optimize {
+        optimize_module(module, &create_target_machine(&options)?, &options)?;
+    }
+
+    // Write LLVM IR to file
     let ir = module.print_to_string().to_string();
-    let ir_path = output_path.with_extension("ll");
 
-    std::fs::write(&ir_path, ir).map_err(|e| anyhow!("Failed to write LLVM IR file: {}", e))?;
+    std::fs::write(ir_path, ir).map_err(|e| anyhow!("Failed to write LLVM IR file: {}", e))?;
 
     // Return the path to the LLVM IR file
-    Ok(ir_path)
+    Ok(ir_path.to_path_buf())
 }
@@ -0,0 +1,210 @@
+use crate::codegen::{Codegen, JIT_MAIN};
+use crate::options::CompilerOptions;
+use crate::output::object::{create_target_machine, optimize_module};
+use anyhow::{Result, anyhow};
+use gneurshk_parser::Program;
+use inkwell::context::Context;
+use std::cell::Cell;
+use std::ffi::{c_int, c_void};
+
+/// The signature of the entry point that runs the program's main function
+type JitMainFunction = unsafe extern "C" fn(*mut JumpBuffer, *mut c_int) -> c_int;
+
+/// Holds the registers `setjmp` saves, large enough for the `jmp_buf` of every supported platform
+#[repr(C, align(16))]
+struct JumpBuffer([u64; 64]);
+
+/// Where a program that runs in process returns to when it exits
+struct ExitState {
+    buffer: JumpBuffer,
+    code: c_int,
+}
+
+thread_local! {
+    /// The exit state of the program that is running on this thread
+    static EXIT_STATE: Cell<*mut ExitState> = const { Cell::new(std::ptr::null_mut()) };
+}
+
+unsafe extern "C" {
+    /// Writes the buffered output of a C stream, or of all streams when it is null
+    fn fflush(stream: *mut c_void) -> c_int;
+
+    /// Jumps back to where `setjmp` saved the buffer, making it return the value
+    fn longjmp(buffer: *mut JumpBuffer, value: c_int) -> !;
+}
+
+/// Replaces `exit` for programs that run in process, so panics, failed assertions and
+/// calls to `exit` return to the host instead of ending this process
+///
+/// The program's frames are skipped with `longjmp`, they don't own anything that has to be dropped
+unsafe extern "C" fn exit_program(code: c_int) -> ! {
+    let state = EXIT_STATE.get();
+
+    if state.is_null() {
+        std::process::exit(code);
+    }
+
+    unsafe {
+        (*state).code = code;
+        longjmp(&raw mut (*state).buffer, 1)
+    }
+}
+
+/// Compiles the AST and runs its main function in this process, without linking an executable
+///
+/// # Returns
+/// The exit code returned by the main function
+pub fn run_jit(ast: Program) -> Result<i32> {
+    run_jit_with_options(ast, CompilerOptions::default())
+}
+
+/// Compiles the AST with the given options and runs its main function in this process,
+/// without linking an executable
+///
+/// Functions such as `printf` are resolved from the C library of this process.
+/// A panic or a call to `exit` stops the program and returns its exit code, this process keeps running
+///
+/// # Returns
+/// The exit code returned by the main function, or the code the program exited with
+pub fn run_jit_with_options(ast: Program, options: CompilerOptions) -> Result<i32> {
+    if options.target.is_some() {
+        return Err(anyhow!("Only programs for the host can be run in process"));
+    }
+
+    let context = Context::create();
+    let mut codegen = Codegen::new(&context, "main", options.clone());
+
+    codegen.compile(ast)?;
+
+    let main = codegen
+        .get_module()
+        .get_function("main")
+        .ok_or_else(|| anyhow!("Failed to find main function"))?;
+
+    codegen.add_jit_entry_point(main);
+
+    let module = codegen.get_module();
+
+    optimize_module(module, &create_target_machine(&options)?, &options)?;
+
+    // Compile the module to machine code in memory
+    let execution_engine = module
+        .create_jit_execution_engine(options.optimization.codegen_level())
+        .map_err(|e| anyhow!("Failed to create JIT compiler: {}", e))?;
+
+    // The program exits through the host, which jumps back into the entry point
+    if let Some(exit) = module.get_function("exit") {
+        execution_engine.add_global_mapping(&exit, exit_program as usize);
+    }
+
+    let jit_main = unsafe { execution_engine.get_function::<JitMainFunction>(JIT_MAIN) }
+        .map_err(|e| anyhow!("Failed to find main function: {}", e))?;
+
+    let mut state = ExitState {
+        buffer: JumpBuffer([0; 64]),
+        code: 0,
+    };
+
+    // The state is only valid while the program runs, exits after that end this process again
+    EXIT_STATE.set(&raw mut state);
+
+    let exit_code = unsafe { jit_main.call(&raw mut state.buffer, &raw mut state.code) };
+
+    EXIT_STATE.set(std::ptr::null_mut());
+
+    // The output of printf is buffered until the process exits, which this one doesn't do
+    unsafe {
+        fflush(std::ptr::null_mut());
+    }
+
+    Ok(exit_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PANIC_EXIT_CODE;
+    use crate::output::jit::run_jit;
+    use std::ffi::c_int;
+    use std::fs::File;
+    use std::os::fd::AsRawFd;
+    use std::sync::Mutex;
+
+    unsafe extern "C" {
+        fn dup(fd: c_int) -> c_int;
+        fn dup2(fd: c_int, new_fd: c_int) -> c_int;
+        fn close(fd: c_int) -> c_int;
+    }
+
+    /// Programs write to the streams of this process, so only one of them can be captured at a time
+    static OUTPUT_LOCK: Mutex<()> = Mutex::new(());
+
+    /// Runs the source in process, capturing what it writes to stdout and stderr
+    fn run_and_capture(source: &str, output_name: &str) -> (i32, String, String) {
+        let _lock = OUTPUT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let ast = gneurshk_parser::parse(&mut gneurshk_lexer::lex(source).unwrap()).unwrap();
+
+        std::fs::create_dir_all("out").unwrap();
+
+        let stdout_path = format!("out/{output_name}.stdout");
+        let stderr_path = format!("out/{output_name}.stderr");
+        let stdout_file = File::create(&stdout_path).unwrap();
+        let stderr_file = File::create(&stderr_path).unwrap();
+
+        // Point the streams at the files while the program runs
+        let exit_code = unsafe {
+            let stdout = dup(1);
+            let stderr = dup(2);
+
+            dup2(stdout_file.as_raw_fd(), 1);
+            dup2(stderr_file.as_raw_fd(), 2);
+
+            let exit_code = run_jit(ast);
+
+            dup2(stdout, 1);
+            dup2(stderr, 2);
+            close(stdout);
+            close(stderr);
+
+            exit_code.unwrap()
+        };
+
+        (
+            exit_code,
+            std::fs::read_to_string(stdout_path).unwrap(),
+            std::fs::read_to_string(stderr_path).unwrap(),
+        )
+    }
+
+    #[test]
+    fn run_in_process() {
+        let source = include_str!("../../tests/fibonacci.iv");
+        let (exit_code, _, _) = run_and_capture(source, "jit_fibonacci");
+
+        assert_eq!(exit_code, 0);
+    }
+
+    #[test]
+    fn output_in_process() {
+        let source = include_str!("../../tests/multiple_println_statements.iv");
+        let (exit_code, stdout, stderr) = run_and_capture(source, "jit_println");
+
+        assert_eq!(exit_code, 0);
+        assert_eq!(stdout.lines().collect::<Vec<_>>(), vec!["1", "2", "3"]);
+        assert_eq!(stderr, "");
+    }
+
+    #[test]
+    fn panic_in_process() {
+        let source = include_str!("../../tests/panic.iv");
+        let (exit_code, stdout, stderr) = run_and_capture(source, "jit_panic");
+
+        // The panic returns to the host, which can keep running programs
+        assert_eq!(exit_code as u64, PANIC_EXIT_CODE);
+        assert_eq!(stdout.lines().collect::<Vec<_>>(), vec!["5"]);
+        assert_eq!(stderr.lines().next(), Some("Panic: not enough apples"));
+
+        let (exit_code, _, _) = run_and_capture(source, "jit_panic_again");
+
+        assert_eq!(exit_code as u64, PANIC_EXIT_CODE);
+    }
+}
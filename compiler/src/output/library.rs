@@ -0,0 +1,148 @@
+use crate::codegen::Codegen;
+use crate::options::{CompilerOptions, CrateType};
+use crate::output::header::write_c_header_file;
+use crate::output::object::{get_target_triple, write_module_machine_code};
+use anyhow::{Result, anyhow};
+use gneurshk_parser::Program;
+use inkwell::context::Context;
+use inkwell::targets::FileType;
+use std::path::{Path, PathBuf};
+
+/// Compiles the AST into a static or shared library, depending on the crate type of the options,
+/// along with a C header (.h) declaring its exported functions
+///
+/// # Returns
+/// The path to the library
+pub fn compile_to_library_with_options(
+    ast: Program,
+    output_path: &Path,
+    options: CompilerOptions,
+) -> Result<PathBuf> {
+    let target_triple = get_target_triple(&options)
+        .as_str()
+        .to_string_lossy()
+        .into_owned();
+
+    let context = Context::create();
+    let mut codegen = Codegen::new(&context, "main", options.clone());
+
+    codegen.compile(ast)?;
+
+    // The header describes the same module that is compiled into the library
+    write_c_header_file(codegen.get_module(), output_path)?;
+
+    // First create an object file
+    let obj_path = write_module_machine_code(
+        codegen.get_module(),
+        &output_path.with_extension("o"),
+        &options,
+        FileType::Object,
+    )?;
+
+    let library_path = match options.crate_type {
+        CrateType::Staticlib => {
+            let library_path = output_path.with_extension("a");
+
+            options.linker.archive(&obj_path, &library_path)?;
+
+            library_path
+        }
+        CrateType::Cdylib => {
+            let library_path =
+                output_path.with_extension(get_shared_library_extension(&target_triple));
+
+            options
+                .linker
+                .link_shared(&obj_path, &library_path, &target_triple)?;
+
+            library_path
+        }
+        CrateType::Bin => {
+            return Err(anyhow!("Executables can't be compiled into a library"));
+        }
+    };
+
+    // Clean up the object file
+    std::fs::remove_file(&obj_path)
+        .map_err(|e| anyhow!("Failed to clean up object file: {}", e))?;
+
+    // Return the path to the library
+    Ok(library_path)
+}
+
+fn get_shared_library_extension(target_triple: &str) -> &'static str {
+    if target_triple.contains("windows") {
+        "dll"
+    } else if target_triple.contains("apple") || target_triple.contains("darwin") {
+        "dylib"
+    } else {
+        "so"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::options::{CompilerOptions, CrateType};
+    use crate::output::library::compile_to_library_with_options;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    #[test]
+    fn static_library_with_header() {
+        let source = include_str!("../../tests/library.iv");
+        let output_path = PathBuf::from("out/static_library/library");
+
+        std::fs::create_dir_all(output_path.parent().unwrap()).unwrap();
+
+        let options = CompilerOptions {
+            crate_type: CrateType::Staticlib,
+            ..Default::default()
+        };
+        let library_path = compile_to_library_with_options(
+            gneurshk_parser::parse(&mut gneurshk_lexer::lex(source).unwrap()).unwrap(),
+            &output_path,
+            options,
+        )
+        .unwrap();
+
+        let header = std::fs::read_to_string(output_path.with_extension("h")).unwrap();
+
+        assert!(header.contains("int32_t add(int32_t a, int32_t b);"));
+        assert!(header.contains("int32_t gneurshk_square(int32_t n);"));
+        assert!(
+            header.contains("double pick_weight(double light, double heavy, bool take_heavy);")
+        );
+        assert!(header.contains("bool is_big(int64_t count);"));
+        assert!(!header.contains("identity"));
+
+        // Call the library from C through the generated header,
+        // 4294968296 is only bigger than 1000 if the whole Int64 is passed
+        let program_path = output_path.with_file_name("program.c");
+        let executable_path = output_path.with_file_name("program");
+
+        std::fs::write(
+            &program_path,
+            "#include <stdio.h>\n#include \"library.h\"\n\nint main(void) {\n    printf(\"%d %d %.1f %d\\n\", add(2, 3), gneurshk_square(4), pick_weight(1.5, 2.5, true), is_big(4294968296));\n    return 0;\n}\n",
+        )
+        .unwrap();
+
+        let status = Command::new("gcc")
+            .arg(&program_path)
+            .arg(&library_path)
+            .arg("-o")
+            .arg(&executable_path)
+            .status()
+            .unwrap();
+
+        assert!(status.success());
+
+        let output = Command::new(&executable_path).output().unwrap();
+
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .collect::<Vec<_>>(),
+            vec!["5 16 2.5 1"]
+        );
+    }
+}
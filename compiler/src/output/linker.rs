@@ -0,0 +1,127 @@
+use anyhow::{Result, anyhow};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The program that links object files into an executable
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Linker {
+    #[default]
+    Gcc,
+    Clang,
+    /// LLVM's linker, which is run through clang so the C runtime is still found
+    Lld,
+    /// A linker that takes the same arguments as gcc, such as the gcc of a cross compiler
+    Custom(PathBuf),
+}
+
+impl Linker {
+    /// Gets the linker with the given name, other names are treated as the path to a linker
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "gcc" => Linker::Gcc,
+            "clang" => Linker::Clang,
+            "lld" | "ld.lld" => Linker::Lld,
+            path => Linker::Custom(PathBuf::from(path)),
+        }
+    }
+
+    /// Gets the name of the linker, as it is shown in errors
+    pub fn name(&self) -> String {
+        match self {
+            Linker::Gcc => "gcc".to_string(),
+            Linker::Clang => "clang".to_string(),
+            Linker::Lld => "ld.lld".to_string(),
+            Linker::Custom(path) => path.display().to_string(),
+        }
+    }
+
+    /// Links the object file into an executable for the target triple
+    pub fn link(&self, object_path: &Path, output_path: &Path, target_triple: &str) -> Result<()> {
+        self.run(object_path, output_path, target_triple, &[])
+    }
+
+    /// Links the object file into a shared library for the target triple
+    pub fn link_shared(
+        &self,
+        object_path: &Path,
+        output_path: &Path,
+        target_triple: &str,
+    ) -> Result<()> {
+        self.run(object_path, output_path, target_triple, &["-shared"])
+    }
+
+    /// Bundles the object file into a static library with the archiver that belongs to the linker
+    pub fn archive(&self, object_path: &Path, output_path: &Path) -> Result<()> {
+        let archiver = self.archiver();
+
+        // Archives are added to rather than replaced, so start with an empty one
+        if output_path.exists() {
+            std::fs::remove_file(output_path)
+                .map_err(|e| anyhow!("Failed to remove old static library: {}", e))?;
+        }
+
+        let output = Command::new(&archiver)
+            .arg("rcs")
+            .arg(output_path)
+            .arg(object_path)
+            .output()
+            .map_err(|e| anyhow!("Failed to run archiver '{}': {}", archiver.display(), e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("Archiver failed: {}", stderr));
+        }
+
+        Ok(())
+    }
+
+    /// Gets the archiver for the linker, cross compilers such as `aarch64-linux-gnu-gcc`
+    /// have their own archiver next to them
+    fn archiver(&self) -> PathBuf {
+        match self {
+            Linker::Gcc | Linker::Clang => PathBuf::from("ar"),
+            Linker::Lld => PathBuf::from("llvm-ar"),
+            Linker::Custom(path) => match path.to_str().and_then(|path| path.strip_suffix("gcc")) {
+                Some(prefix) => PathBuf::from(format!("{prefix}ar")),
+                None => PathBuf::from("ar"),
+            },
+        }
+    }
+
+    fn run(
+        &self,
+        object_path: &Path,
+        output_path: &Path,
+        target_triple: &str,
+        args: &[&str],
+    ) -> Result<()> {
+        let mut command = match self {
+            Linker::Gcc => Command::new("gcc"),
+            Linker::Clang | Linker::Lld => {
+                let mut command = Command::new("clang");
+                command.arg(format!("--target={target_triple}"));
+                command
+            }
+            Linker::Custom(path) => Command::new(path),
+        };
+
+        if *self == Linker::Lld {
+            command.arg("-fuse-ld=lld");
+        }
+
+        let output = command
+            .args(args)
+            .arg(object_path)
+            .arg("-o")
+            .arg(output_path)
+            .output()
+            .map_err(|e| anyhow!("Failed to run linker '{}': {}", self.name(), e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("Linker failed: {}", stderr));
+        }
+
+        Ok(())
+    }
+}
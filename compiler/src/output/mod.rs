@@ -1,3 +1,9 @@
+pub mod assembly;
+pub mod bitcode;
 pub mod executable;
+pub mod header;
 pub mod ir;
+pub mod jit;
+pub mod library;
+pub mod linker;
 pub mod object;
@@ -1,11 +1,13 @@
 use crate::codegen::Codegen;
+use crate::options::{CompilerOptions, CrateType};
 use anyhow::{Result, anyhow};
 use gneurshk_parser::Program;
-use inkwell::OptimizationLevel;
 use inkwell::context::Context;
-#[cfg(windows)]
-use inkwell::targets::TargetTriple;
-use inkwell::targets::{CodeModel, FileType, InitializationConfig, RelocMode, Target};
+use inkwell::module::Module;
+use inkwell::passes::PassBuilderOptions;
+use inkwell::targets::{
+    CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine, TargetTriple,
+};
 use std::path::{Path, PathBuf};
 
 /// Creates object files (.o) from the AST
@@ -13,56 +15,178 @@ use std::path::{Path, PathBuf};
 /// # Returns
 /// The path to the object file
 pub fn create_object_file(ast: Program, output_path: &Path) -> Result<PathBuf> {
+    create_object_file_with_options(ast, output_path, CompilerOptions::default())
+}
+
+/// Creates object files (.o) from the AST, generating code with the given options
+///
+/// # Returns
+/// The path to the object file
+pub fn create_object_file_with_options(
+    ast: Program,
+    output_path: &Path,
+    options: CompilerOptions,
+) -> Result<PathBuf> {
+    write_machine_code_file(
+        ast,
+        &output_path.with_extension("o"),
+        options,
+        FileType::Object,
+    )
+}
+
+/// Compiles and optimizes the AST, then writes the machine code as an object or assembly file
+///
+/// # Returns
+/// The path to the written file
+pub(crate) fn write_machine_code_file(
+    ast: Program,
+    file_path: &Path,
+    options: CompilerOptions,
+    file_type: FileType,
+) -> Result<PathBuf> {
     let context = Context::create();
-    let mut codegen = Codegen::new(&context, "main");
+    let mut codegen = Codegen::new(&context, "main", options.clone());
 
     codegen.compile(ast)?;
 
+    write_module_machine_code(codegen.get_module(), file_path, &options, file_type)
+}
+
+/// Optimizes the compiled module, then writes its machine code as an object or assembly file
+///
+/// # Returns
+/// The path to the written file
+pub(crate) fn write_module_machine_code(
+    module: &Module,
+    file_path: &Path,
+    options: &CompilerOptions,
+    file_type: FileType,
+) -> Result<PathBuf> {
+    // Optimize the module for the target machine
+    let target_machine = create_target_machine(options)?;
+
+    optimize_module(module, &target_machine, options)?;
+
+    // Write the machine code
+    target_machine
+        .write_to_file(module, file_type, file_path)
+        .map_err(|e| anyhow!("Failed to write {}: {}", file_path.display(), e))?;
+
+    Ok(file_path.to_path_buf())
+}
+
+/// Creates the target machine for the target of the options, which generates code at their optimization level
+pub(crate) fn create_target_machine(options: &CompilerOptions) -> Result<TargetMachine> {
     // Initialize LLVM targets
     Target::initialize_all(&InitializationConfig::default());
 
     // Get the appropriate target triple
-    let target_triple = get_host_target_triple();
+    let target_triple = get_target_triple(options);
     let target = Target::from_triple(&target_triple)
         .map_err(|e| anyhow!("Failed to create target: {}", e))?;
 
+    // The native CPU also enables the features of the host, unless they are given
+    let (cpu, features) = if options.target_cpu == "native" {
+        let features = match options.target_features.as_str() {
+            "" => TargetMachine::get_host_cpu_features().to_string(),
+            features => features.to_string(),
+        };
+
+        (TargetMachine::get_host_cpu_name().to_string(), features)
+    } else {
+        (options.target_cpu.clone(), options.target_features.clone())
+    };
+
     // Create target machine
-    let target_machine = target
+    target
         .create_target_machine(
             &target_triple,
-            "generic",
-            "",
-            OptimizationLevel::Default,
-            RelocMode::Default,
+            &cpu,
+            &features,
+            options.optimization.codegen_level(),
+            get_reloc_mode(options),
             CodeModel::Default,
         )
-        .ok_or(anyhow!("Failed to create target machine"))?;
+        .ok_or(anyhow!(
+            "Failed to create target machine for {}",
+            target_triple.as_str().to_string_lossy()
+        ))
+}
 
-    // Write object file
-    let module = codegen.get_module();
-    let obj_path = output_path.with_extension("o");
+/// Libraries are linked into other programs, so their code has to work at any address
+fn get_reloc_mode(options: &CompilerOptions) -> RelocMode {
+    match options.crate_type {
+        CrateType::Bin => RelocMode::Default,
+        CrateType::Staticlib | CrateType::Cdylib => RelocMode::PIC,
+    }
+}
 
-    target_machine
-        .write_to_file(module, FileType::Object, &obj_path)
-        .map_err(|e| anyhow!("Failed to write object file: {}", e))?;
+/// Runs the pass pipeline of the optimization level on the module
+pub(crate) fn optimize_module(
+    module: &Module,
+    target_machine: &TargetMachine,
+    options: &CompilerOptions,
+) -> Result<()> {
+    module
+        .run_passes(
+            options.optimization.pass_pipeline(),
+            target_machine,
+            PassBuilderOptions::create(),
+        )
+        .map_err(|e| anyhow!("Failed to optimize module: {}", e))
+}
 
-    // Return the path to the object file
-    Ok(obj_path)
+/// Gets the target triple of the options, defaulting to the machine the compiler runs on
+pub(crate) fn get_target_triple(options: &CompilerOptions) -> TargetTriple {
+    match &options.target {
+        Some(target) => TargetTriple::create(target),
+        None => get_host_target_triple(),
+    }
 }
 
 fn get_host_target_triple() -> TargetTriple {
-    use std::env::consts::{ARCH, OS};
+    // Executables are linked with MinGW on Windows, which expects the GNU environment instead of MSVC
+    if cfg!(windows) {
+        return TargetTriple::create("x86_64-pc-windows-gnu");
+    }
 
-    // NOTE: ideally I should be using `target_lexicon::HOST` but i've hardcoded using gcc, so we can't do that yet
+    TargetMachine::get_default_triple()
+}
 
-    let triple = match (OS, ARCH) {
-        ("windows", "x86_64") => "x86_64-pc-windows-gnu",
-        // TODO: test theses other platforms
-        ("linux", "x86_64") => "x86_64-unknown-linux-gnu",
-        ("macos", "x86_64") => "x86_64-apple-darwin",
-        ("macos", "aarch64") => "aarch64-apple-darwin",
-        (os, arch) => panic!("Unsupported host platform: {os}-{arch}"),
-    };
+#[cfg(test)]
+mod tests {
+    use crate::options::CompilerOptions;
+    use crate::output::object::create_object_file_with_options;
+    use std::path::PathBuf;
+
+    /// The machine of an ELF file is stored after the identification and the file type
+    const ELF_MACHINE_OFFSET: usize = 18;
+    const ELF_MACHINE_AARCH64: u16 = 0xB7;
+
+    #[test]
+    fn cross_compile_object() {
+        let source = include_str!("../../tests/hello_world.iv");
+        let output_path = PathBuf::from("out/cross_compile_object");
+
+        std::fs::create_dir_all(output_path.parent().unwrap()).unwrap();
+
+        let options = CompilerOptions {
+            target: Some("aarch64-unknown-linux-gnu".to_string()),
+            ..Default::default()
+        };
+        let object_path = create_object_file_with_options(
+            gneurshk_parser::parse(&mut gneurshk_lexer::lex(source).unwrap()).unwrap(),
+            &output_path,
+            options,
+        )
+        .unwrap();
+
+        let object = std::fs::read(object_path).unwrap();
+        let machine =
+            u16::from_le_bytes([object[ELF_MACHINE_OFFSET], object[ELF_MACHINE_OFFSET + 1]]);
 
-    TargetTriple::create(triple)
+        assert_eq!(&object[..4], b"\x7fELF");
+        assert_eq!(machine, ELF_MACHINE_AARCH64);
+    }
 }
@@ -28,6 +28,8 @@ pub enum Token {
 
     #[token(".")]
     Dot,
+    #[token("...")]
+    Ellipsis,
     #[token("::")]
     DoubleColon,
     #[token("->")]
@@ -36,6 +38,8 @@ pub enum Token {
     Colon,
     #[token(",")]
     Comma,
+    #[token("?")]
+    QuestionMark,
 
     // Assignment operators
     #[token("=")]
@@ -105,6 +109,8 @@ pub enum Token {
     Const,
     #[token("if")]
     If,
+    #[token("let")]
+    Let,
     #[token("else")]
     Else,
     #[token("loop")]
@@ -133,6 +139,8 @@ pub enum Token {
     Return,
     #[token("mut")]
     Mut,
+    #[token("none")]
+    None,
 
     #[regex(r"@[a-zA-Z_][a-zA-Z0-9_]*", annotation, priority = 1)]
     Annotation(String),
@@ -51,7 +51,8 @@ pub fn parse_assignment(tokens: &mut TokenStream) -> Result<Stmt> {
 mod tests {
     use crate::{
         Assignment, BinaryExpression, BinaryOperator, Block, Expression, FunctionDeclaration,
-        Identifier, IntegerLit, MemberExpressionBase, Program, Stmt, parse,
+        Identifier, IntegerLit, MemberAccess, MemberExpressionBase, MemberExpressionMember,
+        Program, Stmt, parse,
     };
     use gneurshk_lexer::lex;
 
@@ -74,12 +75,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Assignment(Assignment {
                             member: MemberExpressionBase::Identifier(Identifier {
                                 name: "a".to_string(),
@@ -90,7 +95,7 @@ mod tests {
                                 span: 22..23
                             })
                         })],
-                    }),
+                    })),
                 }],
             }
         )
@@ -105,12 +110,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Assignment(Assignment {
                             member: MemberExpressionBase::Identifier(Identifier {
                                 name: "a".to_string(),
@@ -135,7 +144,7 @@ mod tests {
                                 operator: BinaryOperator::Add
                             })
                         })],
-                    }),
+                    })),
                 }],
             }
         )
@@ -150,12 +159,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Assignment(Assignment {
                             member: MemberExpressionBase::Identifier(Identifier {
                                 name: "b".to_string(),
@@ -173,7 +186,7 @@ mod tests {
                                 operator: BinaryOperator::Subtract
                             })
                         })],
-                    }),
+                    })),
                 }],
             }
         )
@@ -188,12 +201,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Assignment(Assignment {
                             member: MemberExpressionBase::Identifier(Identifier {
                                 name: "c".to_string(),
@@ -211,7 +228,7 @@ mod tests {
                                 operator: BinaryOperator::Multiply
                             })
                         })],
-                    }),
+                    })),
                 }],
             }
         )
@@ -226,12 +243,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Assignment(Assignment {
                             member: MemberExpressionBase::Identifier(Identifier {
                                 name: "d".to_string(),
@@ -249,7 +270,7 @@ mod tests {
                                 operator: BinaryOperator::Divide
                             })
                         })],
-                    }),
+                    })),
                 }],
             }
         )
@@ -264,12 +285,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Assignment(Assignment {
                             member: MemberExpressionBase::Identifier(Identifier {
                                 name: "e".to_string(),
@@ -287,7 +312,49 @@ mod tests {
                                 operator: BinaryOperator::Modulus
                             })
                         })],
-                    }),
+                    })),
+                }],
+            }
+        )
+    }
+
+    #[test]
+    fn member_assignment() {
+        let source = include_str!("../tests/assignments/member_assignment.iv");
+        let stmt = lex_then_parse(source);
+
+        assert_eq!(
+            stmt,
+            Program {
+                imports: vec![],
+                type_aliases: vec![],
+                functions: vec![FunctionDeclaration {
+                    annotations: vec![],
+                    name: "main".to_string(),
+                    span: 5..9,
+                    params: vec![],
+                    return_type: None,
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
+                        body: vec![Stmt::Assignment(Assignment {
+                            member: MemberExpressionBase::MemberAccess(MemberAccess {
+                                base: Box::new(MemberExpressionBase::Identifier(Identifier {
+                                    name: "a".to_string(),
+                                    span: 18..19
+                                })),
+                                member: MemberExpressionMember::Identifier(Identifier {
+                                    name: "b".to_string(),
+                                    span: 20..21
+                                }),
+                                is_static: false
+                            }),
+                            value: Expression::Integer(IntegerLit {
+                                value: 2,
+                                span: 24..25
+                            })
+                        })],
+                    })),
                 }],
             }
         )
@@ -64,14 +64,18 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Block(Block { body: vec![] })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -86,19 +90,23 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Block(Block {
                             body: vec![Stmt::Integer(IntegerLit {
                                 value: 1,
                                 span: 20..21
                             })]
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -113,19 +121,23 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Block(Block {
                             body: vec![Stmt::Integer(IntegerLit {
                                 value: 1,
                                 span: 28..29
                             })]
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -140,12 +152,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Block(Block {
                             body: vec![
                                 Stmt::Block(Block {
@@ -164,7 +180,7 @@ mod tests {
                                 })
                             ]
                         })],
-                    }),
+                    })),
                 }],
             }
         );
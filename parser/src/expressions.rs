@@ -1,8 +1,10 @@
 use super::{
-    BinaryExpression, BinaryOperator, BooleanLit, CastExpression, FloatLit, IntegerLit, StringLit,
-    TokenStream, UnaryExpression, UnaryOperator,
+    BinaryExpression, BinaryOperator, BooleanLit, CastExpression, FloatLit, IntegerLit, NoneLit,
+    StringLit, TokenStream, TryExpression, UnaryExpression, UnaryOperator,
+};
+use crate::{
+    Expression, funcs::parse_lambda, identifiers::parse_member_expression_base, types::parse_type,
 };
-use crate::{Expression, identifiers::parse_member_expression_base, types::parse_type};
 use anyhow::{Result, anyhow};
 use gneurshk_lexer::tokens::Token;
 
@@ -122,7 +124,7 @@ fn parse_multiplication_division(tokens: &mut TokenStream) -> Result<Expression>
         tokens.next(); // Consume the operator token
 
         // With the next lowest priority, parse the right operand
-        let right = parse_term(tokens)?;
+        let right = parse_propagation(tokens)?;
         left = Expression::BinaryExpression(BinaryExpression {
             left: Box::new(left),
             right: Box::new(right),
@@ -134,7 +136,7 @@ fn parse_multiplication_division(tokens: &mut TokenStream) -> Result<Expression>
 
 /// Parses casting
 fn parse_cast(tokens: &mut TokenStream) -> Result<Expression> {
-    let mut value = parse_term(tokens)?; // Parse the next priority level first
+    let mut value = parse_propagation(tokens)?; // Parse the next priority level first
 
     // Continuously parse casts on this level until there are no more
     while let Some((Token::As, _)) = tokens.peek() {
@@ -155,6 +157,23 @@ fn parse_cast(tokens: &mut TokenStream) -> Result<Expression> {
     Ok(value)
 }
 
+/// Parses the '?' operator, which passes errors and missing values on to the caller
+fn parse_propagation(tokens: &mut TokenStream) -> Result<Expression> {
+    let mut value = parse_term(tokens)?; // Parse the next priority level first
+
+    // Continuously parse '?' operators until there are no more
+    while let Some((Token::QuestionMark, span)) = tokens.peek().cloned() {
+        tokens.next(); // Consume the '?' token
+
+        value = Expression::Try(TryExpression {
+            value: Box::new(value),
+            span,
+        });
+    }
+
+    Ok(value)
+}
+
 /// Parses literals and parenthesized expressions (highest priority)
 fn parse_term(tokens: &mut TokenStream) -> Result<Expression> {
     match tokens.peek() {
@@ -174,7 +193,7 @@ fn parse_term(tokens: &mut TokenStream) -> Result<Expression> {
             tokens.next(); // Consume the '-' token
 
             // Parse the operand
-            let operand = parse_term(tokens)?;
+            let operand = parse_propagation(tokens)?;
 
             Ok(Expression::UnaryExpression(UnaryExpression {
                 value: Box::new(operand),
@@ -185,7 +204,7 @@ fn parse_term(tokens: &mut TokenStream) -> Result<Expression> {
             tokens.next(); // Consume the 'not' token
 
             // Parse the operand
-            let operand = parse_term(tokens)?;
+            let operand = parse_propagation(tokens)?;
 
             Ok(Expression::UnaryExpression(UnaryExpression {
                 value: Box::new(operand),
@@ -195,7 +214,9 @@ fn parse_term(tokens: &mut TokenStream) -> Result<Expression> {
         Some((Token::Integer(_), _))
         | Some((Token::Float(_), _))
         | Some((Token::Boolean(_), _))
-        | Some((Token::String(_), _)) => parse_literal(tokens),
+        | Some((Token::String(_), _))
+        | Some((Token::None, _)) => parse_literal(tokens),
+        Some((Token::Func, _)) => Ok(Expression::Lambda(parse_lambda(tokens)?)),
         Some((Token::Word(_), _)) => Ok(parse_member_expression_base(tokens)?.into()),
         Some(_) => Err(anyhow!("Unexpected token in expression")),
         None => Err(anyhow!("Unexpected end of tokens in expression")),
@@ -208,6 +229,7 @@ fn parse_literal(tokens: &mut TokenStream) -> Result<Expression> {
         Some((Token::Float(value), span)) => Ok(Expression::Float(FloatLit { value, span })),
         Some((Token::Boolean(value), span)) => Ok(Expression::Boolean(BooleanLit { value, span })),
         Some((Token::String(value), span)) => Ok(Expression::String(StringLit { value, span })),
+        Some((Token::None, span)) => Ok(Expression::None(NoneLit { span })),
         _ => Err(anyhow!("Expected literal")),
     }
 }
@@ -216,9 +238,9 @@ fn parse_literal(tokens: &mut TokenStream) -> Result<Expression> {
 mod tests {
     use super::*;
     use crate::{
-        BinaryOperator, Block, CastExpression, DataType, Expression, FunctionDeclaration,
-        Identifier, IntegerLit, MemberAccess, MemberExpressionBase, MemberExpressionMember,
-        Program, Stmt, UnaryOperator, VariableDeclaration, parse,
+        BinaryOperator, Block, CastExpression, DataType, Expression, FunctionCall,
+        FunctionDeclaration, Identifier, IntegerLit, MemberAccess, MemberExpressionBase,
+        MemberExpressionMember, Program, Stmt, UnaryOperator, VariableDeclaration, parse,
     };
     use gneurshk_lexer::lex;
 
@@ -241,12 +263,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![
                             Stmt::Integer(IntegerLit {
                                 value: 1,
@@ -295,7 +321,7 @@ mod tests {
                                 span: 41..43
                             }),
                         ],
-                    }),
+                    })),
                 }],
             }
         );
@@ -310,17 +336,21 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Integer(IntegerLit {
                             value: 42,
                             span: 18..20
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -335,12 +365,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::BinaryExpression(BinaryExpression {
                             left: Box::new(Expression::Integer(IntegerLit {
                                 value: 1,
@@ -375,7 +409,7 @@ mod tests {
                             })),
                             operator: BinaryOperator::Add
                         })],
-                    }),
+                    })),
                 }],
             }
         )
@@ -390,12 +424,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::BinaryExpression(BinaryExpression {
                             left: Box::new(Expression::BinaryExpression(BinaryExpression {
                                 left: Box::new(Expression::BinaryExpression(BinaryExpression {
@@ -435,7 +473,7 @@ mod tests {
                             })),
                             operator: BinaryOperator::Or,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -450,12 +488,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::BinaryExpression(BinaryExpression {
                             left: Box::new(Expression::BinaryExpression(BinaryExpression {
                                 left: Box::new(Expression::Integer(IntegerLit {
@@ -495,7 +537,7 @@ mod tests {
                             })),
                             operator: BinaryOperator::Or,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -510,12 +552,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::BinaryExpression(BinaryExpression {
                             left: Box::new(Expression::BinaryExpression(BinaryExpression {
                                 left: Box::new(Expression::BinaryExpression(BinaryExpression {
@@ -569,7 +615,7 @@ mod tests {
                             })),
                             operator: BinaryOperator::Or,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -584,12 +630,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::BinaryExpression(BinaryExpression {
                             left: Box::new(Expression::BinaryExpression(BinaryExpression {
                                 left: Box::new(Expression::BinaryExpression(BinaryExpression {
@@ -647,7 +697,7 @@ mod tests {
                             })),
                             operator: BinaryOperator::Or,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -662,12 +712,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::UnaryExpression(UnaryExpression {
                             value: Box::new(Expression::Integer(IntegerLit {
                                 value: 1,
@@ -675,7 +729,7 @@ mod tests {
                             })),
                             operator: UnaryOperator::Negative,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -690,12 +744,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::UnaryExpression(UnaryExpression {
                             value: Box::new(Expression::BinaryExpression(BinaryExpression {
                                 left: Box::new(Expression::Integer(IntegerLit {
@@ -710,7 +768,7 @@ mod tests {
                             })),
                             operator: UnaryOperator::Negative,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -725,12 +783,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::UnaryExpression(UnaryExpression {
                             value: Box::new(Expression::BinaryExpression(BinaryExpression {
                                 left: Box::new(Expression::Integer(IntegerLit {
@@ -745,7 +807,7 @@ mod tests {
                             })),
                             operator: UnaryOperator::Not,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -760,17 +822,21 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Float(FloatLit {
                             value: 1.0,
                             span: 18..21
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -785,12 +851,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::BinaryExpression(BinaryExpression {
                             left: Box::new(Expression::Integer(IntegerLit {
                                 value: 1,
@@ -802,7 +872,7 @@ mod tests {
                             })),
                             operator: BinaryOperator::Add,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -817,12 +887,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::BinaryExpression(BinaryExpression {
                             left: Box::new(Expression::Float(FloatLit {
                                 value: 1.0,
@@ -834,7 +908,7 @@ mod tests {
                             })),
                             operator: BinaryOperator::Add,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -849,17 +923,21 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::String(StringLit {
                             value: "i love you".to_string(),
                             span: 18..30,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -874,12 +952,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Cast(CastExpression {
                             value: Box::new(Expression::Integer(IntegerLit {
                                 value: 5,
@@ -887,7 +969,7 @@ mod tests {
                             })),
                             data_type: DataType::Float32,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -902,12 +984,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::BinaryExpression(BinaryExpression {
                             left: Box::new(Expression::Integer(IntegerLit {
                                 value: 1,
@@ -922,7 +1008,7 @@ mod tests {
                             })),
                             operator: BinaryOperator::Add,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -937,12 +1023,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Cast(CastExpression {
                             value: Box::new(Expression::BinaryExpression(BinaryExpression {
                                 left: Box::new(Expression::Integer(IntegerLit {
@@ -957,7 +1047,7 @@ mod tests {
                             })),
                             data_type: DataType::Float32,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -972,12 +1062,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::BinaryExpression(BinaryExpression {
                             left: Box::new(Expression::Cast(CastExpression {
                                 value: Box::new(Expression::Integer(IntegerLit {
@@ -992,7 +1086,7 @@ mod tests {
                             })),
                             operator: BinaryOperator::Multiply,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -1007,14 +1101,19 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::VariableDeclaration(VariableDeclaration::Mutable {
                             name: "x".to_string(),
+                            span: 22..23,
                             data_type: None,
                             value: Some(Expression::Cast(CastExpression {
                                 value: Box::new(Expression::Integer(IntegerLit {
@@ -1024,7 +1123,7 @@ mod tests {
                                 data_type: DataType::Float32,
                             })),
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -1039,12 +1138,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Cast(CastExpression {
                             value: Box::new(Expression::Integer(IntegerLit {
                                 value: 5,
@@ -1052,7 +1155,7 @@ mod tests {
                             })),
                             data_type: DataType::Custom("CustomType".to_string()),
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -1067,12 +1170,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Cast(CastExpression {
                             value: Box::new(Expression::Identifier(Identifier {
                                 name: "foo".to_string(),
@@ -1080,7 +1187,7 @@ mod tests {
                             })),
                             data_type: DataType::Int32,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -1102,12 +1209,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Cast(CastExpression {
                             value: Box::new(Expression::MemberAccess(MemberAccess {
                                 base: Box::new(MemberExpressionBase::Identifier(Identifier {
@@ -1122,9 +1233,60 @@ mod tests {
                             })),
                             data_type: DataType::Int32,
                         })],
+                    })),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn propagation() {
+        let source = include_str!("../tests/expressions/propagation.iv");
+        let stmt = lex_then_parse(source);
+
+        assert_eq!(
+            stmt,
+            Program {
+                imports: vec![],
+                type_aliases: vec![],
+                functions: vec![FunctionDeclaration {
+                    annotations: vec![],
+                    name: "read".to_string(),
+                    span: 5..9,
+                    params: vec![],
+                    return_type: Some(DataType::Result {
+                        value: Box::new(DataType::Int32),
+                        error: Box::new(DataType::String),
                     }),
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
+                        body: vec![Stmt::BinaryExpression(BinaryExpression {
+                            left: Box::new(Expression::UnaryExpression(UnaryExpression {
+                                value: Box::new(Expression::Try(TryExpression {
+                                    value: Box::new(Expression::FunctionCall(FunctionCall {
+                                        name: "parse".to_string(),
+                                        args: vec![],
+                                        named_args: vec![],
+                                        span: 44..51
+                                    })),
+                                    span: 51..52
+                                })),
+                                operator: UnaryOperator::Negative,
+                            })),
+                            right: Box::new(Expression::Integer(IntegerLit {
+                                value: 2,
+                                span: 55..56
+                            })),
+                            operator: BinaryOperator::Multiply,
+                        })],
+                    })),
                 }],
             }
         );
+        assert_eq!(
+            stmt.functions[0].return_type.as_ref().unwrap().to_string(),
+            "Result[Int32, String]"
+        );
     }
 }
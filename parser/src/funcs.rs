@@ -1,7 +1,7 @@
 use super::{TokenStream, expressions::parse_expression};
 use crate::{
-    Annotation, FunctionDeclaration, FunctionParam, block::parse_block, consume_all_newlines,
-    types::parse_type,
+    Annotation, FunctionDeclaration, FunctionParam, Lambda, block::parse_block,
+    consume_all_newlines, types::parse_type,
 };
 use anyhow::{Result, anyhow};
 use gneurshk_lexer::tokens::Token;
@@ -63,21 +63,108 @@ pub fn parse_func_declaration(tokens: &mut TokenStream) -> Result<FunctionDeclar
     consume_all_newlines(tokens);
 
     // Read the function name
-    let name = match tokens.next() {
-        Some((Token::Word(name), _)) => name,
+    let (name, span) = match tokens.next() {
+        Some((Token::Word(name), span)) => (name, span),
         _ => return Err(anyhow!("Expected the function name")),
     };
 
     // Consume all new line tokens
     consume_all_newlines(tokens);
 
+    // Read the type parameters
+    let mut type_params = vec![];
+
+    if let Some((Token::OpenBracket, _)) = tokens.peek() {
+        tokens.next(); // Consume the opening bracket
+
+        loop {
+            // Consume all new line tokens
+            consume_all_newlines(tokens);
+
+            match tokens.next() {
+                Some((Token::Word(type_param), _)) => type_params.push(type_param),
+                _ => return Err(anyhow!("Expected a type parameter name")),
+            }
+
+            // Consume all new line tokens
+            consume_all_newlines(tokens);
+
+            // Check for comma or closing bracket
+            match tokens.next() {
+                Some((Token::Comma, _)) => {}
+                Some((Token::CloseBracket, _)) => break,
+                _ => {
+                    return Err(anyhow!(
+                        "Expected a comma or closing bracket after the type parameter"
+                    ));
+                }
+            }
+        }
+
+        // Consume all new line tokens
+        consume_all_newlines(tokens);
+    }
+
     // Read the parameters
+    let (parameters, variadic) = parse_parameters(tokens)?;
+
+    // Consume all new line tokens
+    consume_all_newlines(tokens);
+
+    // Parse the return type
+    let return_type = match tokens.peek() {
+        // Functions without a body can be followed by another declaration
+        Some((
+            Token::OpenBrace | Token::Func | Token::Annotation(_) | Token::Import | Token::Type,
+            _,
+        ))
+        | None => None,
+        Some((Token::Arrow, _)) => {
+            tokens.next(); // Consume the Arrow token
+
+            // Consume all new line tokens
+            consume_all_newlines(tokens);
+
+            // Read the type
+            parse_type(tokens)?
+        }
+        _ => {
+            return Err(anyhow!(
+                "Missing a colon after the function name or a return type"
+            ));
+        }
+    };
+
+    // Consume all new line tokens
+    consume_all_newlines(tokens);
+
+    // Parse the body of the function if it has one
+    let block = match tokens.peek() {
+        Some((Token::OpenBrace, _)) => Some(Box::new(parse_block(tokens)?)),
+        _ => None,
+    };
+
+    Ok(FunctionDeclaration {
+        annotations,
+        name: name.to_string(),
+        span,
+        type_params,
+        params: parameters,
+        return_type,
+        variadic,
+        block,
+    })
+}
+
+/// Parses a parenthesized parameter list and whether it ends with the variadic marker
+fn parse_parameters(tokens: &mut TokenStream) -> Result<(Vec<FunctionParam>, bool)> {
     match tokens.next().clone() {
         Some((Token::OpenParen, _)) => {}
         _ => return Err(anyhow!("Expected an opening parenthesis")),
     }
 
     let mut parameters = vec![];
+    let mut variadic = false;
 
     loop {
         match tokens.peek().cloned() {
@@ -89,7 +176,24 @@ pub fn parse_func_declaration(tokens: &mut TokenStream) -> Result<FunctionDeclar
                 tokens.next(); // Consume the token
                 break; // Stop reading parameters
             }
-            Some((Token::Word(name), _)) => {
+            Some((Token::Ellipsis, _)) => {
+                tokens.next(); // Consume the token
+                variadic = true;
+
+                // Consume all new line tokens
+                consume_all_newlines(tokens);
+
+                // The variadic marker must be the last parameter
+                match tokens.next() {
+                    Some((Token::CloseParen, _)) => break,
+                    _ => {
+                        return Err(anyhow!(
+                            "Expected a closing parenthesis after the variadic parameter"
+                        ));
+                    }
+                }
+            }
+            Some((Token::Word(name), span)) => {
                 tokens.next(); // Consume the token
 
                 // Consume all new line tokens
@@ -149,6 +253,7 @@ pub fn parse_func_declaration(tokens: &mut TokenStream) -> Result<FunctionDeclar
                     mutable,
                     data_type,
                     default_value,
+                    span,
                 });
             }
             _ => {
@@ -159,12 +264,26 @@ pub fn parse_func_declaration(tokens: &mut TokenStream) -> Result<FunctionDeclar
         }
     }
 
-    // Consume all new line tokens
-    consume_all_newlines(tokens);
+    Ok((parameters, variadic))
+}
+
+/// Parses an anonymous function, such as `func(x: Int32) -> Int32 { x * 2 }`
+pub fn parse_lambda(tokens: &mut TokenStream) -> Result<Lambda> {
+    // Consume the Func token
+    let span = match tokens.next() {
+        Some((Token::Func, span)) => span,
+        _ => return Err(anyhow!("Expected the 'func' keyword")),
+    };
+
+    // Read the parameters
+    let (params, variadic) = parse_parameters(tokens)?;
+
+    if variadic {
+        return Err(anyhow!("Anonymous functions can't be variadic"));
+    }
 
     // Parse the return type
     let return_type = match tokens.peek() {
-        Some((Token::OpenBrace, _)) => None,
         Some((Token::Arrow, _)) => {
             tokens.next(); // Consume the Arrow token
 
@@ -174,35 +293,27 @@ pub fn parse_func_declaration(tokens: &mut TokenStream) -> Result<FunctionDeclar
             // Read the type
             parse_type(tokens)?
         }
-        _ => {
-            return Err(anyhow!(
-                "Missing a colon after the function name or a return type"
-            ));
-        }
+        _ => None,
     };
 
     // Consume all new line tokens
     consume_all_newlines(tokens);
 
-    // Parse the body of the function
-    let block = parse_block(tokens)?;
-
-    Ok(FunctionDeclaration {
-        annotations,
-        name: name.to_string(),
-        params: parameters,
+    Ok(Lambda {
+        params,
         return_type,
-        block: Box::new(block),
+        block: Box::new(parse_block(tokens)?),
+        span,
     })
 }
 
 #[cfg(test)]
 mod tests {
     use crate::MemberExpressionBase::{self};
-    use crate::types::DataType;
+    use crate::types::{DataType, FunctionType};
     use crate::{
         Annotation, Assignment, BinaryExpression, BinaryOperator, Block, Expression, FloatLit,
-        FunctionDeclaration, FunctionParam, Identifier, IntegerLit, Program, Stmt,
+        FunctionDeclaration, FunctionParam, Identifier, IntegerLit, Lambda, Program, Stmt,
         VariableDeclaration, parse,
     };
     use gneurshk_lexer::lex;
@@ -237,21 +348,26 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "apple".to_string(),
+                    span: 5..10,
                     params: vec![],
                     return_type: Some(DataType::Int32),
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::VariableDeclaration(VariableDeclaration::Mutable {
                             name: "peas".to_string(),
+                            span: 30..34,
                             data_type: None,
                             value: Some(Expression::Integer(IntegerLit {
                                 value: 2,
                                 span: 37..38
                             })),
                         })]
-                    }),
+                    })),
                 }],
             }
         );
@@ -265,21 +381,26 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "pear".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::VariableDeclaration(VariableDeclaration::Constant {
                             name: "cucumbers".to_string(),
+                            span: 22..31,
                             data_type: None,
                             value: Expression::Integer(IntegerLit {
                                 value: 8,
                                 span: 34..35
                             }),
                         })]
-                    }),
+                    })),
                 }],
             }
         );
@@ -293,25 +414,31 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "potato".to_string(),
+                    span: 5..11,
                     params: vec![
                         FunctionParam {
                             name: "a".to_string(),
+                            span: 12..13,
                             mutable: false,
                             data_type: DataType::Int32,
                             default_value: None,
                         },
                         FunctionParam {
                             name: "b".to_string(),
+                            span: 22..23,
                             mutable: false,
                             data_type: DataType::Float32,
                             default_value: None,
                         },
                     ],
                     return_type: None,
-                    block: Box::new(Block { body: vec![] }),
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block { body: vec![] })),
                 }],
             }
         );
@@ -325,12 +452,15 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "vegetable".to_string(),
+                    span: 5..14,
                     params: vec![
                         FunctionParam {
                             name: "a".to_string(),
+                            span: 15..16,
                             mutable: false,
                             data_type: DataType::Int32,
                             default_value: Some(Expression::Integer(IntegerLit {
@@ -340,6 +470,7 @@ mod tests {
                         },
                         FunctionParam {
                             name: "b".to_string(),
+                            span: 29..30,
                             mutable: false,
                             data_type: DataType::Float32,
                             default_value: Some(Expression::Float(FloatLit {
@@ -349,7 +480,9 @@ mod tests {
                         },
                     ],
                     return_type: None,
-                    block: Box::new(Block { body: vec![] }),
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block { body: vec![] })),
                 }],
             }
         );
@@ -363,15 +496,19 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![Annotation {
                         name: "test".to_string(),
                         args: vec![],
                     }],
                     name: "egg".to_string(),
+                    span: 11..14,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block { body: vec![] }),
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block { body: vec![] })),
                 }],
             }
         );
@@ -385,6 +522,7 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![
                         Annotation {
@@ -410,9 +548,12 @@ mod tests {
                         },
                     ],
                     name: "ham".to_string(),
+                    span: 27..30,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block { body: vec![] }),
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block { body: vec![] })),
                 }],
             }
         );
@@ -426,25 +567,31 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "mutable_params".to_string(),
+                    span: 5..19,
                     params: vec![
                         FunctionParam {
                             name: "a".to_string(),
+                            span: 20..21,
                             mutable: true,
                             data_type: DataType::Int32,
                             default_value: None,
                         },
                         FunctionParam {
                             name: "b".to_string(),
+                            span: 34..35,
                             mutable: false,
                             data_type: DataType::Float32,
                             default_value: None,
                         },
                     ],
                     return_type: None,
-                    block: Box::new(Block { body: vec![] }),
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block { body: vec![] })),
                 }],
             }
         );
@@ -459,18 +606,22 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "add".to_string(),
+                    span: 5..8,
                     params: vec![
                         FunctionParam {
                             name: "a".to_string(),
+                            span: 15..16,
                             mutable: true,
                             data_type: DataType::Int32,
                             default_value: None,
                         },
                         FunctionParam {
                             name: "b".to_string(),
+                            span: 51..52,
                             mutable: false,
                             data_type: DataType::Int32,
                             default_value: Some(Expression::Integer(IntegerLit {
@@ -480,7 +631,9 @@ mod tests {
                         },
                     ],
                     return_type: Some(DataType::Int32),
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Assignment(Assignment {
                             value: Expression::BinaryExpression(BinaryExpression {
                                 left: Box::new(Expression::Identifier(Identifier {
@@ -498,7 +651,223 @@ mod tests {
                                 span: 115..116
                             })
                         })],
-                    }),
+                    })),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn func_without_body() {
+        let stmt = lex_then_parse("@extern func puts(s: String) -> Int32\nfunc main() { }");
+
+        assert_eq!(
+            stmt,
+            Program {
+                imports: vec![],
+                type_aliases: vec![],
+                functions: vec![
+                    FunctionDeclaration {
+                        annotations: vec![Annotation {
+                            name: "extern".to_string(),
+                            args: vec![],
+                        }],
+                        name: "puts".to_string(),
+                        span: 13..17,
+                        params: vec![FunctionParam {
+                            name: "s".to_string(),
+                            span: 18..19,
+                            mutable: false,
+                            data_type: DataType::String,
+                            default_value: None,
+                        }],
+                        return_type: Some(DataType::Int32),
+                        variadic: false,
+                        type_params: vec![],
+                        block: None,
+                    },
+                    FunctionDeclaration {
+                        annotations: vec![],
+                        name: "main".to_string(),
+                        span: 43..47,
+                        params: vec![],
+                        return_type: None,
+                        variadic: false,
+                        type_params: vec![],
+                        block: Some(Box::new(Block { body: vec![] })),
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn variadic_func() {
+        let stmt = lex_then_parse("@extern func printf(format: String, ...) -> Int32");
+
+        assert_eq!(
+            stmt,
+            Program {
+                imports: vec![],
+                type_aliases: vec![],
+                functions: vec![FunctionDeclaration {
+                    annotations: vec![Annotation {
+                        name: "extern".to_string(),
+                        args: vec![],
+                    }],
+                    name: "printf".to_string(),
+                    span: 13..19,
+                    params: vec![FunctionParam {
+                        name: "format".to_string(),
+                        span: 20..26,
+                        mutable: false,
+                        data_type: DataType::String,
+                        default_value: None,
+                    }],
+                    return_type: Some(DataType::Int32),
+                    variadic: true,
+                    type_params: vec![],
+                    block: None,
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn generic_func() {
+        let stmt = lex_then_parse("func pick[T, U](a: T, b: U) -> T");
+
+        assert_eq!(
+            stmt,
+            Program {
+                imports: vec![],
+                type_aliases: vec![],
+                functions: vec![FunctionDeclaration {
+                    annotations: vec![],
+                    name: "pick".to_string(),
+                    span: 5..9,
+                    params: vec![
+                        FunctionParam {
+                            name: "a".to_string(),
+                            span: 16..17,
+                            mutable: false,
+                            data_type: DataType::Custom("T".to_string()),
+                            default_value: None,
+                        },
+                        FunctionParam {
+                            name: "b".to_string(),
+                            span: 22..23,
+                            mutable: false,
+                            data_type: DataType::Custom("U".to_string()),
+                            default_value: None,
+                        }
+                    ],
+                    return_type: Some(DataType::Custom("T".to_string())),
+                    variadic: false,
+                    type_params: vec!["T".to_string(), "U".to_string()],
+                    block: None,
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn function_type_param() {
+        let stmt = lex_then_parse("func twice(callback: (Int32, Boolean) -> Int32?, done: ())");
+
+        assert_eq!(
+            stmt,
+            Program {
+                imports: vec![],
+                type_aliases: vec![],
+                functions: vec![FunctionDeclaration {
+                    annotations: vec![],
+                    name: "twice".to_string(),
+                    span: 5..10,
+                    params: vec![
+                        FunctionParam {
+                            name: "callback".to_string(),
+                            span: 11..19,
+                            mutable: false,
+                            data_type: DataType::Function(FunctionType {
+                                params: vec![DataType::Int32, DataType::Boolean],
+                                return_type: Some(Box::new(DataType::Optional(Box::new(
+                                    DataType::Int32
+                                )))),
+                            }),
+                            default_value: None,
+                        },
+                        FunctionParam {
+                            name: "done".to_string(),
+                            span: 49..53,
+                            mutable: false,
+                            data_type: DataType::Function(FunctionType::default()),
+                            default_value: None,
+                        }
+                    ],
+                    return_type: None,
+                    variadic: false,
+                    type_params: vec![],
+                    block: None,
+                }],
+            }
+        );
+
+        assert_eq!(
+            stmt.functions[0].function_type().to_string(),
+            "((Int32, Boolean) -> Int32?, ())"
+        );
+    }
+
+    #[test]
+    fn lambda() {
+        let source = include_str!("../tests/funcs/lambda.iv");
+        let stmt = lex_then_parse(source);
+
+        assert_eq!(
+            stmt,
+            Program {
+                imports: vec![],
+                type_aliases: vec![],
+                functions: vec![FunctionDeclaration {
+                    annotations: vec![],
+                    name: "main".to_string(),
+                    span: 5..9,
+                    params: vec![],
+                    return_type: None,
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
+                        body: vec![Stmt::VariableDeclaration(VariableDeclaration::Constant {
+                            name: "double".to_string(),
+                            span: 24..30,
+                            data_type: None,
+                            value: Expression::Lambda(Lambda {
+                                params: vec![FunctionParam {
+                                    name: "x".to_string(),
+                                    span: 38..39,
+                                    mutable: false,
+                                    data_type: DataType::Int32,
+                                    default_value: None,
+                                }],
+                                return_type: Some(DataType::Int32),
+                                block: Box::new(Block {
+                                    body: vec![Stmt::BinaryExpression(BinaryExpression {
+                                        left: Box::new(Expression::Identifier(Identifier {
+                                            name: "x".to_string(),
+                                            span: 59..60
+                                        })),
+                                        right: Box::new(Expression::Integer(IntegerLit {
+                                            value: 2,
+                                            span: 63..64
+                                        })),
+                                        operator: BinaryOperator::Multiply
+                                    })]
+                                }),
+                                span: 33..37
+                            })
+                        })],
+                    })),
                 }],
             }
         );
@@ -1,6 +1,6 @@
 use crate::{
-    FunctionCall, Identifier, MemberAccess, MemberExpressionBase, MemberExpressionMember,
-    expressions::parse_expression,
+    Expression, FunctionCall, Identifier, MemberAccess, MemberExpressionBase,
+    MemberExpressionMember, NamedArgument, expressions::parse_expression,
 };
 use anyhow::{Result, anyhow};
 use gneurshk_lexer::{TokenStream, tokens::Token};
@@ -17,46 +17,12 @@ pub fn parse_member_expression_base(tokens: &mut TokenStream) -> Result<MemberEx
 
     let mut base = match tokens.peek() {
         Some((Token::OpenParen, _)) => {
-            tokens.next(); // Consume the opening parenthesis
-
-            // Parse the arguments
-            let mut args = Vec::new();
-
-            let close_paren_end: usize;
-
-            if let Some((Token::CloseParen, span)) = tokens.peek() {
-                // Handle empty argument list
-                close_paren_end = span.end;
-
-                tokens.next(); // Consume the closing parenthesis
-            } else {
-                // Otherwise, loop while there are still arguments to parse
-                loop {
-                    let arg = parse_expression(tokens)?;
-                    args.push(arg);
-
-                    match tokens.peek() {
-                        Some((Token::Comma, _)) => {
-                            tokens.next();
-                        }
-                        Some((Token::CloseParen, span)) => {
-                            close_paren_end = span.end;
-
-                            tokens.next(); // Consume the closing parenthesis
-                            break;
-                        }
-                        _ => {
-                            return Err(anyhow!(
-                                "Expected a comma or closing parenthesis in the function call"
-                            ));
-                        }
-                    }
-                }
-            }
+            let (args, named_args, close_paren_end) = parse_arguments(tokens)?;
 
             MemberExpressionBase::FunctionCall(FunctionCall {
                 name,
                 args,
+                named_args,
                 span: word_span.start..close_paren_end,
             })
         }
@@ -90,46 +56,12 @@ pub fn parse_member_expression_base(tokens: &mut TokenStream) -> Result<MemberEx
 
         let member = match tokens.peek() {
             Some((Token::OpenParen, _)) => {
-                tokens.next(); // Consume the opening parenthesis
-
-                // Parse the arguments
-                let mut args = Vec::new();
-
-                let close_paren_end: usize;
-
-                if let Some((Token::CloseParen, span)) = tokens.peek() {
-                    // Handle empty argument list
-                    close_paren_end = span.end;
-
-                    tokens.next(); // Consume the closing parenthesis
-                } else {
-                    // Otherwise, loop while there are still arguments to parse
-                    loop {
-                        let arg = parse_expression(tokens)?;
-                        args.push(arg);
-
-                        match tokens.peek() {
-                            Some((Token::Comma, _)) => {
-                                tokens.next();
-                            }
-                            Some((Token::CloseParen, span)) => {
-                                close_paren_end = span.end;
-
-                                tokens.next(); // Consume the closing parenthesis
-                                break;
-                            }
-                            _ => {
-                                return Err(anyhow!(
-                                    "Expected a comma or closing parenthesis in the function call"
-                                ));
-                            }
-                        }
-                    }
-                }
+                let (args, named_args, close_paren_end) = parse_arguments(tokens)?;
 
                 MemberExpressionMember::FunctionCall(FunctionCall {
                     name: member_name,
                     args,
+                    named_args,
                     span: member_span.start..close_paren_end,
                 })
             }
@@ -150,13 +82,68 @@ pub fn parse_member_expression_base(tokens: &mut TokenStream) -> Result<MemberEx
     Ok(base)
 }
 
+/// Parses the arguments of a function call, returning the positional arguments, the named
+/// arguments and where the closing parenthesis ends
+fn parse_arguments(
+    tokens: &mut TokenStream,
+) -> Result<(Vec<Expression>, Vec<NamedArgument>, usize)> {
+    tokens.next(); // Consume the opening parenthesis
+
+    let mut args = Vec::new();
+    let mut named_args = Vec::new();
+
+    // Handle empty argument list
+    if let Some((Token::CloseParen, span)) = tokens.peek() {
+        let close_paren_end = span.end;
+
+        tokens.next(); // Consume the closing parenthesis
+
+        return Ok((args, named_args, close_paren_end));
+    }
+
+    // Otherwise, loop while there are still arguments to parse
+    loop {
+        let position = args.len() + named_args.len();
+
+        // Check if the argument is named
+        let mut lookahead_tokens = tokens.clone();
+
+        lookahead_tokens.next(); // Consume the word token
+
+        match (tokens.peek().cloned(), lookahead_tokens.peek()) {
+            (Some((Token::Word(name), name_span)), Some((Token::Equal, _))) => {
+                tokens.next(); // Consume the name
+                tokens.next(); // Consume the equal sign
+
+                named_args.push(NamedArgument {
+                    name,
+                    value: parse_expression(tokens)?,
+                    position,
+                    span: name_span,
+                });
+            }
+            _ => args.push(parse_expression(tokens)?),
+        }
+
+        match tokens.next() {
+            Some((Token::Comma, _)) => {}
+            Some((Token::CloseParen, span)) => return Ok((args, named_args, span.end)),
+            _ => {
+                return Err(anyhow!(
+                    "Expected a comma or closing parenthesis in the function call"
+                ));
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::Stmt::{self};
     use crate::{
-        BinaryExpression, BinaryOperator, Block, Expression, FunctionCall, FunctionDeclaration,
-        Identifier, IntegerLit, MemberAccess, MemberExpressionBase, MemberExpressionMember,
-        Program, parse,
+        BinaryExpression, BinaryOperator, Block, Expression, FloatLit, FunctionCall,
+        FunctionDeclaration, Identifier, IntegerLit, MemberAccess, MemberExpressionBase,
+        MemberExpressionMember, NamedArgument, Program, parse,
     };
     use gneurshk_lexer::lex;
 
@@ -179,17 +166,21 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Identifier(Identifier {
                             name: "chicken".to_string(),
                             span: 18..25,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -204,12 +195,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![
                             Stmt::Identifier(Identifier {
                                 name: "chicken".to_string(),
@@ -244,7 +239,7 @@ mod tests {
                                 span: 74..81,
                             }),
                         ],
-                    }),
+                    })),
                 }],
             }
         );
@@ -259,18 +254,23 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::FunctionCall(FunctionCall {
                             name: "foo".to_string(),
                             args: vec![],
+                            named_args: vec![],
                             span: 18..23,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -285,21 +285,26 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::FunctionCall(FunctionCall {
                             name: "bar".to_string(),
                             args: vec![Expression::Integer(IntegerLit {
                                 value: 42,
                                 span: 22..24
                             })],
+                            named_args: vec![],
                             span: 18..25,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -314,12 +319,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::FunctionCall(FunctionCall {
                             name: "baz".to_string(),
                             args: vec![
@@ -336,9 +345,52 @@ mod tests {
                                     span: 28..29
                                 }),
                             ],
+                            named_args: vec![],
                             span: 18..30,
                         })],
-                    }),
+                    })),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn function_call_named_args() {
+        let source = include_str!("../tests/identifiers/function_call_named_args.iv");
+        let stmt = lex_then_parse(source);
+
+        assert_eq!(
+            stmt,
+            Program {
+                imports: vec![],
+                type_aliases: vec![],
+                functions: vec![FunctionDeclaration {
+                    annotations: vec![],
+                    name: "main".to_string(),
+                    span: 5..9,
+                    params: vec![],
+                    return_type: None,
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
+                        body: vec![Stmt::FunctionCall(FunctionCall {
+                            name: "baz".to_string(),
+                            args: vec![Expression::Integer(IntegerLit {
+                                value: 1,
+                                span: 22..23
+                            })],
+                            named_args: vec![NamedArgument {
+                                name: "scale".to_string(),
+                                value: Expression::Float(FloatLit {
+                                    value: 2.0,
+                                    span: 33..36
+                                }),
+                                position: 1,
+                                span: 25..30,
+                            }],
+                            span: 18..37,
+                        })],
+                    })),
                 }],
             }
         );
@@ -353,12 +405,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::FunctionCall(FunctionCall {
                             name: "calculate".to_string(),
                             args: vec![
@@ -394,9 +450,10 @@ mod tests {
                                     operator: BinaryOperator::Multiply,
                                 }),
                             ],
+                            named_args: vec![],
                             span: 18..47,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -411,12 +468,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::MemberAccess(MemberAccess {
                             base: Box::new(MemberExpressionBase::Identifier(Identifier {
                                 name: "foo".to_string(),
@@ -428,7 +489,7 @@ mod tests {
                             }),
                             is_static: false,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -443,12 +504,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::MemberAccess(MemberAccess {
                             base: Box::new(MemberExpressionBase::MemberAccess(MemberAccess {
                                 base: Box::new(MemberExpressionBase::Identifier(Identifier {
@@ -467,7 +532,7 @@ mod tests {
                             }),
                             is_static: false,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -482,12 +547,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::MemberAccess(MemberAccess {
                             base: Box::new(MemberExpressionBase::MemberAccess(MemberAccess {
                                 base: Box::new(MemberExpressionBase::Identifier(Identifier {
@@ -496,6 +565,7 @@ mod tests {
                                 })),
                                 member: MemberExpressionMember::FunctionCall(FunctionCall {
                                     name: "bar".to_string(),
+                                    named_args: vec![],
                                     span: 23..28,
                                     args: vec![]
                                 }),
@@ -507,7 +577,7 @@ mod tests {
                             }),
                             is_static: false,
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -1,5 +1,5 @@
 use super::{TokenStream, expressions::parse_expression};
-use crate::{ElseBranch, IfStatement, block::parse_block, consume_all_newlines};
+use crate::{ElseBranch, Identifier, IfStatement, block::parse_block, consume_all_newlines};
 use anyhow::{Result, anyhow};
 use gneurshk_lexer::tokens::Token;
 
@@ -10,6 +10,26 @@ pub fn parse_if_statement(tokens: &mut TokenStream) -> Result<IfStatement> {
         _ => return Err(anyhow!("Expected if statement")),
     }
 
+    // Check if an optional value is being unwrapped, such as `if let fruit = basket`
+    let binding = match tokens.peek() {
+        Some((Token::Let, _)) => {
+            tokens.next(); // Consume the Let token
+
+            let binding = match tokens.next() {
+                Some((Token::Word(name), span)) => Identifier { name, span },
+                _ => return Err(anyhow!("Expected a name after let")),
+            };
+
+            match tokens.next() {
+                Some((Token::Equal, _)) => {}
+                _ => return Err(anyhow!("Expected '=' after the name in if let")),
+            }
+
+            Some(binding)
+        }
+        _ => None,
+    };
+
     // Parse the condition
     let condition = parse_expression(tokens)?;
 
@@ -39,6 +59,7 @@ pub fn parse_if_statement(tokens: &mut TokenStream) -> Result<IfStatement> {
     };
 
     Ok(IfStatement {
+        binding,
         condition: Box::new(condition),
         if_block: Box::new(if_block),
         else_statement: else_block,
@@ -49,7 +70,8 @@ pub fn parse_if_statement(tokens: &mut TokenStream) -> Result<IfStatement> {
 mod tests {
     use crate::{
         BinaryExpression, BinaryOperator, Block, BooleanLit, ElseBranch, Expression,
-        FunctionDeclaration, IfStatement, IntegerLit, Program, Stmt, VariableDeclaration, parse,
+        FunctionDeclaration, Identifier, IfStatement, IntegerLit, NoneLit, Program, Stmt,
+        VariableDeclaration, parse,
     };
     use gneurshk_lexer::lex;
 
@@ -72,14 +94,19 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![
                             Stmt::IfStatement(IfStatement {
+                                binding: None,
                                 condition: Box::new(Expression::BinaryExpression(
                                     BinaryExpression {
                                         left: Box::new(Expression::Integer(IntegerLit {
@@ -96,6 +123,7 @@ mod tests {
                                 if_block: Box::new(Block {
                                     body: vec![
                                         Stmt::IfStatement(IfStatement {
+                                            binding: None,
                                             condition: Box::new(Expression::BinaryExpression(
                                                 BinaryExpression {
                                                     left: Box::new(Expression::Integer(
@@ -117,6 +145,7 @@ mod tests {
                                                 body: vec![Stmt::VariableDeclaration(
                                                     VariableDeclaration::Mutable {
                                                         name: "apple".to_string(),
+                                                        span: 68..73,
                                                         data_type: None,
                                                         value: Some(Expression::Integer(
                                                             IntegerLit {
@@ -130,6 +159,7 @@ mod tests {
                                             else_statement: None,
                                         }),
                                         Stmt::IfStatement(IfStatement {
+                                            binding: None,
                                             condition: Box::new(Expression::BinaryExpression(
                                                 BinaryExpression {
                                                     left: Box::new(Expression::Integer(
@@ -151,6 +181,7 @@ mod tests {
                                                 body: vec![Stmt::VariableDeclaration(
                                                     VariableDeclaration::Mutable {
                                                         name: "green".to_string(),
+                                                        span: 125..130,
                                                         data_type: None,
                                                         value: Some(Expression::Integer(
                                                             IntegerLit {
@@ -169,6 +200,7 @@ mod tests {
                             }),
                             Stmt::VariableDeclaration(VariableDeclaration::Constant {
                                 name: "borg".to_string(),
+                                span: 161..165,
                                 data_type: None,
                                 value: Expression::Integer(IntegerLit {
                                     value: 5,
@@ -176,7 +208,7 @@ mod tests {
                                 }),
                             }),
                         ],
-                    }),
+                    })),
                 }],
             }
         );
@@ -190,13 +222,18 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::IfStatement(IfStatement {
+                            binding: None,
                             condition: Box::new(Expression::BinaryExpression(BinaryExpression {
                                 left: Box::new(Expression::Integer(IntegerLit {
                                     value: 10,
@@ -221,7 +258,7 @@ mod tests {
                                 })]
                             }))),
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -236,13 +273,18 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::IfStatement(IfStatement {
+                            binding: None,
                             condition: Box::new(Expression::BinaryExpression(BinaryExpression {
                                 left: Box::new(Expression::Integer(IntegerLit {
                                     value: 10,
@@ -261,6 +303,7 @@ mod tests {
                                 })]
                             }),
                             else_statement: Some(Box::new(ElseBranch::IfStatement(IfStatement {
+                                binding: None,
                                 condition: Box::new(Expression::BinaryExpression(
                                     BinaryExpression {
                                         left: Box::new(Expression::Integer(IntegerLit {
@@ -283,7 +326,7 @@ mod tests {
                                 else_statement: None,
                             }))),
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -298,13 +341,18 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::IfStatement(IfStatement {
+                            binding: None,
                             condition: Box::new(Expression::BinaryExpression(BinaryExpression {
                                 left: Box::new(Expression::Integer(IntegerLit {
                                     value: 10,
@@ -323,6 +371,7 @@ mod tests {
                                 })]
                             }),
                             else_statement: Some(Box::new(ElseBranch::IfStatement(IfStatement {
+                                binding: None,
                                 condition: Box::new(Expression::BinaryExpression(
                                     BinaryExpression {
                                         left: Box::new(Expression::Integer(IntegerLit {
@@ -350,7 +399,7 @@ mod tests {
                                 }))),
                             }))),
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -365,13 +414,18 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::IfStatement(IfStatement {
+                            binding: None,
                             condition: Box::new(Expression::Boolean(BooleanLit {
                                 value: true,
                                 span: 22..26
@@ -389,7 +443,51 @@ mod tests {
                                 })]
                             }))),
                         })],
-                    }),
+                    })),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn if_let() {
+        let source = include_str!("../tests/ifs/if_let.iv");
+        let stmt = lex_then_parse(source);
+
+        assert_eq!(
+            stmt,
+            Program {
+                imports: vec![],
+                type_aliases: vec![],
+                functions: vec![FunctionDeclaration {
+                    annotations: vec![],
+                    name: "main".to_string(),
+                    span: 5..9,
+                    params: vec![],
+                    return_type: None,
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
+                        body: vec![Stmt::IfStatement(IfStatement {
+                            binding: Some(Identifier {
+                                name: "apple".to_string(),
+                                span: 25..30
+                            }),
+                            condition: Box::new(Expression::Identifier(Identifier {
+                                name: "basket".to_string(),
+                                span: 33..39
+                            })),
+                            if_block: Box::new(Block {
+                                body: vec![Stmt::Identifier(Identifier {
+                                    name: "apple".to_string(),
+                                    span: 50..55
+                                })]
+                            }),
+                            else_statement: Some(Box::new(ElseBranch::Block(Block {
+                                body: vec![Stmt::None(NoneLit { span: 77..81 })]
+                            }))),
+                        })],
+                    })),
                 }],
             }
         );
@@ -154,6 +154,7 @@ mod tests {
                         ("sqrt".to_string(), Some("square_root".to_string())),
                     ],
                 })],
+                type_aliases: vec![],
                 functions: vec![],
             }
         );
@@ -177,6 +178,7 @@ mod tests {
                         modules: vec![("random".to_string(), Some("rng".to_string()))],
                     }),
                 ],
+                type_aliases: vec![],
                 functions: vec![],
             }
         );
@@ -196,6 +198,7 @@ mod tests {
                         ("random".to_string(), Some("rng".to_string())),
                     ],
                 })],
+                type_aliases: vec![],
                 functions: vec![],
             }
         );
@@ -211,6 +214,7 @@ mod tests {
                 imports: vec![ImportStmt::Everything(ImportEverything {
                     module: "math".to_string(),
                 })],
+                type_aliases: vec![],
                 functions: vec![],
             }
         );
@@ -1,11 +1,13 @@
 use crate::assignments::parse_assignment;
 use crate::block::parse_block;
 use crate::expressions::parse_expression;
+use crate::identifiers::parse_member_expression_base;
 use crate::ifs::parse_if_statement;
 use crate::imports::parse_import;
 use crate::loops::{parse_loop, parse_while_loop};
 use crate::returns::parse_return_statement;
-use crate::types::DataType;
+use crate::type_aliases::parse_type_alias;
+use crate::types::{DataType, FunctionType};
 use crate::variables::parse_variable_declaration;
 use anyhow::{Result, anyhow};
 use funcs::parse_func_declaration;
@@ -22,12 +24,15 @@ mod ifs;
 mod imports;
 mod loops;
 mod returns;
+mod spans;
+mod type_aliases;
 pub mod types;
 mod variables;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Program {
     pub imports: Vec<ImportStmt>,
+    pub type_aliases: Vec<TypeAlias>,
     pub functions: Vec<FunctionDeclaration>,
 }
 
@@ -62,6 +67,7 @@ pub struct FunctionParam {
     pub mutable: bool,
     pub data_type: DataType,
     pub default_value: Option<Expression>,
+    pub span: Range<usize>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -75,6 +81,27 @@ pub struct Block {
     pub body: Vec<Stmt>,
 }
 
+impl Block {
+    /// Turns a final `none`, `ok(...)` or `err(...)` into a return statement,
+    /// since their type can only be inferred from the return type of the function
+    pub fn return_untyped_value(&mut self) {
+        let value = match self.body.last() {
+            Some(Stmt::None(none)) => Expression::None(none.clone()),
+            Some(Stmt::FunctionCall(call)) if call.is_result_constructor() => {
+                Expression::FunctionCall(call.clone())
+            }
+            _ => return,
+        };
+
+        if let Some(last) = self.body.last_mut() {
+            *last = Stmt::Return(Return {
+                span: value.span().unwrap_or_default(),
+                value: Some(value),
+            });
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum MemberExpressionMember {
     Identifier(Identifier),
@@ -106,6 +133,43 @@ impl From<MemberExpressionBase> for Expression {
 pub struct FunctionCall {
     pub name: String,
     pub args: Vec<Expression>,
+    pub named_args: Vec<NamedArgument>,
+    pub span: Range<usize>,
+}
+
+impl FunctionCall {
+    /// Checks if the call is one of the built-in `ok` and `err` functions that create a result
+    pub fn is_result_constructor(&self) -> bool {
+        matches!(self.name.as_str(), "ok" | "err")
+    }
+}
+
+impl Expression {
+    /// Checks if the expression only uses literals, so it has the same value wherever it is built
+    pub fn is_constant(&self) -> bool {
+        match self {
+            Expression::Integer(_)
+            | Expression::Float(_)
+            | Expression::Boolean(_)
+            | Expression::String(_)
+            | Expression::None(_) => true,
+            Expression::BinaryExpression(BinaryExpression { left, right, .. }) => {
+                left.is_constant() && right.is_constant()
+            }
+            Expression::UnaryExpression(UnaryExpression { value, .. }) => value.is_constant(),
+            Expression::Cast(cast) => cast.value.is_constant(),
+            _ => false,
+        }
+    }
+}
+
+/// An argument that is passed by the name of the parameter, such as `scale = 2.0`
+#[derive(Debug, PartialEq, Clone)]
+pub struct NamedArgument {
+    pub name: String,
+    pub value: Expression,
+    /// The index of the argument in the call, including the positional arguments
+    pub position: usize,
     pub span: Range<usize>,
 }
 
@@ -132,24 +196,24 @@ pub enum ImportStmt {
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct ImportModule {
-    module: String,
-    alias: Option<String>,
+    pub module: String,
+    pub alias: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct ImportModules {
-    modules: Vec<(String, Option<String>)>,
+    pub modules: Vec<(String, Option<String>)>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct ImportEverything {
-    module: String,
+    pub module: String,
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct ImportCollection {
-    module: String,
-    items: Vec<(String, Option<String>)>,
+    pub module: String,
+    pub items: Vec<(String, Option<String>)>,
 }
 
 /// Represents anything that can come after 'else' in an if statement
@@ -170,10 +234,13 @@ pub enum Expression {
     Float(FloatLit),
     Boolean(BooleanLit),
     String(StringLit),
+    None(NoneLit),
     Identifier(Identifier),
     FunctionCall(FunctionCall),
     MemberAccess(MemberAccess),
     Cast(CastExpression),
+    Try(TryExpression),
+    Lambda(Lambda),
 }
 
 impl From<Expression> for Stmt {
@@ -191,16 +258,21 @@ impl From<Expression> for Stmt {
             Expression::Float(float_lit) => Stmt::Float(float_lit),
             Expression::Boolean(boolean_lit) => Stmt::Boolean(boolean_lit),
             Expression::String(string_lit) => Stmt::String(string_lit),
+            Expression::None(none_lit) => Stmt::None(none_lit),
             Expression::Identifier(identifier) => Stmt::Identifier(identifier),
             Expression::FunctionCall(function_call) => Stmt::FunctionCall(function_call),
             Expression::MemberAccess(member_access) => Stmt::MemberAccess(member_access),
             Expression::Cast(cast) => Stmt::Cast(cast),
+            Expression::Try(try_expression) => Stmt::Try(try_expression),
+            Expression::Lambda(lambda) => Stmt::Lambda(lambda),
         }
     }
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct IfStatement {
+    /// The name that an optional value is unwrapped into, such as `fruit` in `if let fruit = basket`
+    pub binding: Option<Identifier>,
     pub condition: Box<Expression>,
     pub if_block: Box<Block>,
     pub else_statement: Option<Box<ElseBranch>>,
@@ -209,6 +281,8 @@ pub struct IfStatement {
 #[derive(Debug, PartialEq, Clone)]
 pub struct LoopStmt {
     pub block: Box<Block>,
+    /// The location of the 'loop' or 'while' keyword
+    pub span: Range<usize>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -235,6 +309,12 @@ pub struct StringLit {
     pub span: Range<usize>,
 }
 
+/// The missing value of an optional type
+#[derive(Debug, PartialEq, Clone)]
+pub struct NoneLit {
+    pub span: Range<usize>,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct BinaryExpression {
     pub left: Box<Expression>,
@@ -254,6 +334,38 @@ pub struct CastExpression {
     pub data_type: DataType,
 }
 
+/// Unwraps a result or optional, returning its error or none from the function instead, such as `read()?`
+#[derive(Debug, PartialEq, Clone)]
+pub struct TryExpression {
+    pub value: Box<Expression>,
+    /// The location of the '?' operator
+    pub span: Range<usize>,
+}
+
+/// An anonymous function that can use the variables around it, such as `func(x: Int32) -> Int32 { x * 2 }`
+#[derive(Debug, PartialEq, Clone)]
+pub struct Lambda {
+    pub params: Vec<FunctionParam>,
+    pub return_type: Option<DataType>,
+    pub block: Box<Block>,
+    /// The location of the 'func' keyword
+    pub span: Range<usize>,
+}
+
+impl Lambda {
+    /// Gets the type of the function value that the lambda creates
+    pub fn function_type(&self) -> FunctionType {
+        FunctionType {
+            params: self
+                .params
+                .iter()
+                .map(|param| param.data_type.clone())
+                .collect(),
+            return_type: self.return_type.clone().map(Box::new),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Assignment {
     pub member: MemberExpressionBase,
@@ -264,9 +376,41 @@ pub struct Assignment {
 pub struct FunctionDeclaration {
     pub annotations: Vec<Annotation>,
     pub name: String,
+    pub span: Range<usize>,
+    /// The names of the type parameters, such as `T` in `func max[T]`
+    pub type_params: Vec<String>,
     pub params: Vec<FunctionParam>,
     pub return_type: Option<DataType>,
-    pub block: Box<Block>,
+    /// Whether the function accepts any number of extra arguments after its parameters
+    pub variadic: bool,
+    /// The body of the function, which is missing for external functions
+    pub block: Option<Box<Block>>,
+}
+
+impl FunctionDeclaration {
+    /// Finds the first annotation with the given name
+    pub fn get_annotation(&self, name: &str) -> Option<&Annotation> {
+        self.annotations
+            .iter()
+            .find(|annotation| annotation.name == name)
+    }
+
+    /// Checks if the function has an annotation with the given name
+    pub fn has_annotation(&self, name: &str) -> bool {
+        self.get_annotation(name).is_some()
+    }
+
+    /// Gets the type of the function when it is used as a value
+    pub fn function_type(&self) -> FunctionType {
+        FunctionType {
+            params: self
+                .params
+                .iter()
+                .map(|param| param.data_type.clone())
+                .collect(),
+            return_type: self.return_type.clone().map(Box::new),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -275,26 +419,36 @@ pub enum VariableDeclaration {
         name: String,
         data_type: Option<DataType>,
         value: Option<Expression>,
+        span: Range<usize>,
     },
     Constant {
         name: String,
         data_type: Option<DataType>,
         value: Expression,
+        span: Range<usize>,
     },
 }
 
+/// A new name for an existing type, such as `type Meters = Float64`
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypeAlias {
+    pub name: String,
+    /// The aliased type, or every member of a union such as `Int64 | String`
+    pub types: Vec<DataType>,
+    pub span: Range<usize>,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Return {
     pub value: Option<Expression>,
+    /// The location of the 'return' keyword, or of the value for implicit returns
+    pub span: Range<usize>,
 }
 
 /// Anything that can be declared at the top level of a program
 #[derive(Debug, PartialEq, Clone)]
 pub enum Declaration {
-    // TypeAlias {
-    //     name: String,
-    //     types: Vec<String>,
-    // },
+    TypeAlias(TypeAlias),
     Import(ImportStmt),
     Function(FunctionDeclaration),
 }
@@ -313,19 +467,25 @@ pub enum Stmt {
     FunctionCall(FunctionCall),
     MemberAccess(MemberAccess),
     Cast(CastExpression),
+    Try(TryExpression),
+    Lambda(Lambda),
     Integer(IntegerLit),
     Float(FloatLit),
     Boolean(BooleanLit),
     String(StringLit),
+    None(NoneLit),
     Return(Return),
     Loop(LoopStmt),
-    Continue,
-    Break,
+    /// A 'continue' statement and the location of its keyword
+    Continue(Range<usize>),
+    /// A 'break' statement and the location of its keyword
+    Break(Range<usize>),
 }
 
 /// Parses statements that appear directly after an new line and or indentation
 pub fn parse(tokens: &mut TokenStream) -> Result<Program> {
     let mut imports = vec![];
+    let mut type_aliases = vec![];
     let mut functions = vec![];
 
     while let Some((token, _)) = tokens.peek() {
@@ -336,6 +496,9 @@ pub fn parse(tokens: &mut TokenStream) -> Result<Program> {
 
         // Append statements or catch and throw errors
         match parse_declaration(tokens)? {
+            Declaration::TypeAlias(type_alias) => {
+                type_aliases.push(type_alias);
+            }
             Declaration::Import(import) => {
                 imports.push(import);
             }
@@ -345,7 +508,11 @@ pub fn parse(tokens: &mut TokenStream) -> Result<Program> {
         }
     }
 
-    Ok(Program { imports, functions })
+    Ok(Program {
+        imports,
+        type_aliases,
+        functions,
+    })
 }
 
 fn parse_declaration(tokens: &mut TokenStream) -> Result<Declaration> {
@@ -360,6 +527,7 @@ fn parse_declaration(tokens: &mut TokenStream) -> Result<Declaration> {
         Token::Annotation(_) | Token::Func => {
             Declaration::Function(parse_func_declaration(tokens)?)
         }
+        Token::Type => Declaration::TypeAlias(parse_type_alias(tokens)?),
         Token::Import => Declaration::Import(parse_import(tokens)?),
         _ => {
             println!("token: {token:?}");
@@ -376,7 +544,7 @@ fn parse_declaration(tokens: &mut TokenStream) -> Result<Declaration> {
 
 fn parse_statement(tokens: &mut TokenStream) -> Result<Stmt> {
     // Peek at the next token
-    let (token, _) = match tokens.peek() {
+    let (token, span) = match tokens.peek() {
         Some(e) => e,
         _ => return Err(anyhow!("Unexpected end of tokens at beginning of line")),
     };
@@ -389,16 +557,19 @@ fn parse_statement(tokens: &mut TokenStream) -> Result<Stmt> {
         | Token::Float(_)
         | Token::Boolean(_)
         | Token::String(_)
+        | Token::None
+        | Token::Func
         | Token::OpenParen
         | Token::Minus
         | Token::Not => Ok(parse_expression(tokens)?.into()),
         Token::Word(_) => {
             let mut lookahead_tokens = tokens.clone();
 
-            lookahead_tokens.next(); // Consume the word token
+            // Skip over the assignment target, including any member accesses
+            let target = parse_member_expression_base(&mut lookahead_tokens);
 
             // Check if its an assignment
-            match lookahead_tokens.peek() {
+            match lookahead_tokens.peek().filter(|_| target.is_ok()) {
                 Some((Token::Equal, _))
                 | Some((Token::PlusEqual, _))
                 | Some((Token::MinusEqual, _))
@@ -414,14 +585,16 @@ fn parse_statement(tokens: &mut TokenStream) -> Result<Stmt> {
         Token::Loop => Ok(Stmt::Loop(parse_loop(tokens)?)),
         Token::While => Ok(Stmt::Loop(parse_while_loop(tokens)?)),
         Token::Break => {
+            let span = span.clone();
             tokens.next(); // Consume the break token
 
-            Ok(Stmt::Break)
+            Ok(Stmt::Break(span))
         }
         Token::Continue => {
+            let span = span.clone();
             tokens.next(); // Consume the continue token
 
-            Ok(Stmt::Continue)
+            Ok(Stmt::Continue(span))
         }
         _ => {
             println!("token: {token:?}");
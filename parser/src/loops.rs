@@ -7,23 +7,24 @@ use gneurshk_lexer::{TokenStream, tokens::Token};
 
 pub fn parse_loop(tokens: &mut TokenStream) -> Result<LoopStmt> {
     // Consume the Loop token
-    match tokens.next() {
-        Some((Token::Loop, _)) => {}
+    let span = match tokens.next() {
+        Some((Token::Loop, span)) => span,
         _ => return Err(anyhow!("Expected if statement")),
-    }
+    };
 
     // Return a loop statement with the parsed block
     Ok(LoopStmt {
         block: Box::new(parse_block(tokens)?),
+        span,
     })
 }
 
 pub fn parse_while_loop(tokens: &mut TokenStream) -> Result<LoopStmt> {
     // Consume the While token
-    match tokens.next() {
-        Some((Token::While, _)) => {}
+    let span = match tokens.next() {
+        Some((Token::While, span)) => span,
         _ => return Err(anyhow!("Expected if statement")),
-    }
+    };
 
     // Parse the condition expression
     let condition = parse_expression(tokens)?;
@@ -33,12 +34,13 @@ pub fn parse_while_loop(tokens: &mut TokenStream) -> Result<LoopStmt> {
 
     // Create while loop guard
     let guard = Stmt::IfStatement(IfStatement {
+        binding: None,
         condition: Box::new(Expression::UnaryExpression(UnaryExpression {
             value: Box::new(condition),
             operator: UnaryOperator::Not,
         })),
         if_block: Box::new(Block {
-            body: vec![Stmt::Break],
+            body: vec![Stmt::Break(span.clone())],
         }),
         else_statement: None,
     });
@@ -48,6 +50,7 @@ pub fn parse_while_loop(tokens: &mut TokenStream) -> Result<LoopStmt> {
 
     Ok(LoopStmt {
         block: Box::new(Block { body: guarded_body }),
+        span,
     })
 }
 
@@ -78,12 +81,16 @@ mod tests {
             program,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
+                    variadic: false,
+                    type_params: vec![],
                     annotations: vec![],
-                    block: Box::new(Block {
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Loop(LoopStmt {
                             block: Box::new(Block {
                                 body: vec![Stmt::FunctionCall(FunctionCall {
@@ -92,11 +99,13 @@ mod tests {
                                         value: "Hello, world!".to_string(),
                                         span: 41..56
                                     })],
+                                    named_args: vec![],
                                     span: 33..57
                                 })],
                             }),
+                            span: 18..22,
                         })],
-                    })
+                    }))
                 }]
             }
         );
@@ -111,18 +120,23 @@ mod tests {
             program,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
+                    variadic: false,
+                    type_params: vec![],
                     annotations: vec![],
-                    block: Box::new(Block {
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Loop(LoopStmt {
                             block: Box::new(Block {
-                                body: vec![Stmt::Break, Stmt::Continue],
+                                body: vec![Stmt::Break(33..38), Stmt::Continue(47..55)],
                             }),
+                            span: 18..22,
                         })],
-                    })
+                    }))
                 }]
             }
         );
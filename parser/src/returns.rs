@@ -4,27 +4,25 @@ use gneurshk_lexer::{TokenStream, tokens::Token};
 
 pub fn parse_return_statement(tokens: &mut TokenStream) -> Result<Stmt> {
     // Consume the Return token
-    match tokens.next() {
-        Some((Token::Return, _)) => {}
+    let span = match tokens.next() {
+        Some((Token::Return, span)) => span,
         _ => return Err(anyhow!("Expected return statement")),
-    }
+    };
 
-    // Check if the next token is something that can be parsed as an expression
+    // Anything other than the end of the statement is the returned expression
     let value = match tokens.peek() {
-        Some((Token::Integer(_), _)) | Some((Token::OpenParen, _)) | Some((Token::Word(_), _)) => {
-            Some(parse_expression(tokens)?)
-        }
-        _ => None,
+        Some((Token::NewLine, _)) | Some((Token::CloseBrace, _)) | None => None,
+        _ => Some(parse_expression(tokens)?),
     };
 
-    Ok(Stmt::Return(Return { value }))
+    Ok(Stmt::Return(Return { value, span }))
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        BinaryExpression, BinaryOperator, Block, Expression, FunctionDeclaration, IntegerLit,
-        Program, Return, Stmt, parse,
+        BinaryExpression, BinaryOperator, Block, BooleanLit, Expression, FunctionDeclaration,
+        IntegerLit, Program, Return, Stmt, parse,
     };
     use gneurshk_lexer::lex;
 
@@ -47,14 +45,21 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
-                        body: vec![Stmt::Return(Return { value: None })],
-                    }),
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
+                        body: vec![Stmt::Return(Return {
+                            value: None,
+                            span: 18..24
+                        })],
+                    })),
                 }],
             }
         );
@@ -69,19 +74,24 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Return(Return {
                             value: Some(Expression::Integer(IntegerLit {
                                 value: 1,
                                 span: 25..26
-                            }))
+                            })),
+                            span: 18..24
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -96,12 +106,16 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Return(Return {
                             value: Some(Expression::BinaryExpression(BinaryExpression {
                                 left: Box::new(Expression::Integer(IntegerLit {
@@ -113,9 +127,10 @@ mod tests {
                                     span: 29..30
                                 })),
                                 operator: BinaryOperator::Add,
-                            }))
+                            })),
+                            span: 18..24
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -130,16 +145,23 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Block(Block {
-                            body: vec![Stmt::Return(Return { value: None })]
+                            body: vec![Stmt::Return(Return {
+                                value: None,
+                                span: 28..34
+                            })]
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -154,21 +176,58 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::Block(Block {
                             body: vec![Stmt::Return(Return {
                                 value: Some(Expression::Integer(IntegerLit {
                                     value: 1,
                                     span: 27..28
-                                }))
+                                })),
+                                span: 20..26
                             })]
                         })],
-                    }),
+                    })),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn return_boolean() {
+        let source = include_str!("../tests/returns/return_boolean.iv");
+        let stmt = lex_then_parse(source);
+
+        assert_eq!(
+            stmt,
+            Program {
+                imports: vec![],
+                type_aliases: vec![],
+                functions: vec![FunctionDeclaration {
+                    annotations: vec![],
+                    name: "main".to_string(),
+                    span: 5..9,
+                    params: vec![],
+                    return_type: None,
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
+                        body: vec![Stmt::Return(Return {
+                            value: Some(Expression::Boolean(BooleanLit {
+                                value: true,
+                                span: 25..29
+                            })),
+                            span: 18..24
+                        })],
+                    })),
                 }],
             }
         );
@@ -0,0 +1,116 @@
+use crate::{
+    Assignment, BinaryExpression, Block, CastExpression, Expression, FunctionCall, Identifier,
+    IfStatement, MemberAccess, MemberExpressionBase, Return, Stmt, TryExpression, UnaryExpression,
+    VariableDeclaration,
+};
+use std::ops::Range;
+
+impl Stmt {
+    /// Returns the location of the statement in the source code, if it is known
+    pub fn span(&self) -> Option<Range<usize>> {
+        match self {
+            Stmt::Assignment(Assignment { member, .. }) => member.span(),
+            Stmt::BinaryExpression(expr) => expr.span(),
+            Stmt::UnaryExpression(expr) => expr.value.span(),
+            Stmt::Cast(CastExpression { value, .. }) => value.span(),
+            Stmt::Try(expr) => expr.span(),
+            Stmt::Identifier(Identifier { span, .. })
+            | Stmt::FunctionCall(FunctionCall { span, .. }) => Some(span.clone()),
+            Stmt::Integer(literal) => Some(literal.span.clone()),
+            Stmt::Float(literal) => Some(literal.span.clone()),
+            Stmt::Boolean(literal) => Some(literal.span.clone()),
+            Stmt::String(literal) => Some(literal.span.clone()),
+            Stmt::None(literal) => Some(literal.span.clone()),
+            Stmt::Lambda(lambda) => Some(lambda.span.clone()),
+            Stmt::VariableDeclaration(
+                VariableDeclaration::Mutable { span, .. }
+                | VariableDeclaration::Constant { span, .. },
+            ) => Some(span.clone()),
+            Stmt::IfStatement(if_stmt) => if_stmt.span(),
+            Stmt::Block(block) => block.span(),
+            Stmt::Return(Return { span, .. }) | Stmt::Break(span) | Stmt::Continue(span) => {
+                Some(span.clone())
+            }
+            Stmt::Loop(loop_stmt) => Some(loop_stmt.span.clone()),
+            Stmt::MemberAccess(member_access) => member_access.span(),
+        }
+    }
+}
+
+impl Expression {
+    /// Returns the location of the expression in the source code, if it is known
+    pub fn span(&self) -> Option<Range<usize>> {
+        match self {
+            Expression::BinaryExpression(expr) => expr.span(),
+            Expression::UnaryExpression(UnaryExpression { value, .. }) => value.span(),
+            Expression::Cast(CastExpression { value, .. }) => value.span(),
+            Expression::Try(expr) => expr.span(),
+            Expression::Identifier(Identifier { span, .. })
+            | Expression::FunctionCall(FunctionCall { span, .. }) => Some(span.clone()),
+            Expression::Integer(literal) => Some(literal.span.clone()),
+            Expression::Float(literal) => Some(literal.span.clone()),
+            Expression::Boolean(literal) => Some(literal.span.clone()),
+            Expression::String(literal) => Some(literal.span.clone()),
+            Expression::None(literal) => Some(literal.span.clone()),
+            Expression::Lambda(lambda) => Some(lambda.span.clone()),
+            Expression::IfStatement(if_stmt) => if_stmt.span(),
+            Expression::Block(block) => block.span(),
+            Expression::MemberAccess(member_access) => member_access.span(),
+        }
+    }
+}
+
+impl BinaryExpression {
+    /// Returns the location spanning both operands, if it is known
+    pub fn span(&self) -> Option<Range<usize>> {
+        match (self.left.span(), self.right.span()) {
+            (Some(left), Some(right)) => Some(left.start..right.end),
+            (left, right) => left.or(right),
+        }
+    }
+}
+
+impl TryExpression {
+    /// Returns the location from the start of the unwrapped value to the '?' operator
+    pub fn span(&self) -> Option<Range<usize>> {
+        match self.value.span() {
+            Some(value) => Some(value.start..self.span.end),
+            None => Some(self.span.clone()),
+        }
+    }
+}
+
+impl IfStatement {
+    /// Returns the location of the unwrapped binding or the condition
+    pub fn span(&self) -> Option<Range<usize>> {
+        match &self.binding {
+            Some(binding) => Some(binding.span.clone()),
+            None => self.condition.span(),
+        }
+    }
+}
+
+impl Block {
+    /// Returns the location of the first statement in the block that has one
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.body.iter().find_map(Stmt::span)
+    }
+}
+
+impl MemberAccess {
+    /// Returns the location of the value whose member is accessed
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.base.span()
+    }
+}
+
+impl MemberExpressionBase {
+    /// Returns the location of the innermost identifier or call
+    pub fn span(&self) -> Option<Range<usize>> {
+        match self {
+            MemberExpressionBase::Identifier(Identifier { span, .. })
+            | MemberExpressionBase::FunctionCall(FunctionCall { span, .. }) => Some(span.clone()),
+            MemberExpressionBase::MemberAccess(member_access) => member_access.span(),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{TypeAlias, consume_all_newlines, types::parse_type};
+use anyhow::{Result, anyhow};
+use gneurshk_lexer::{TokenStream, tokens::Token};
+
+pub fn parse_type_alias(tokens: &mut TokenStream) -> Result<TypeAlias> {
+    // Consume the Type token
+    match tokens.next() {
+        Some((Token::Type, _)) => {}
+        _ => return Err(anyhow!("Expected the 'type' keyword")),
+    }
+
+    // Read the alias name
+    let (name, span) = match tokens.next() {
+        Some((Token::Word(name), span)) => (name, span),
+        _ => return Err(anyhow!("Expected the type alias name")),
+    };
+
+    // Consume the Equal token
+    match tokens.next() {
+        Some((Token::Equal, _)) => {}
+        _ => return Err(anyhow!("Expected an equals sign after the type alias name")),
+    }
+
+    // Consume all new line tokens
+    consume_all_newlines(tokens);
+
+    // Read the aliased types, which are separated by pipes for unions
+    let mut types = vec![];
+
+    loop {
+        match parse_type(tokens)? {
+            Some(data_type) => types.push(data_type),
+            None => return Err(anyhow!("Expected a type in the type alias")),
+        }
+
+        match tokens.peek() {
+            Some((Token::BitwiseOr, _)) => {
+                tokens.next(); // Consume the token
+
+                // Consume all new line tokens
+                consume_all_newlines(tokens);
+            }
+            _ => break,
+        }
+    }
+
+    Ok(TypeAlias { name, types, span })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Program, TypeAlias, parse, types::DataType};
+    use gneurshk_lexer::lex;
+
+    /// Helper function for testing the parse function
+    fn lex_then_parse(input: &'static str) -> Program {
+        let tokens = lex(input).expect("Failed to lex");
+
+        match parse(&mut tokens.clone()) {
+            Ok(result) => result,
+            Err(e) => panic!("Parsing error: {e}"),
+        }
+    }
+
+    #[test]
+    fn type_alias() {
+        let program = lex_then_parse("type Meters = Float64");
+
+        assert_eq!(
+            program,
+            Program {
+                imports: vec![],
+                type_aliases: vec![TypeAlias {
+                    name: "Meters".to_string(),
+                    types: vec![DataType::Float64],
+                    span: 5..11,
+                }],
+                functions: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn union_type_alias() {
+        let program = lex_then_parse("type Id = Int64 | String");
+
+        assert_eq!(
+            program,
+            Program {
+                imports: vec![],
+                type_aliases: vec![TypeAlias {
+                    name: "Id".to_string(),
+                    types: vec![DataType::Int64, DataType::String],
+                    span: 5..7,
+                }],
+                functions: vec![],
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn missing_aliased_type() {
+        let _ = lex_then_parse("type Meters =");
+    }
+}
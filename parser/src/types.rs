@@ -1,5 +1,6 @@
 use anyhow::{Result, anyhow};
 use gneurshk_lexer::{TokenStream, tokens::Token};
+use std::fmt::{self, Display, Formatter};
 use std::str::FromStr;
 use strum_macros::EnumString;
 
@@ -35,15 +36,145 @@ pub enum DataType {
     Boolean,
     #[strum(to_string = "{0}")]
     Custom(String),
+    /// A value that might be missing, written as `Int32?`
+    #[strum(to_string = "{0}?", default_with = "default_inner_type")]
+    Optional(Box<DataType>),
+    /// Either a value or an error, written as `Result[Int32, String]`
+    #[strum(to_string = "Result[{value}, {error}]")]
+    Result {
+        #[strum(default_with = "default_inner_type")]
+        value: Box<DataType>,
+        #[strum(default_with = "default_inner_type")]
+        error: Box<DataType>,
+    },
+    /// A function value that can be stored and called later, written as `(Int32) -> Int32`
+    #[strum(transparent, serialize = "()")]
+    Function(FunctionType),
+}
+
+/// The parameter and return types of a function value
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct FunctionType {
+    pub params: Vec<DataType>,
+    pub return_type: Option<Box<DataType>>,
+}
+
+impl Display for FunctionType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let params = self
+            .params
+            .iter()
+            .map(|param| param.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        match &self.return_type {
+            Some(return_type) => write!(f, "({params}) -> {return_type}"),
+            None => write!(f, "({params})"),
+        }
+    }
+}
+
+/// Types that wrap other types are never parsed from a single name, so this is only a placeholder
+fn default_inner_type() -> Box<DataType> {
+    Box::new(DataType::Int32)
 }
 
 pub(crate) fn parse_type(tokens: &mut TokenStream) -> Result<Option<DataType>> {
-    if let Some((Token::Word(name), _)) = tokens.next() {
-        match DataType::from_str(name.as_str()) {
-            Ok(primitive) => Ok(Some(primitive)),
-            Err(_) => Ok(Some(DataType::Custom(name))),
+    let data_type = match tokens.next() {
+        Some((Token::OpenParen, _)) => parse_function_type(tokens)?,
+        Some((Token::Word(name), _))
+            if name == "Result" && matches!(tokens.peek(), Some((Token::OpenBracket, _))) =>
+        {
+            parse_result_type(tokens)?
         }
+        Some((Token::Word(name), _)) => match DataType::from_str(name.as_str()) {
+            Ok(primitive) => primitive,
+            Err(_) => DataType::Custom(name),
+        },
+        _ => return Err(anyhow!("Expected a type name")),
+    };
+
+    // Check if the type is optional
+    if let Some((Token::QuestionMark, _)) = tokens.peek() {
+        tokens.next(); // Consume the question mark
+
+        return Ok(Some(DataType::Optional(Box::new(data_type))));
+    }
+
+    Ok(Some(data_type))
+}
+
+/// Parses the parameter and return types of a function, such as `Int32) -> Int32` in `(Int32) -> Int32`
+fn parse_function_type(tokens: &mut TokenStream) -> Result<DataType> {
+    let mut params = vec![];
+
+    // Handle empty parameter list
+    if let Some((Token::CloseParen, _)) = tokens.peek() {
+        tokens.next(); // Consume the closing parenthesis
     } else {
-        Err(anyhow!("Expected a type name"))
+        loop {
+            let param = parse_type(tokens)?.ok_or_else(|| anyhow!("Expected a parameter type"))?;
+            params.push(param);
+
+            // Check for comma or closing parenthesis
+            match tokens.next() {
+                Some((Token::Comma, _)) => {}
+                Some((Token::CloseParen, _)) => break,
+                _ => {
+                    return Err(anyhow!(
+                        "Expected a comma or closing parenthesis after the parameter type"
+                    ));
+                }
+            }
+        }
+    }
+
+    // Functions without an arrow don't return a value
+    let return_type = match tokens.peek() {
+        Some((Token::Arrow, _)) => {
+            tokens.next(); // Consume the Arrow token
+
+            let return_type =
+                parse_type(tokens)?.ok_or_else(|| anyhow!("Expected a return type"))?;
+
+            Some(Box::new(return_type))
+        }
+        _ => None,
+    };
+
+    Ok(DataType::Function(FunctionType {
+        params,
+        return_type,
+    }))
+}
+
+/// Parses the value and error types of a result, such as `[Int32, String]` in `Result[Int32, String]`
+fn parse_result_type(tokens: &mut TokenStream) -> Result<DataType> {
+    tokens.next(); // Consume the '[' token
+
+    let value =
+        parse_type(tokens)?.ok_or_else(|| anyhow!("Expected the value type of a Result"))?;
+
+    match tokens.next() {
+        Some((Token::Comma, _)) => {}
+        _ => {
+            return Err(anyhow!(
+                "Expected ',' between the value and error types of a Result"
+            ));
+        }
     }
+
+    let error =
+        parse_type(tokens)?.ok_or_else(|| anyhow!("Expected the error type of a Result"))?;
+
+    match tokens.next() {
+        Some((Token::CloseBracket, _)) => {}
+        _ => return Err(anyhow!("Expected ']' after the error type of a Result")),
+    }
+
+    Ok(DataType::Result {
+        value: Box::new(value),
+        error: Box::new(error),
+    })
 }
@@ -11,8 +11,8 @@ pub fn parse_variable_declaration(tokens: &mut TokenStream) -> Result<Stmt> {
     };
 
     // Read variable name
-    let name = match tokens.next() {
-        Some((Token::Word(name), _)) => name,
+    let (name, span) = match tokens.next() {
+        Some((Token::Word(name), span)) => (name, span),
         _ => return Err(anyhow!("Expected variable name")),
     };
 
@@ -40,12 +40,14 @@ pub fn parse_variable_declaration(tokens: &mut TokenStream) -> Result<Stmt> {
                     name,
                     data_type,
                     value: Some(init_value),
+                    span,
                 }))
             } else {
                 Ok(Stmt::VariableDeclaration(VariableDeclaration::Constant {
                     name,
                     data_type,
                     value: init_value,
+                    span,
                 }))
             }
         }
@@ -62,6 +64,7 @@ pub fn parse_variable_declaration(tokens: &mut TokenStream) -> Result<Stmt> {
                     name,
                     data_type,
                     value: None,
+                    span,
                 }))
             } else {
                 Err(anyhow!("Constants must have an initial value"))
@@ -74,7 +77,7 @@ pub fn parse_variable_declaration(tokens: &mut TokenStream) -> Result<Stmt> {
 mod tests {
     use crate::{
         BinaryExpression, BinaryOperator, Block, Expression, FunctionDeclaration, IntegerLit,
-        Program, Stmt, VariableDeclaration, parse, types::DataType,
+        NoneLit, Program, Stmt, VariableDeclaration, parse, types::DataType,
     };
     use gneurshk_lexer::lex;
 
@@ -119,18 +122,23 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::VariableDeclaration(VariableDeclaration::Mutable {
                             name: "apple".to_string(),
+                            span: 22..27,
                             data_type: None,
                             value: None
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -145,18 +153,23 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::VariableDeclaration(VariableDeclaration::Mutable {
                             name: "pepper".to_string(),
+                            span: 22..28,
                             data_type: Some(DataType::Int32),
                             value: None
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -171,21 +184,26 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::VariableDeclaration(VariableDeclaration::Mutable {
                             name: "potatoes".to_string(),
+                            span: 22..30,
                             data_type: Some(DataType::Int32),
                             value: Some(Expression::Integer(IntegerLit {
                                 value: 5,
                                 span: 40..41
                             }))
                         })],
-                    }),
+                    })),
                 }],
             }
         );
@@ -200,14 +218,19 @@ mod tests {
             stmt,
             Program {
                 imports: vec![],
+                type_aliases: vec![],
                 functions: vec![FunctionDeclaration {
                     annotations: vec![],
                     name: "main".to_string(),
+                    span: 5..9,
                     params: vec![],
                     return_type: None,
-                    block: Box::new(Block {
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
                         body: vec![Stmt::VariableDeclaration(VariableDeclaration::Mutable {
                             name: "canned_corn".to_string(),
+                            span: 22..33,
                             data_type: None,
                             value: Some(Expression::BinaryExpression(BinaryExpression {
                                 left: Box::new(Expression::Integer(IntegerLit {
@@ -221,7 +244,38 @@ mod tests {
                                 operator: BinaryOperator::Add
                             }))
                         })],
-                    }),
+                    })),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn optional_type_with_none() {
+        let source = include_str!("../tests/variables/optional_type_with_none.iv");
+        let stmt = lex_then_parse(source);
+
+        assert_eq!(
+            stmt,
+            Program {
+                imports: vec![],
+                type_aliases: vec![],
+                functions: vec![FunctionDeclaration {
+                    annotations: vec![],
+                    name: "main".to_string(),
+                    span: 5..9,
+                    params: vec![],
+                    return_type: None,
+                    variadic: false,
+                    type_params: vec![],
+                    block: Some(Box::new(Block {
+                        body: vec![Stmt::VariableDeclaration(VariableDeclaration::Mutable {
+                            name: "cherry".to_string(),
+                            span: 22..28,
+                            data_type: Some(DataType::Optional(Box::new(DataType::Int32))),
+                            value: Some(Expression::None(NoneLit { span: 39..43 }))
+                        })],
+                    })),
                 }],
             }
         );